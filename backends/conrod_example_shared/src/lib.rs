@@ -57,6 +57,7 @@ pub fn theme() -> conrod_core::Theme {
         widget_styling: conrod_core::theme::StyleMap::default(),
         mouse_drag_threshold: 0.0,
         double_click_threshold: std::time::Duration::from_millis(500),
+        ..conrod_core::Theme::default()
     }
 }
 
@@ -71,8 +72,6 @@ widget_ids! {
         // Shapes.
         shapes_canvas,
         rounded_rectangle,
-        shapes_left_col,
-        shapes_right_col,
         shapes_title,
         line,
         point_path,
@@ -154,58 +153,51 @@ pub fn gui(ui: &mut conrod_core::UiCell, ids: &Ids, app: &mut DemoApp) {
         .font_size(SUBTITLE_SIZE)
         .set(ids.shapes_title, ui);
 
-    // Lay out the shapes in two horizontal columns.
-    //
-    // TODO: Have conrod provide an auto-flowing, fluid-list widget that is more adaptive for these
-    // sorts of situations.
-    widget::Canvas::new()
+    // Lay out the shapes from left to right, wrapping them onto new rows as the width of the
+    // canvas is exhausted.
+    let mut shapes = widget::Flow::new()
         .down(0.0)
         .align_middle_x_of(ids.canvas)
         .kid_area_w_of(ids.canvas)
-        .h(360.0)
-        .color(conrod_core::color::TRANSPARENT)
         .pad(MARGIN)
-        .flow_down(&[
-            (ids.shapes_left_col, widget::Canvas::new()),
-            (ids.shapes_right_col, widget::Canvas::new()),
-        ])
+        .spacing(SHAPE_GAP, SHAPE_GAP)
         .set(ids.shapes_canvas, ui);
 
-    let shapes_canvas_rect = ui.rect_of(ids.shapes_canvas).unwrap();
-    let w = shapes_canvas_rect.w();
-    let h = shapes_canvas_rect.h() * 5.0 / 6.0;
-    let radius = 10.0;
-    widget::RoundedRectangle::fill([w, h], radius)
-        .color(conrod_core::color::CHARCOAL.alpha(0.25))
-        .middle_of(ids.shapes_canvas)
-        .set(ids.rounded_rectangle, ui);
-
     let start = [-40.0, -40.0];
     let end = [40.0, 40.0];
-    widget::Line::centred(start, end).mid_left_of(ids.shapes_left_col).set(ids.line, ui);
+    shapes.set(widget::Line::centred(start, end), ids.line, ui);
 
     let left = [-40.0, -40.0];
     let top = [0.0, 40.0];
     let right = [40.0, -40.0];
     let points = once(left).chain(once(top)).chain(once(right));
-    widget::PointPath::centred(points).right(SHAPE_GAP).set(ids.point_path, ui);
+    shapes.set(widget::PointPath::centred(points), ids.point_path, ui);
 
-    widget::Rectangle::fill([80.0, 80.0]).right(SHAPE_GAP).set(ids.rectangle_fill, ui);
+    shapes.set(widget::Rectangle::fill([80.0, 80.0]), ids.rectangle_fill, ui);
 
-    widget::Rectangle::outline([80.0, 80.0]).right(SHAPE_GAP).set(ids.rectangle_outline, ui);
+    shapes.set(widget::Rectangle::outline([80.0, 80.0]), ids.rectangle_outline, ui);
 
     let bl = [-40.0, -40.0];
     let tl = [-20.0, 40.0];
     let tr = [20.0, 40.0];
     let br = [40.0, -40.0];
     let points = once(bl).chain(once(tl)).chain(once(tr)).chain(once(br));
-    widget::Polygon::centred_fill(points).mid_left_of(ids.shapes_right_col).set(ids.trapezoid, ui);
+    shapes.set(widget::Polygon::centred_fill(points), ids.trapezoid, ui);
 
-    widget::Oval::fill([40.0, 80.0]).right(SHAPE_GAP + 20.0).align_middle_y().set(ids.oval_fill, ui);
+    shapes.set(widget::Oval::fill([40.0, 80.0]), ids.oval_fill, ui);
 
-    widget::Oval::outline([80.0, 40.0]).right(SHAPE_GAP + 20.0).align_middle_y().set(ids.oval_outline, ui);
+    shapes.set(widget::Oval::outline([80.0, 40.0]), ids.oval_outline, ui);
 
-    widget::Circle::fill(40.0).right(SHAPE_GAP).align_middle_y().set(ids.circle, ui);
+    shapes.set(widget::Circle::fill(40.0), ids.circle, ui);
+
+    // Now that the height of the shapes is known, draw a backdrop behind them.
+    let shapes_wh = ui.wh_of(ids.shapes_canvas).unwrap();
+    let radius = 10.0;
+    widget::RoundedRectangle::fill(shapes_wh, radius)
+        .middle_of(ids.shapes_canvas)
+        .depth(1.0)
+        .color(conrod_core::color::CHARCOAL.alpha(0.25))
+        .set(ids.rounded_rectangle, ui);
 
 
     /////////////////
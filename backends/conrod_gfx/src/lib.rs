@@ -16,7 +16,7 @@ use conrod_core::{
     color,
     image,
     render,
-    text::{rt, GlyphCache},
+    text::{self, rt, GlyphCache, GlyphCacheMode},
 };
 
 /// A `Command` describing a step in the drawing process.
@@ -37,18 +37,25 @@ pub struct Commands<'a> {
 /// A `Command` for drawing to the target.
 ///
 /// Each variant describes how to draw the contents of the vertex buffer.
+///
+/// New kinds of drawing may be added in the future, so matches on `Draw` must include a
+/// wildcard arm.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
     Image(image::Id, &'a [Vertex]),
     /// A range of vertices representing plain triangles.
     Plain(&'a [Vertex]),
+    /// A range of vertices representing triangles textured with the RGBA bitmap glyph atlas.
+    BitmapGlyphs(&'a [Vertex]),
 }
 
 enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
     Plain(std::ops::Range<usize>),
+    BitmapGlyphs(std::ops::Range<usize>),
     Scizzor(gfx::Rect),
 }
 
@@ -58,6 +65,8 @@ pub const MODE_TEXT: u32 = 0;
 pub const MODE_IMAGE: u32 = 1;
 /// Ignore `tex` and draw simple, colored 2D geometry.
 pub const MODE_GEOMETRY: u32 = 2;
+/// Draw text from the signed distance field text cache texture `tex` in the fragment shader.
+pub const MODE_TEXT_SDF: u32 = 3;
 
 const FRAGMENT_SHADER: &'static [u8] = b"
     #version 140
@@ -81,6 +90,13 @@ const FRAGMENT_SHADER: &'static [u8] = b"
         // 2D Geometry
         } else if (v_Mode == uint(2)) {
             f_Color = v_Color;
+
+        // Signed Distance Field Text
+        } else if (v_Mode == uint(3)) {
+            float dist = texture(t_Color, v_Uv).a;
+            float width = fwidth(dist);
+            float alpha = smoothstep(0.5 - width, 0.5 + width, dist);
+            f_Color = v_Color * vec4(1.0, 1.0, 1.0, alpha);
         }
     }
 ";
@@ -150,8 +166,12 @@ use self::defines::*;
 pub struct Renderer<'a, R: Resources> {
     pipeline: PipelineState<R, pipe::Meta>,
     glyph_cache: GlyphCache<'a>,
+    glyph_cache_mode: GlyphCacheMode,
     cache_tex: gfx::handle::Texture<R, SurfaceFormat>,
     cache_tex_view: gfx::handle::ShaderResourceView<R, [f32; 4]>,
+    bitmap_atlas: text::bitmap::Atlas,
+    bitmap_tex: gfx::handle::Texture<R, SurfaceFormat>,
+    bitmap_tex_view: gfx::handle::ShaderResourceView<R, [f32; 4]>,
     data: pipe::Data<R>,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
@@ -165,6 +185,20 @@ impl<'a, R: Resources> Renderer<'a, R> {
                   dpi_factor: f64)
                   -> Result<Self, RendererCreationError>
         where F: Factory<R>,
+    {
+        Self::with_glyph_cache_mode(factory, rtv, dpi_factor, GlyphCacheMode::Coverage)
+    }
+
+    /// Create a new renderer whose glyph cache caches glyphs using the given `glyph_cache_mode`.
+    ///
+    /// Using `GlyphCacheMode::Sdf` allows for a single cached glyph to be drawn at any scale,
+    /// which is useful for text that is frequently zoomed.
+    pub fn with_glyph_cache_mode<F>(factory: &mut F,
+                                    rtv: &RenderTargetView<R, ColorFormat>,
+                                    dpi_factor: f64,
+                                    glyph_cache_mode: GlyphCacheMode)
+                                    -> Result<Self, RendererCreationError>
+        where F: Factory<R>,
     {
         let sampler_info = texture::SamplerInfo::new(
             texture::FilterMethod::Bilinear,
@@ -195,30 +229,31 @@ impl<'a, R: Resources> Renderer<'a, R> {
             },
             pipe::new())?;
 
-        let (glyph_cache, cache_tex, cache_tex_view) = {
+        let (glyph_cache, cache_tex, cache_tex_view, bitmap_atlas, bitmap_tex, bitmap_tex_view) = {
             let width = (width as f64 * dpi_factor) as u32;
             let height = (height as f64 * dpi_factor) as u32;
 
-            const SCALE_TOLERANCE: f32 = 0.1;
-            const POSITION_TOLERANCE: f32 = 0.1;
-
-            let cache = GlyphCache::builder()
-                .dimensions(width, height)
-                .scale_tolerance(SCALE_TOLERANCE)
-                .position_tolerance(POSITION_TOLERANCE)
-                .build();
+            let cache = glyph_cache_mode.cache_builder(width, height).build();
 
             let data = vec![0; (width * height * 4) as usize];
 
             let (texture, texture_view) = create_texture(factory, width, height, &data);
 
-            (cache, texture, texture_view)
+            // Color bitmap glyphs are cached within their own RGBA atlas texture.
+            let bitmap_atlas = text::bitmap::Atlas::new(width, height);
+            let (bitmap_texture, bitmap_texture_view) = create_texture(factory, width, height, &data);
+
+            (cache, texture, texture_view, bitmap_atlas, bitmap_texture, bitmap_texture_view)
         };
         Ok(Renderer {
             pipeline,
             glyph_cache,
+            glyph_cache_mode,
             cache_tex,
             cache_tex_view,
+            bitmap_atlas,
+            bitmap_tex,
+            bitmap_tex_view,
             data,
             commands: vec![],
             vertices: vec![],
@@ -245,7 +280,16 @@ impl<'a, R: Resources> Renderer<'a, R> {
         where P: render::PrimitiveWalker,
               C: gfx::CommandBuffer<R>,
     {
-        let Renderer { ref mut commands, ref mut vertices, ref mut glyph_cache, ref mut cache_tex, .. } = *self;
+        let Renderer {
+            ref mut commands,
+            ref mut vertices,
+            ref mut glyph_cache,
+            glyph_cache_mode,
+            ref mut cache_tex,
+            ref mut bitmap_atlas,
+            ref mut bitmap_tex,
+            ..
+        } = *self;
 
         commands.clear();
         vertices.clear();
@@ -406,17 +450,27 @@ impl<'a, R: Resources> Renderer<'a, R> {
                 render::PrimitiveKind::Text { color, text, font_id } => {
                     switch_to_plain_state!();
 
+                    let bitmap_source = text.bitmap_source();
                     let positioned_glyphs = text.positioned_glyphs(dpi_factor as f32);
 
-                    // Queue the glyphs to be cached
+                    // Queue the glyphs to be cached, separating out any color bitmap glyphs.
+                    let mut has_bitmap_glyphs = false;
                     for glyph in positioned_glyphs {
-                        glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+                        if let Some(source) = bitmap_source {
+                            if bitmap_atlas.queue_glyph(font_id.index(), glyph, source) {
+                                has_bitmap_glyphs = true;
+                                continue;
+                            }
+                        }
+                        glyph_cache_mode.queue_glyph(glyph_cache, font_id.index(), glyph);
                     }
 
                     glyph_cache.cache_queued(|rect, data| {
                         let offset = [rect.min.x as u16, rect.min.y as u16];
                         let size = [rect.width() as u16, rect.height() as u16];
 
+                        // Convert the coverage data to a distance field if necessary.
+                        let data = glyph_cache_mode.glyph_data(data, rect.width(), rect.height());
                         let new_data = data.iter().map(|x| [255, 255, 255, *x]).collect::<Vec<_>>();
 
                         update_texture(encoder, &cache_tex, offset, size, &new_data);
@@ -427,32 +481,75 @@ impl<'a, R: Resources> Renderer<'a, R> {
                     let origin = rt::point(0.0, 0.0);
 
                     // A closure to convert RustType rects to GL rects
-                    let to_gl_rect = |screen_rect: rt::Rect<i32>| rt::Rect {
+                    let to_gl_rect = |screen_rect: rt::Rect<f32>| rt::Rect {
                         min: origin
-                            + (rt::vector(screen_rect.min.x as f32 / screen_w - 0.5,
-                                          1.0 - screen_rect.min.y as f32 / screen_h - 0.5)) * 2.0,
+                            + (rt::vector(screen_rect.min.x / screen_w - 0.5,
+                                          1.0 - screen_rect.min.y / screen_h - 0.5)) * 2.0,
                         max: origin
-                            + (rt::vector(screen_rect.max.x as f32 / screen_w - 0.5,
-                                          1.0 - screen_rect.max.y as f32 / screen_h - 0.5)) * 2.0,
+                            + (rt::vector(screen_rect.max.x / screen_w - 0.5,
+                                          1.0 - screen_rect.max.y / screen_h - 0.5)) * 2.0,
+                    };
+
+                    let vertex_mode = match glyph_cache_mode {
+                        GlyphCacheMode::Coverage => MODE_TEXT,
+                        GlyphCacheMode::Sdf(_) => MODE_TEXT_SDF,
+                    };
+
+                    // Push the two triangles for a glyph's quad.
+                    let push_glyph = |vertices: &mut Vec<Vertex>,
+                                      uv_rect: rt::Rect<f32>,
+                                      screen_rect: rt::Rect<f32>,
+                                      color: [f32; 4],
+                                      mode: u32| {
+                        let gl_rect = to_gl_rect(screen_rect);
+                        let v = |p, t| Vertex {
+                            pos: p,
+                            uv: t,
+                            color: color,
+                            mode: mode,
+                        };
+                        let mut push_v = |p, t| vertices.push(v(p, t));
+                        push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
+                        push_v([gl_rect.min.x, gl_rect.min.y], [uv_rect.min.x, uv_rect.min.y]);
+                        push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
+                        push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
+                        push_v([gl_rect.max.x, gl_rect.max.y], [uv_rect.max.x, uv_rect.max.y]);
+                        push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
                     };
 
                     for g in positioned_glyphs {
-                        if let Ok(Some((uv_rect, screen_rect))) = glyph_cache.rect_for(cache_id, g) {
-                            let gl_rect = to_gl_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                pos: p,
-                                uv: t,
-                                color: color,
-                                mode: MODE_TEXT,
-                            };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
-                            push_v([gl_rect.min.x, gl_rect.min.y], [uv_rect.min.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.max.y], [uv_rect.max.x, uv_rect.max.y]);
-                            push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
+                        if let Ok(Some((uv_rect, screen_rect))) =
+                            glyph_cache_mode.rect_for(glyph_cache, cache_id, g)
+                        {
+                            push_glyph(vertices, uv_rect, screen_rect, color, vertex_mode);
+                        }
+                    }
+
+                    // Color bitmap glyphs are drawn untinted from the RGBA atlas texture, so they
+                    // require their own command.
+                    if has_bitmap_glyphs {
+                        bitmap_atlas.cache_queued(|rect, data| {
+                            let offset = [rect.min.x as u16, rect.min.y as u16];
+                            let size = [rect.width() as u16, rect.height() as u16];
+                            let new_data = data.chunks(4)
+                                .map(|p| [p[0], p[1], p[2], p[3]])
+                                .collect::<Vec<_>>();
+                            update_texture(encoder, &bitmap_tex, offset, size, &new_data);
+                        }).unwrap();
+
+                        if let State::Plain { start } = current_state {
+                            commands.push(PreparedCommand::Plain(start..vertices.len()));
+                        }
+
+                        let start = vertices.len();
+                        let white = [1.0; 4];
+                        for g in positioned_glyphs {
+                            if let Some((uv_rect, screen_rect)) = bitmap_atlas.rect_for(cache_id, g) {
+                                push_glyph(vertices, uv_rect, screen_rect, white, MODE_IMAGE);
+                            }
                         }
+                        commands.push(PreparedCommand::BitmapGlyphs(start..vertices.len()));
+                        current_state = State::Plain { start: vertices.len() };
                     }
                 }
 
@@ -557,7 +654,7 @@ impl<'a, R: Resources> Renderer<'a, R> {
         where F: Factory<R>,
               C: gfx::CommandBuffer<R>,
     {
-        let Renderer { ref pipeline, ref data, ref cache_tex_view, .. } = *self;
+        let Renderer { ref pipeline, ref data, ref cache_tex_view, ref bitmap_tex_view, .. } = *self;
 
         let mut data = data.clone();
 
@@ -578,6 +675,14 @@ impl<'a, R: Resources> Renderer<'a, R> {
                         encoder.draw(&slice, &pipeline, &data);
                     }
 
+                    // Draw color bitmap glyphs from the RGBA atlas texture.
+                    Draw::BitmapGlyphs(verts) => {
+                        data.color.0 = bitmap_tex_view.clone();
+                        let (vbuf, slice) = factory.create_vertex_buffer_with_slice(&verts, ());
+                        data.vbuf = vbuf;
+                        encoder.draw(&slice, &pipeline, &data);
+                    }
+
                     // Draw an image whose texture data lies within the `image_map` at the
                     // given `id`.
                     Draw::Image(image_id, verts) => {
@@ -705,6 +810,8 @@ impl<'a> Iterator for Commands<'a> {
             PreparedCommand::Scizzor(scizzor) => Command::Scizzor(scizzor),
             PreparedCommand::Plain(ref range) =>
                 Command::Draw(Draw::Plain(&vertices[range.clone()])),
+            PreparedCommand::BitmapGlyphs(ref range) =>
+                Command::Draw(Draw::BitmapGlyphs(&vertices[range.clone()])),
             PreparedCommand::Image(id, ref range) =>
                 Command::Draw(Draw::Image(id, &vertices[range.clone()])),
         })
@@ -25,25 +25,37 @@ pub enum Command<'a> {
 /// A `Command` for drawing to the target.
 ///
 /// Each variant describes how to draw the contents of the vertex buffer.
+///
+/// New kinds of drawing may be added in the future, so matches on `Draw` must include a
+/// wildcard arm.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
     Image(image::Id, &'a [Vertex]),
     /// A range of vertices representing plain triangles.
     Plain(&'a [Vertex]),
+    /// A range of vertices representing triangles textured with the RGBA bitmap glyph atlas.
+    BitmapGlyphs(&'a [Vertex]),
 }
 
 enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
     Plain(std::ops::Range<usize>),
+    BitmapGlyphs(std::ops::Range<usize>),
     Scizzor(glium::Rect),
 }
 
 /// A rusttype `GlyphCache` along with a `glium::texture::Texture2d` for caching text on the `GPU`.
+///
+/// Color bitmap glyphs are cached separately within an RGBA `text::bitmap::Atlas` texture.
 pub struct GlyphCache {
     cache: text::GlyphCache<'static>,
     texture: glium::texture::Texture2d,
+    mode: text::GlyphCacheMode,
+    bitmap_atlas: text::bitmap::Atlas,
+    bitmap_texture: glium::texture::Texture2d,
 }
 
 /// A type used for translating `render::Primitives` into `Command`s that indicate how to draw the
@@ -87,6 +99,7 @@ pub struct Vertex {
     /// `0` for rendering text.
     /// `1` for rendering an image.
     /// `2` for rendering non-textured 2D geometry.
+    /// `3` for rendering text from a signed distance field glyph cache.
     ///
     /// If any other value is given, the fragment shader will not output any color.
     pub mode: u32,
@@ -118,6 +131,8 @@ pub const MODE_TEXT: u32 = 0;
 pub const MODE_IMAGE: u32 = 1;
 /// Ignore `tex` and draw simple, colored 2D geometry.
 pub const MODE_GEOMETRY: u32 = 2;
+/// Draw text from the signed distance field text cache texture `tex` in the fragment shader.
+pub const MODE_TEXT_SDF: u32 = 3;
 
 
 /// The vertex shader used within the `glium::Program` for OpenGL.
@@ -162,6 +177,13 @@ pub const FRAGMENT_SHADER_120: &'static str = "
         // 2D Geometry
         } else if (v_mode == 2.0) {
             gl_FragColor = v_color;
+
+        // Signed Distance Field Text
+        } else if (v_mode == 3.0) {
+            float dist = texture2D(tex, v_tex_coords).r;
+            float width = fwidth(dist);
+            float alpha = smoothstep(0.5 - width, 0.5 + width, dist);
+            gl_FragColor = v_color * vec4(1.0, 1.0, 1.0, alpha);
         }
     }
 ";
@@ -210,6 +232,13 @@ pub const FRAGMENT_SHADER_140: &'static str = "
         // 2D Geometry
         } else if (v_mode == uint(2)) {
             f_color = v_color;
+
+        // Signed Distance Field Text
+        } else if (v_mode == uint(3)) {
+            float dist = texture(tex, v_tex_coords).r;
+            float width = fwidth(dist);
+            float alpha = smoothstep(0.5 - width, 0.5 + width, dist);
+            f_color = v_color * vec4(1.0, 1.0, 1.0, alpha);
         }
     }
 ";
@@ -260,6 +289,13 @@ pub const FRAGMENT_SHADER_300_ES: &'static str = "
         // 2D Geometry
         } else if (v_mode == uint(2)) {
             f_color = v_color;
+
+        // Signed Distance Field Text
+        } else if (v_mode == uint(3)) {
+            float dist = texture(tex, v_tex_coords).r;
+            float width = fwidth(dist);
+            float alpha = smoothstep(0.5 - width, 0.5 + width, dist);
+            f_color = v_color * vec4(1.0, 1.0, 1.0, alpha);
         }
     }
 ";
@@ -334,14 +370,8 @@ pub fn text_texture_uncompressed_float_format(opengl_version: &glium::Version) -
 }
 
 // Creating the rusttype glyph cache used within a `GlyphCache`.
-fn rusttype_glyph_cache(w: u32, h: u32) -> text::GlyphCache<'static> {
-    const SCALE_TOLERANCE: f32 = 0.1;
-    const POSITION_TOLERANCE: f32 = 0.1;
-    text::GlyphCache::builder()
-        .dimensions(w, h)
-        .scale_tolerance(SCALE_TOLERANCE)
-        .position_tolerance(POSITION_TOLERANCE)
-        .build()
+fn rusttype_glyph_cache(w: u32, h: u32, mode: text::GlyphCacheMode) -> text::GlyphCache<'static> {
+    mode.cache_builder(w, h).build()
 }
 
 // Create the texture used within a `GlyphCache` of the given size.
@@ -372,6 +402,20 @@ where
     glium::texture::Texture2d::with_format(facade, grey_image, format, no_mipmap)
 }
 
+// Create the RGBA texture used for the bitmap glyph atlas within a `GlyphCache`.
+fn bitmap_glyph_texture<F>(
+    facade: &F,
+    width: u32,
+    height: u32,
+) -> Result<glium::texture::Texture2d, glium::texture::TextureCreationError>
+where
+    F: glium::backend::Facade,
+{
+    let format = glium::texture::UncompressedFloatFormat::U8U8U8U8;
+    let no_mipmap = glium::texture::MipmapsOption::NoMipmap;
+    glium::texture::Texture2d::empty_with_format(facade, format, no_mipmap, width, height)
+}
+
 impl GlyphCache {
     /// Construct a **GlyphCache** with the given texture dimensions.
     ///
@@ -384,19 +428,43 @@ impl GlyphCache {
         width: u32,
         height: u32,
     ) -> Result<Self, glium::texture::TextureCreationError>
+    where
+        F: glium::backend::Facade,
+    {
+        Self::with_dimensions_and_mode(facade, width, height, text::GlyphCacheMode::Coverage)
+    }
+
+    /// Construct a **GlyphCache** with the given texture dimensions that caches glyphs using the
+    /// given `mode`.
+    ///
+    /// Using `GlyphCacheMode::Sdf` allows for a single cached glyph to be drawn at any scale,
+    /// which is useful for text that is frequently zoomed.
+    pub fn with_dimensions_and_mode<F>(
+        facade: &F,
+        width: u32,
+        height: u32,
+        mode: text::GlyphCacheMode,
+    ) -> Result<Self, glium::texture::TextureCreationError>
     where
         F: glium::backend::Facade,
     {
         // First, the rusttype `Cache` which performs the logic for rendering and laying out glyphs
         // in the cache.
-        let cache = rusttype_glyph_cache(width, height);
+        let cache = rusttype_glyph_cache(width, height, mode);
 
         // Now the texture to which glyphs will be rendered.
         let texture = glyph_cache_texture(facade, width, height)?;
 
+        // The atlas and texture for color bitmap glyphs.
+        let bitmap_atlas = text::bitmap::Atlas::new(width, height);
+        let bitmap_texture = bitmap_glyph_texture(facade, width, height)?;
+
         Ok(GlyphCache {
             cache: cache,
             texture: texture,
+            mode: mode,
+            bitmap_atlas: bitmap_atlas,
+            bitmap_texture: bitmap_texture,
         })
     }
 
@@ -414,6 +482,16 @@ impl GlyphCache {
     pub fn texture(&self) -> &glium::texture::Texture2d {
         &self.texture
     }
+
+    /// The mode with which glyphs are cached.
+    pub fn mode(&self) -> text::GlyphCacheMode {
+        self.mode
+    }
+
+    /// The RGBA texture used to cache color bitmap glyphs on the GPU.
+    pub fn bitmap_texture(&self) -> &glium::texture::Texture2d {
+        &self.bitmap_texture
+    }
 }
 
 
@@ -442,6 +520,21 @@ impl Renderer {
         Self::with_glyph_cache(facade, glyph_cache)
     }
 
+    /// Construct a new empty `Renderer` with the given glyph cache dimensions and glyph cache
+    /// `mode`.
+    pub fn with_glyph_cache_dimensions_and_mode<F>(
+        facade: &F,
+        width: u32,
+        height: u32,
+        mode: text::GlyphCacheMode,
+    ) -> Result<Self, RendererCreationError>
+    where
+        F: glium::backend::Facade,
+    {
+        let glyph_cache = GlyphCache::with_dimensions_and_mode(facade, width, height, mode)?;
+        Self::with_glyph_cache(facade, glyph_cache)
+    }
+
     // Construct a new **Renderer** that uses the given glyph cache for caching text.
     fn with_glyph_cache<F>(facade: &F, gc: GlyphCache) -> Result<Self, RendererCreationError>
     where
@@ -648,13 +741,27 @@ impl Renderer {
                 render::PrimitiveKind::Text { color, text, font_id } => {
                     switch_to_plain_state!();
 
+                    let bitmap_source = text.bitmap_source();
                     let positioned_glyphs = text.positioned_glyphs(dpi_factor as f32);
 
-                    let GlyphCache { ref mut cache, ref mut texture } = *glyph_cache;
+                    let GlyphCache {
+                        ref mut cache,
+                        ref mut texture,
+                        mode,
+                        ref mut bitmap_atlas,
+                        ref mut bitmap_texture,
+                    } = *glyph_cache;
 
-                    // Queue the glyphs to be cached.
+                    // Queue the glyphs to be cached, separating out any color bitmap glyphs.
+                    let mut has_bitmap_glyphs = false;
                     for glyph in positioned_glyphs.iter() {
-                        cache.queue_glyph(font_id.index(), glyph.clone());
+                        if let Some(source) = bitmap_source {
+                            if bitmap_atlas.queue_glyph(font_id.index(), glyph, source) {
+                                has_bitmap_glyphs = true;
+                                continue;
+                            }
+                        }
+                        mode.queue_glyph(cache, font_id.index(), glyph);
                     }
 
                     // Cache the glyphs on the GPU.
@@ -668,9 +775,11 @@ impl Renderer {
                             height: h,
                         };
 
+                        // Convert the coverage data to a distance field if necessary.
+                        let data = mode.glyph_data(data, w, h);
                         let data = match client_format {
                             // `rusttype` gives data in the `U8` format so we can use it directly.
-                            glium::texture::ClientFormat::U8 => std::borrow::Cow::Borrowed(data),
+                            glium::texture::ClientFormat::U8 => data,
                             // Otherwise we have to convert to the supported format.
                             glium::texture::ClientFormat::U8U8U8 => {
                                 text_data_u8u8u8.clear();
@@ -699,32 +808,80 @@ impl Renderer {
                     let cache_id = font_id.index();
 
                     let origin = text::rt::point(0.0, 0.0);
-                    let to_gl_rect = |screen_rect: text::rt::Rect<i32>| text::rt::Rect {
+                    let to_gl_rect = |screen_rect: text::rt::Rect<f32>| text::rt::Rect {
                         min: origin
-                            + (text::rt::vector(screen_rect.min.x as f32 / screen_w as f32 - 0.5,
-                                          1.0 - screen_rect.min.y as f32 / screen_h as f32 - 0.5)) * 2.0,
+                            + (text::rt::vector(screen_rect.min.x / screen_w as f32 - 0.5,
+                                          1.0 - screen_rect.min.y / screen_h as f32 - 0.5)) * 2.0,
                         max: origin
-                            + (text::rt::vector(screen_rect.max.x as f32 / screen_w as f32 - 0.5,
-                                          1.0 - screen_rect.max.y as f32 / screen_h as f32 - 0.5)) * 2.0
+                            + (text::rt::vector(screen_rect.max.x / screen_w as f32 - 0.5,
+                                          1.0 - screen_rect.max.y / screen_h as f32 - 0.5)) * 2.0
+                    };
+
+                    let vertex_mode = match mode {
+                        text::GlyphCacheMode::Coverage => MODE_TEXT,
+                        text::GlyphCacheMode::Sdf(_) => MODE_TEXT_SDF,
+                    };
+
+                    // Push the two triangles for a glyph's quad.
+                    let push_glyph = |vertices: &mut Vec<Vertex>,
+                                      uv_rect: text::rt::Rect<f32>,
+                                      screen_rect: text::rt::Rect<f32>,
+                                      color: [f32; 4],
+                                      mode: u32| {
+                        let gl_rect = to_gl_rect(screen_rect);
+                        let v = |p, t| Vertex {
+                            position: p,
+                            tex_coords: t,
+                            color: color,
+                            mode: mode,
+                        };
+                        let mut push_v = |p, t| vertices.push(v(p, t));
+                        push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
+                        push_v([gl_rect.min.x, gl_rect.min.y], [uv_rect.min.x, uv_rect.min.y]);
+                        push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
+                        push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
+                        push_v([gl_rect.max.x, gl_rect.max.y], [uv_rect.max.x, uv_rect.max.y]);
+                        push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
                     };
 
                     for g in positioned_glyphs {
-                        if let Ok(Some((uv_rect, screen_rect))) = cache.rect_for(cache_id, g) {
-                            let gl_rect = to_gl_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                position: p,
-                                tex_coords: t,
-                                color: color,
-                                mode: MODE_TEXT,
+                        if let Ok(Some((uv_rect, screen_rect))) = mode.rect_for(cache, cache_id, g) {
+                            push_glyph(vertices, uv_rect, screen_rect, color, vertex_mode);
+                        }
+                    }
+
+                    // Color bitmap glyphs are drawn untinted from the RGBA atlas texture, so they
+                    // require their own command.
+                    if has_bitmap_glyphs {
+                        bitmap_atlas.cache_queued(|rect, data| {
+                            let glium_rect = glium::Rect {
+                                left: rect.min.x,
+                                bottom: rect.min.y,
+                                width: rect.width(),
+                                height: rect.height(),
                             };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
-                            push_v([gl_rect.min.x, gl_rect.min.y], [uv_rect.min.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.min.y], [uv_rect.max.x, uv_rect.min.y]);
-                            push_v([gl_rect.max.x, gl_rect.max.y], [uv_rect.max.x, uv_rect.max.y]);
-                            push_v([gl_rect.min.x, gl_rect.max.y], [uv_rect.min.x, uv_rect.max.y]);
+                            let image = glium::texture::RawImage2d {
+                                data: std::borrow::Cow::Borrowed(data),
+                                width: rect.width(),
+                                height: rect.height(),
+                                format: glium::texture::ClientFormat::U8U8U8U8,
+                            };
+                            bitmap_texture.main_level().write(glium_rect, image);
+                        }).unwrap();
+
+                        if let State::Plain { start } = current_state {
+                            commands.push(PreparedCommand::Plain(start..vertices.len()));
+                        }
+
+                        let start = vertices.len();
+                        let white = [1.0; 4];
+                        for g in positioned_glyphs {
+                            if let Some((uv_rect, screen_rect)) = bitmap_atlas.rect_for(cache_id, g) {
+                                push_glyph(vertices, uv_rect, screen_rect, white, MODE_IMAGE);
+                            }
                         }
+                        commands.push(PreparedCommand::BitmapGlyphs(start..vertices.len()));
+                        current_state = State::Plain { start: vertices.len() };
                     }
                 },
 
@@ -835,10 +992,15 @@ impl Renderer {
     {
         let mut draw_params = draw_parameters();
         let no_indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+        // Distance fields must be interpolated in order to reconstruct smooth glyph edges.
+        let magnify_filter = match self.glyph_cache.mode() {
+            text::GlyphCacheMode::Coverage => glium::uniforms::MagnifySamplerFilter::Nearest,
+            text::GlyphCacheMode::Sdf(_) => glium::uniforms::MagnifySamplerFilter::Linear,
+        };
         let uniforms = uniform! {
             tex: self.glyph_cache.texture()
                 .sampled()
-                .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                .magnify_filter(magnify_filter)
                 .minify_filter(glium::uniforms::MinifySamplerFilter::Linear)
         };
 
@@ -861,6 +1023,20 @@ impl Renderer {
                         surface.draw(&vertex_buffer, no_indices, &self.program, &uniforms, &draw_params).unwrap();
                     },
 
+                    // Draw color bitmap glyphs from the RGBA atlas texture.
+                    //
+                    // Only submit the vertices if there is enough for at least one triangle.
+                    Draw::BitmapGlyphs(slice) => if slice.len() >= NUM_VERTICES_IN_TRIANGLE {
+                        let vertex_buffer = glium::VertexBuffer::new(facade, slice)?;
+                        let bitmap_uniforms = uniform! {
+                            tex: self.glyph_cache.bitmap_texture()
+                                .sampled()
+                                .magnify_filter(glium::uniforms::MagnifySamplerFilter::Linear)
+                                .minify_filter(glium::uniforms::MinifySamplerFilter::Linear)
+                        };
+                        surface.draw(&vertex_buffer, no_indices, &self.program, &bitmap_uniforms, &draw_params).unwrap();
+                    },
+
                     // Draw an image whose texture data lies within the `image_map` at the
                     // given `id`.
                     //
@@ -894,6 +1070,8 @@ impl<'a> Iterator for Commands<'a> {
             PreparedCommand::Scizzor(scizzor) => Command::Scizzor(scizzor),
             PreparedCommand::Plain(ref range) =>
                 Command::Draw(Draw::Plain(&vertices[range.clone()])),
+            PreparedCommand::BitmapGlyphs(ref range) =>
+                Command::Draw(Draw::BitmapGlyphs(&vertices[range.clone()])),
             PreparedCommand::Image(id, ref range) =>
                 Command::Draw(Draw::Image(id, &vertices[range.clone()])),
         })
@@ -144,6 +144,20 @@ fn main() {
                     .expect("failed to submit command for caching glyph");
 
             }
+            for cmd in cmds.bitmap_commands.drain(..) {
+                let buffer = cmds.glyph_cpu_buffer_pool.chunk(cmd.data.iter().cloned()).unwrap();
+                command_buffer_builder = command_buffer_builder
+                    .copy_buffer_to_image_dimensions(
+                        buffer,
+                        cmds.bitmap_glyph_texture.clone(),
+                        [cmd.offset[0], cmd.offset[1], 0],
+                        [cmd.size[0], cmd.size[1], 1],
+                        0,
+                        1,
+                        0
+                    )
+                    .expect("failed to submit command for caching bitmap glyph");
+            }
 
             let mut command_buffer_builder = command_buffer_builder
                 .begin_render_pass(
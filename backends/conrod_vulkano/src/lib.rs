@@ -7,7 +7,7 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::sync::Arc;
 
-use conrod_core::text::{rt, GlyphCache};
+use conrod_core::text::{self, rt, GlyphCache, GlyphCacheMode};
 use conrod_core::{color, image, render, Rect, Scalar};
 
 use vulkano::buffer::cpu_pool::CpuBufferPool;
@@ -46,18 +46,25 @@ pub struct Commands<'a> {
 /// A `Command` for drawing to the target.
 ///
 /// Each variant describes how to draw the contents of the vertex buffer.
+///
+/// New kinds of drawing may be added in the future, so matches on `Draw` must include a
+/// wildcard arm.
 #[derive(Clone, Debug)]
+#[non_exhaustive]
 pub enum Draw<'a> {
     /// A range of vertices representing triangles textured with the image in the
     /// image_map at the given `widget::Id`.
     Image(image::Id, &'a [Vertex]),
     /// A range of vertices representing plain triangles.
     Plain(&'a [Vertex]),
+    /// A range of vertices representing triangles textured with the RGBA bitmap glyph atlas.
+    BitmapGlyphs(&'a [Vertex]),
 }
 
 enum PreparedCommand {
     Image(image::Id, std::ops::Range<usize>),
     Plain(std::ops::Range<usize>),
+    BitmapGlyphs(std::ops::Range<usize>),
     Scizzor(Scissor),
 }
 
@@ -77,6 +84,8 @@ pub const MODE_TEXT: u32 = 0;
 pub const MODE_IMAGE: u32 = 1;
 /// Ignore `tex` and draw simple, colored 2D geometry.
 pub const MODE_GEOMETRY: u32 = 2;
+/// Draw text from the signed distance field text cache texture `tex` in the fragment shader.
+pub const MODE_TEXT_SDF: u32 = 3;
 
 mod vs {
     vulkano_shaders::shader! {
@@ -129,6 +138,13 @@ void main() {
     // 2D Geometry
     } else if (v_Mode == uint(2)) {
         Target0 = v_Color;
+
+    // Signed Distance Field Text
+    } else if (v_Mode == uint(3)) {
+        float dist = texture(t_Color, v_Uv).a;
+        float width = fwidth(dist);
+        float alpha = smoothstep(0.5 - width, 0.5 + width, dist);
+        Target0 = v_Color * vec4(1.0, 1.0, 1.0, alpha);
     }
 }
 "
@@ -157,6 +173,7 @@ pub struct Vertex {
     /// `0` for rendering text.
     /// `1` for rendering an image.
     /// `2` for rendering non-textured 2D geometry.
+    /// `3` for rendering text from a signed distance field glyph cache.
     ///
     /// If any other value is given, the fragment shader will not output any color.
     pub mode: u32,
@@ -169,8 +186,11 @@ impl_vertex!(Vertex, pos, uv, color, mode);
 pub struct Renderer {
     pipeline: Arc<GraphicsPipelineAbstract + Send + Sync>,
     glyph_cache: GlyphCache<'static>,
+    glyph_cache_mode: GlyphCacheMode,
     glyph_uploads: Arc<CpuBufferPool<[u8; 4]>>,
     glyph_cache_tex: Arc<StorageImage<R8G8B8A8Unorm>>,
+    bitmap_atlas: text::bitmap::Atlas,
+    bitmap_tex: Arc<StorageImage<R8G8B8A8Unorm>>,
     sampler: Arc<Sampler>,
     commands: Vec<PreparedCommand>,
     vertices: Vec<Vertex>,
@@ -185,6 +205,10 @@ pub struct GlyphCacheCommands {
     pub glyph_cpu_buffer_pool: Arc<CpuBufferPool<[u8; 4]>>,
     /// Commands for caching individual glyphs.
     pub commands: Vec<GlyphCacheCommand>,
+    /// The GPU image to which color bitmap glyphs are cached.
+    pub bitmap_glyph_texture: Arc<StorageImage<R8G8B8A8Unorm>>,
+    /// Commands for caching individual color bitmap glyphs within the `bitmap_glyph_texture`.
+    pub bitmap_commands: Vec<GlyphCacheCommand>,
 }
 
 /// An command for uploading an individual glyph.
@@ -255,6 +279,30 @@ impl Renderer {
         graphics_queue_family: QueueFamily<'a>,
         glyph_cache_dims: [u32; 2],
     ) -> Result<Self, RendererCreationError>
+    where
+        L: RenderPassDesc + RenderPassAbstract + Send + Sync + 'static,
+    {
+        Self::with_glyph_cache_dimensions_and_mode(
+            device,
+            subpass,
+            graphics_queue_family,
+            glyph_cache_dims,
+            GlyphCacheMode::Coverage,
+        )
+    }
+
+    /// Construct a new empty `Renderer` whose glyph cache caches glyphs using the given
+    /// `glyph_cache_mode`.
+    ///
+    /// Using `GlyphCacheMode::Sdf` allows for a single cached glyph to be drawn at any scale,
+    /// which is useful for text that is frequently zoomed.
+    pub fn with_glyph_cache_dimensions_and_mode<'a, L>(
+        device: Arc<Device>,
+        subpass: Subpass<L>,
+        graphics_queue_family: QueueFamily<'a>,
+        glyph_cache_dims: [u32; 2],
+        glyph_cache_mode: GlyphCacheMode,
+    ) -> Result<Self, RendererCreationError>
     where
         L: RenderPassDesc + RenderPassAbstract + Send + Sync + 'static,
     {
@@ -291,17 +339,10 @@ impl Renderer {
                 .build(device.clone())?
         );
 
-        let (glyph_cache, glyph_cache_tex) = {
+        let (glyph_cache, glyph_cache_tex, bitmap_atlas, bitmap_tex) = {
             let [width, height] = glyph_cache_dims;
 
-            const SCALE_TOLERANCE: f32 = 0.1;
-            const POSITION_TOLERANCE: f32 = 0.1;
-
-            let glyph_cache = GlyphCache::builder()
-                .dimensions(width, height)
-                .scale_tolerance(SCALE_TOLERANCE)
-                .position_tolerance(POSITION_TOLERANCE)
-                .build();
+            let glyph_cache = glyph_cache_mode.cache_builder(width, height).build();
 
             let glyph_cache_tex = StorageImage::with_usage(
                 device.clone(),
@@ -315,7 +356,21 @@ impl Renderer {
                 vec![graphics_queue_family],
             )?;
 
-            (glyph_cache, glyph_cache_tex)
+            // The atlas and image for color bitmap glyphs.
+            let bitmap_atlas = text::bitmap::Atlas::new(width, height);
+            let bitmap_tex = StorageImage::with_usage(
+                device.clone(),
+                Dimensions::Dim2d { width, height },
+                R8G8B8A8Unorm,
+                ImageUsage {
+                    transfer_destination: true,
+                    sampled: true,
+                    ..ImageUsage::none()
+                },
+                vec![graphics_queue_family],
+            )?;
+
+            (glyph_cache, glyph_cache_tex, bitmap_atlas, bitmap_tex)
         };
 
         let tex_descs = FixedSizeDescriptorSetsPool::new(pipeline.clone() as Arc<_>, 0);
@@ -324,8 +379,11 @@ impl Renderer {
         Ok(Renderer {
             pipeline: pipeline,
             glyph_cache,
+            glyph_cache_mode,
             glyph_uploads,
             glyph_cache_tex,
+            bitmap_atlas,
+            bitmap_tex,
             sampler,
             commands: Vec::new(),
             vertices: Vec::new(),
@@ -358,14 +416,18 @@ impl Renderer {
             ref mut commands,
             ref mut vertices,
             ref mut glyph_cache,
+            glyph_cache_mode,
             ref glyph_uploads,
             ref glyph_cache_tex,
+            ref mut bitmap_atlas,
+            ref bitmap_tex,
             ..
         } = *self;
 
         commands.clear();
         vertices.clear();
         let mut glyph_cache_commands = vec![];
+        let mut bitmap_commands = vec![];
 
         enum State {
             Image { image_id: image::Id, start: usize },
@@ -530,17 +592,27 @@ impl Renderer {
                 } => {
                     switch_to_plain_state!();
 
+                    let bitmap_source = text.bitmap_source();
                     let positioned_glyphs = text.positioned_glyphs(dpi_factor as f32);
 
-                    // Queue the glyphs to be cached
+                    // Queue the glyphs to be cached, separating out any color bitmap glyphs.
+                    let mut has_bitmap_glyphs = false;
                     for glyph in positioned_glyphs {
-                        glyph_cache.queue_glyph(font_id.index(), glyph.clone());
+                        if let Some(source) = bitmap_source {
+                            if bitmap_atlas.queue_glyph(font_id.index(), glyph, source) {
+                                has_bitmap_glyphs = true;
+                                continue;
+                            }
+                        }
+                        glyph_cache_mode.queue_glyph(glyph_cache, font_id.index(), glyph);
                     }
 
                     glyph_cache.cache_queued(|rect, data| {
                         let offset = [rect.min.x as u32, rect.min.y as u32];
                         let size = [rect.width() as u32, rect.height() as u32];
 
+                        // Convert the coverage data to a distance field if necessary.
+                        let data = glyph_cache_mode.glyph_data(data, rect.width(), rect.height());
                         let data = data
                             .iter()
                             .map(|x| [255, 255, 255, *x])
@@ -555,56 +627,102 @@ impl Renderer {
                     let origin = rt::point(0.0, 0.0);
 
                     // A closure to convert RustType rects to GL rects
-                    let to_vk_rect = |screen_rect: rt::Rect<i32>| rt::Rect {
+                    let to_vk_rect = |screen_rect: rt::Rect<f32>| rt::Rect {
                         min: origin
                             + (rt::vector(
-                                screen_rect.min.x as f32 / viewport_w - 0.5,
-                                screen_rect.min.y as f32 / viewport_h - 0.5,
+                                screen_rect.min.x / viewport_w - 0.5,
+                                screen_rect.min.y / viewport_h - 0.5,
                             )) * 2.0,
                         max: origin
                             + (rt::vector(
-                                screen_rect.max.x as f32 / viewport_w - 0.5,
-                                screen_rect.max.y as f32 / viewport_h - 0.5,
+                                screen_rect.max.x / viewport_w - 0.5,
+                                screen_rect.max.y / viewport_h - 0.5,
                             )) * 2.0,
                     };
 
+                    let vertex_mode = match glyph_cache_mode {
+                        GlyphCacheMode::Coverage => MODE_TEXT,
+                        GlyphCacheMode::Sdf(_) => MODE_TEXT_SDF,
+                    };
+
+                    // Push the two triangles for a glyph's quad.
+                    let push_glyph = |vertices: &mut Vec<Vertex>,
+                                      uv_rect: rt::Rect<f32>,
+                                      screen_rect: rt::Rect<f32>,
+                                      color: [f32; 4],
+                                      mode: u32| {
+                        let vk_rect = to_vk_rect(screen_rect);
+                        let v = |p, t| Vertex {
+                            pos: p,
+                            uv: t,
+                            color: color,
+                            mode: mode,
+                        };
+                        let mut push_v = |p, t| vertices.push(v(p, t));
+                        push_v(
+                            [vk_rect.min.x, vk_rect.max.y],
+                            [uv_rect.min.x, uv_rect.max.y],
+                        );
+                        push_v(
+                            [vk_rect.min.x, vk_rect.min.y],
+                            [uv_rect.min.x, uv_rect.min.y],
+                        );
+                        push_v(
+                            [vk_rect.max.x, vk_rect.min.y],
+                            [uv_rect.max.x, uv_rect.min.y],
+                        );
+                        push_v(
+                            [vk_rect.max.x, vk_rect.min.y],
+                            [uv_rect.max.x, uv_rect.min.y],
+                        );
+                        push_v(
+                            [vk_rect.max.x, vk_rect.max.y],
+                            [uv_rect.max.x, uv_rect.max.y],
+                        );
+                        push_v(
+                            [vk_rect.min.x, vk_rect.max.y],
+                            [uv_rect.min.x, uv_rect.max.y],
+                        );
+                    };
+
                     for g in positioned_glyphs {
-                        if let Ok(Some((uv_rect, screen_rect))) = glyph_cache.rect_for(cache_id, g)
+                        if let Ok(Some((uv_rect, screen_rect))) =
+                            glyph_cache_mode.rect_for(glyph_cache, cache_id, g)
                         {
-                            let vk_rect = to_vk_rect(screen_rect);
-                            let v = |p, t| Vertex {
-                                pos: p,
-                                uv: t,
-                                color: color,
-                                mode: MODE_TEXT,
-                            };
-                            let mut push_v = |p, t| vertices.push(v(p, t));
-                            push_v(
-                                [vk_rect.min.x, vk_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [vk_rect.min.x, vk_rect.min.y],
-                                [uv_rect.min.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.min.y],
-                                [uv_rect.max.x, uv_rect.min.y],
-                            );
-                            push_v(
-                                [vk_rect.max.x, vk_rect.max.y],
-                                [uv_rect.max.x, uv_rect.max.y],
-                            );
-                            push_v(
-                                [vk_rect.min.x, vk_rect.max.y],
-                                [uv_rect.min.x, uv_rect.max.y],
-                            );
+                            push_glyph(vertices, uv_rect, screen_rect, color, vertex_mode);
                         }
                     }
+
+                    // Color bitmap glyphs are drawn untinted from the RGBA atlas image, so they
+                    // require their own command.
+                    if has_bitmap_glyphs {
+                        bitmap_atlas.cache_queued(|rect, data| {
+                            let offset = [rect.min.x, rect.min.y];
+                            let size = [rect.width(), rect.height()];
+                            let data = data
+                                .chunks(4)
+                                .map(|p| [p[0], p[1], p[2], p[3]])
+                                .collect::<Vec<[u8; 4]>>();
+                            let cmd = GlyphCacheCommand { offset, size, data };
+                            bitmap_commands.push(cmd);
+                        })?;
+
+                        if let State::Plain { start } = current_state {
+                            commands.push(PreparedCommand::Plain(start..vertices.len()));
+                        }
+
+                        let start = vertices.len();
+                        let white = [1.0; 4];
+                        for g in positioned_glyphs {
+                            if let Some((uv_rect, screen_rect)) =
+                                bitmap_atlas.rect_for(cache_id, g)
+                            {
+                                push_glyph(vertices, uv_rect, screen_rect, white, MODE_IMAGE);
+                            }
+                        }
+                        commands.push(PreparedCommand::BitmapGlyphs(start..vertices.len()));
+                        current_state = State::Plain { start: vertices.len() };
+                    }
                 }
 
                 render::PrimitiveKind::Image {
@@ -711,6 +829,8 @@ impl Renderer {
             glyph_cache_texture: glyph_cache_tex.clone(),
             glyph_cpu_buffer_pool: glyph_uploads.clone(),
             commands: glyph_cache_commands,
+            bitmap_glyph_texture: bitmap_tex.clone(),
+            bitmap_commands: bitmap_commands,
         })
     }
 
@@ -750,6 +870,13 @@ impl Renderer {
                 .build()?
         );
 
+        let desc_bitmap = Arc::new(
+            self.tex_descs
+                .next()
+                .add_sampled_image(self.bitmap_tex.clone(), self.sampler.clone())?
+                .build()?
+        );
+
         let tex_descs = &mut self.tex_descs;
 
         let commands = Commands {
@@ -789,6 +916,23 @@ impl Renderer {
                         }
                     }
 
+                    // Draw color bitmap glyphs from the RGBA atlas image.
+                    Draw::BitmapGlyphs(verts) => {
+                        if verts.len() > 0 {
+                            let (vbuf, _vbuf_fut) = ImmutableBuffer::<[Vertex]>::from_iter(
+                                verts.iter().cloned(),
+                                BufferUsage::vertex_buffer(),
+                                queue.clone(),
+                            )?;
+                            draw_commands.push(DrawCommand {
+                                graphics_pipeline: self.pipeline.clone(),
+                                dynamic_state: dynamic_state(current_scissor.clone()),
+                                vertex_buffer: vbuf,
+                                descriptor_set: desc_bitmap.clone(),
+                            });
+                        }
+                    }
+
                     // Draw an image whose texture data lies within the `image_map` at the
                     // given `id`.
                     Draw::Image(image_id, verts) => {
@@ -850,6 +994,9 @@ impl<'a> Iterator for Commands<'a> {
             PreparedCommand::Plain(ref range) => {
                 Command::Draw(Draw::Plain(&vertices[range.clone()]))
             }
+            PreparedCommand::BitmapGlyphs(ref range) => {
+                Command::Draw(Draw::BitmapGlyphs(&vertices[range.clone()]))
+            }
             PreparedCommand::Image(id, ref range) => {
                 Command::Draw(Draw::Image(id, &vertices[range.clone()]))
             }
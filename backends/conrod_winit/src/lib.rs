@@ -141,6 +141,13 @@ pub fn convert_window_event<W>(e: winit::WindowEvent, window: &W) -> Option<Inpu
             Some(Input::Redraw)
         },
 
+        // Winit does not report the position of hovering or dropped files and does not report
+        // cursor motion during the drag on all platforms, so conrod targets the widget under the
+        // last known cursor position.
+        winit::WindowEvent::HoveredFile(path) => Some(Input::FileHovered(path)),
+        winit::WindowEvent::DroppedFile(path) => Some(Input::FileDropped(path)),
+        winit::WindowEvent::HoveredFileCancelled => Some(Input::FileHoverCancelled),
+
         _ => None,
     }
 }
@@ -0,0 +1,74 @@
+//! Sources of time used by the `Ui` when interpreting input.
+//!
+//! The `Ui` uses its `Clock` to timestamp clicks and touches, e.g. when detecting `DoubleClick`s
+//! and `LongPress`es. By default the `System` clock is used. A `Manual` clock may be given to the
+//! `UiBuilder` instead so that simulated sessions, tests and replays are reproducible.
+
+use std;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A source of the current time.
+pub trait Clock: std::fmt::Debug + Send {
+    /// The current time.
+    fn now(&self) -> Instant;
+}
+
+/// A `Clock` that yields the system's monotonic time, i.e. `Instant::now()`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct System;
+
+/// A `Clock` whose time only changes when it is explicitly advanced.
+///
+/// Clones share the same time, so a clone may be kept in order to advance the clock after the
+/// original has been given to a `Ui`.
+#[derive(Clone, Debug)]
+pub struct Manual {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl Clock for System {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl Manual {
+
+    /// A new clock beginning at the current system time.
+    pub fn new() -> Self {
+        Manual {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::from_secs(0))),
+        }
+    }
+
+    /// The duration that the clock has been advanced by since it was created.
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().unwrap()
+    }
+
+    /// Set the duration that the clock has been advanced by since it was created.
+    pub fn set_elapsed(&self, elapsed: Duration) {
+        *self.elapsed.lock().unwrap() = elapsed;
+    }
+
+    /// Advance the clock by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap() += duration;
+    }
+
+}
+
+impl Default for Manual {
+    fn default() -> Self {
+        Manual::new()
+    }
+}
+
+impl Clock for Manual {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+}
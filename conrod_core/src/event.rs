@@ -37,6 +37,7 @@
 
 use input;
 use position::{Dimensions, Point};
+use std::path::PathBuf;
 use utils::vec2_sub;
 use widget;
 
@@ -57,6 +58,7 @@ use widget;
 /// co-ordinates when converting to this event. Also be sure to invert the *y* axis of MouseScroll
 /// events.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Input {
     /// A button on some input device was pressed.
     Press(input::Button),
@@ -74,6 +76,22 @@ pub enum Input {
     Focus(bool),
     /// The backed requested to redraw.
     Redraw,
+    /// A file is being dragged over the window from elsewhere in the OS.
+    ///
+    /// When several files are dragged at once, one `FileHovered` is received for each.
+    ///
+    /// Windowing APIs do not report the position of the hovering file, and some do not report
+    /// cursor motion while a file is dragged over the window. The file is considered to lie at the
+    /// last position given by `Motion`, so the widget to which it is delivered may be stale.
+    FileHovered(PathBuf),
+    /// A file was dropped onto the window.
+    ///
+    /// When several files are dropped at once, one `FileDropped` is received for each.
+    ///
+    /// As with `FileHovered`, the file is dropped at the last position given by `Motion`.
+    FileDropped(PathBuf),
+    /// The files being dragged over the window left the window or the drag was cancelled.
+    FileHoverCancelled,
 }
 
 
@@ -114,8 +132,45 @@ pub enum Ui {
     DoubleClick(Option<widget::Id>, DoubleClick),
     /// A user tapped a touch screen/surface.
     Tap(Option<widget::Id>, Tap),
+    /// A touch was held in place for at least the `theme.long_press_duration`, along with the
+    /// widget over which the touch began.
+    LongPress(Option<widget::Id>, LongPress),
+    /// Two touches moved towards or away from one another, along with the innermost widget
+    /// containing the beginning of both touches.
+    Pinch(Option<widget::Id>, Pinch),
+    /// Two touches rotated around one another, along with the innermost widget containing the
+    /// beginning of both touches.
+    Rotate(Option<widget::Id>, Rotate),
+    /// Two touches moved together in the same direction, along with the innermost widget
+    /// containing the beginning of both touches.
+    ///
+    /// The `Ui` also applies the movement to the scrollable widgets under the touches as though
+    /// it were a `Scroll`.
+    Pan(Option<widget::Id>, Pan),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Option<widget::Id>, Drag),
+    /// A drag was cancelled (i.e. via the `Escape` key) before the dragging button was released,
+    /// along with the widget over which the drag began.
+    ///
+    /// No further `Drag`, `Click` or `DoubleClick` events will be produced for the cancelled
+    /// button until it is pressed again.
+    ///
+    /// This is also produced for the source of an `input::drag_drop::Drag` that is released over
+    /// a widget that does not accept it.
+    DragCancel(Option<widget::Id>, DragCancel),
+    /// The payload of an `input::drag_drop::Drag` was dropped onto the given widget.
+    ///
+    /// This is delivered to both the target and the source widget.
+    Drop(widget::Id, DropEvent),
+    /// A file from the OS is being dragged over the window, along with the widget under the mouse
+    /// cursor at the time.
+    FileHover(Option<widget::Id>, FileHover),
+    /// A file from the OS was dropped onto the window, along with the widget under the mouse
+    /// cursor at the time.
+    FileDrop(Option<widget::Id>, FileDrop),
+    /// The files being dragged over the window left the window without being dropped, along with
+    /// the widget that received the last `FileHover`.
+    FileHoverCancel(Option<widget::Id>),
     /// A generic scroll event.
     ///
     /// `Scroll` does not necessarily have to get created by a mouse wheel, it could be generated
@@ -125,6 +180,15 @@ pub enum Ui {
     /// top to bottom. The remainder will then be applied to either 1. whatever widget captures the
     /// device from which the scroll was emitted or 2. whatever widget was specified.
     Scroll(Option<widget::Id>, Scroll),
+    /// A registered keyboard shortcut was triggered, along with the widget whose scope the
+    /// shortcut was registered within (`None` for `Global` shortcuts).
+    Shortcut(Option<widget::Id>, input::shortcut::Id),
+    /// The mouse cursor moved over the given widget, i.e. it became the top-most widget under the
+    /// cursor.
+    MouseEnter(widget::Id),
+    /// The mouse cursor moved off the given widget, i.e. it is no longer the top-most widget under
+    /// the cursor.
+    MouseLeave(widget::Id),
     /// Indicates that the given widget has captured the given user input source.
     WidgetCapturesInputSource(widget::Id, input::Source),
     /// Indicates that the given widget has released the given user input source.
@@ -159,12 +223,39 @@ pub enum Widget {
     DoubleClick(DoubleClick),
     /// A user tapped the widget on a touch screen/surface.
     Tap(Tap),
+    /// A touch that began over the widget was held in place.
+    LongPress(LongPress),
+    /// Two touches that began over the widget moved towards or away from one another.
+    Pinch(Pinch),
+    /// Two touches that began over the widget rotated around one another.
+    Rotate(Rotate),
+    /// Two touches that began over the widget moved together in the same direction.
+    Pan(Pan),
     /// Represents a pointing device button being pressed and a subsequent movement of the mouse.
     Drag(Drag),
+    /// A drag that began over the widget was cancelled before the dragging button was released.
+    ///
+    /// Widgets should use this to restore any state that they had prior to the drag.
+    DragCancel(DragCancel),
+    /// The payload of an `input::drag_drop::Drag` was dropped onto the widget or was dragged
+    /// from the widget and dropped elsewhere.
+    Drop(DropEvent),
+    /// A file from the OS is being dragged over the widget.
+    FileHover(FileHover),
+    /// A file from the OS was dropped onto the widget.
+    FileDrop(FileDrop),
+    /// The files that were being dragged over the widget left the window without being dropped.
+    FileHoverCancel,
     /// Represents the amount of scroll that has been applied to this widget.
     Scroll(Scroll),
     /// The window's dimensions were resized.
     WindowResized(Dimensions),
+    /// A keyboard shortcut registered within the widget's scope was triggered.
+    Shortcut(input::shortcut::Id),
+    /// The mouse cursor moved over the widget.
+    MouseEnter,
+    /// The mouse cursor moved off the widget.
+    MouseLeave,
     /// The widget has captured the given input source.
     CapturesInputSource(input::Source),
     /// The widget has released the input source from capturing.
@@ -279,6 +370,52 @@ pub struct Drag {
     pub modifiers: input::keyboard::ModifierKey,
 }
 
+/// Contains all the relevant information for a cancelled mouse drag.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DragCancel {
+    /// Which mouse button was being held during the drag.
+    pub button: input::MouseButton,
+    /// The point from which the cancelled series of drag events began.
+    pub origin: Point,
+    /// The position of the pointing device at the time the drag was cancelled.
+    pub xy: Point,
+    /// Which modifier keys were being held when the drag was cancelled.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
+/// Contains all the relevant information for a dropped `input::drag_drop::Drag`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DropEvent {
+    /// The widget from which the drag began.
+    pub source: widget::Id,
+    /// The widget that accepted the drop.
+    pub target: widget::Id,
+    /// The position of the pointing device at the time of the drop.
+    pub xy: Point,
+    /// Which modifier keys were being held at the time of the drop.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
+/// Contains all the relevant information for a file being dragged over the window from the OS.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileHover {
+    /// The path to the file.
+    pub path: PathBuf,
+    /// The position of the mouse cursor at the time.
+    pub xy: Point,
+}
+
+/// Contains all the relevant information for a file dropped onto the window from the OS.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FileDrop {
+    /// The path to the file.
+    pub path: PathBuf,
+    /// The position of the mouse cursor at the time of the drop.
+    pub xy: Point,
+    /// Which modifier keys were being held at the time of the drop.
+    pub modifiers: input::keyboard::ModifierKey,
+}
+
 /// Contains all the relevant information for a mouse click.
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Click {
@@ -312,6 +449,44 @@ pub struct Tap {
     pub xy: Point,
 }
 
+/// All relevant information for a touch-screen long press event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct LongPress {
+    /// The unique identifier of the source of the touch.
+    pub id: input::touch::Id,
+    /// The position of the touch at the time of the long press.
+    pub xy: Point,
+}
+
+/// All relevant information for a two-finger pinch event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pinch {
+    /// The point midway between the two touches.
+    pub center: Point,
+    /// The ratio of the new distance between the touches to the previous distance.
+    ///
+    /// Values greater than `1.0` indicate that the touches moved apart (i.e. zooming in).
+    pub scale: f64,
+}
+
+/// All relevant information for a two-finger rotation event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rotate {
+    /// The point midway between the two touches.
+    pub center: Point,
+    /// The change in angle between the two touches in radians, counter-clockwise.
+    pub radians: f64,
+}
+
+/// All relevant information for a two-finger pan event.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Pan {
+    /// The point midway between the two touches.
+    pub center: Point,
+    /// The movement of the `center` since the last `Pan`.
+    pub delta_xy: Point,
+}
+
 /// Holds all the relevant information about a scroll event
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Scroll {
@@ -432,6 +607,46 @@ impl Tap {
     }
 }
 
+impl LongPress {
+    /// Returns a copy of the `LongPress` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        LongPress {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl Pinch {
+    /// Returns a copy of the `Pinch` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        Pinch {
+            center: vec2_sub(self.center, xy),
+            ..*self
+        }
+    }
+}
+
+impl Rotate {
+    /// Returns a copy of the `Rotate` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        Rotate {
+            center: vec2_sub(self.center, xy),
+            ..*self
+        }
+    }
+}
+
+impl Pan {
+    /// Returns a copy of the `Pan` relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> Self {
+        Pan {
+            center: vec2_sub(self.center, xy),
+            ..*self
+        }
+    }
+}
+
 impl Click {
     /// Returns a copy of the Click relative to the given `xy`
     pub fn relative_to(&self, xy: Point) -> Click {
@@ -464,6 +679,48 @@ impl Drag {
     }
 }
 
+impl DragCancel {
+    /// Returns a copy of the DragCancel relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> DragCancel {
+        DragCancel {
+            origin: vec2_sub(self.origin, xy),
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl DropEvent {
+    /// Returns a copy of the DropEvent relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> DropEvent {
+        DropEvent {
+            xy: vec2_sub(self.xy, xy),
+            ..*self
+        }
+    }
+}
+
+impl FileHover {
+    /// Returns a copy of the FileHover relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> FileHover {
+        FileHover {
+            path: self.path.clone(),
+            xy: vec2_sub(self.xy, xy),
+        }
+    }
+}
+
+impl FileDrop {
+    /// Returns a copy of the FileDrop relative to the given `xy`
+    pub fn relative_to(&self, xy: Point) -> FileDrop {
+        FileDrop {
+            path: self.path.clone(),
+            xy: vec2_sub(self.xy, xy),
+            modifiers: self.modifiers,
+        }
+    }
+}
+
 
 impl From<input::Motion> for Input {
     fn from(motion: input::Motion) -> Self {
@@ -537,6 +794,30 @@ impl From<Tap> for Widget {
     }
 }
 
+impl From<LongPress> for Widget {
+    fn from(long_press: LongPress) -> Self {
+        Widget::LongPress(long_press)
+    }
+}
+
+impl From<Pinch> for Widget {
+    fn from(pinch: Pinch) -> Self {
+        Widget::Pinch(pinch)
+    }
+}
+
+impl From<Rotate> for Widget {
+    fn from(rotate: Rotate) -> Self {
+        Widget::Rotate(rotate)
+    }
+}
+
+impl From<Pan> for Widget {
+    fn from(pan: Pan) -> Self {
+        Widget::Pan(pan)
+    }
+}
+
 impl From<Scroll> for Widget {
     fn from(scroll: Scroll) -> Self {
         Widget::Scroll(scroll)
@@ -548,3 +829,27 @@ impl From<Drag> for Widget {
         Widget::Drag(drag)
     }
 }
+
+impl From<DragCancel> for Widget {
+    fn from(drag_cancel: DragCancel) -> Self {
+        Widget::DragCancel(drag_cancel)
+    }
+}
+
+impl From<DropEvent> for Widget {
+    fn from(drop: DropEvent) -> Self {
+        Widget::Drop(drop)
+    }
+}
+
+impl From<FileHover> for Widget {
+    fn from(file_hover: FileHover) -> Self {
+        Widget::FileHover(file_hover)
+    }
+}
+
+impl From<FileDrop> for Widget {
+    fn from(file_drop: FileDrop) -> Self {
+        Widget::FileDrop(file_drop)
+    }
+}
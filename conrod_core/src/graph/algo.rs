@@ -272,7 +272,7 @@ pub fn kids_bounding_box(graph: &Graph,
 /// Return the `scroll_offset` for the widget at the given index.
 ///
 /// The offset is retrieved from the widget that is the immediate `depth_parent` of the widget at
-/// the given `idx` unless:
+/// the given `idx`, including any overscroll while that widget bounces kinetically, unless:
 ///
 /// - the immediate `depth_parent` of `idx` is also a `graphic_parent` to `idx`. In this case,
 /// `NO_OFFSET` will be returned, as child widgets that are graphical elements of their parents
@@ -311,12 +311,14 @@ pub fn scroll_offset(graph: &Graph, idx: widget::Id) -> Point {
 
                 let x_offset = depth_parent_widget.maybe_x_scroll_state.map(|scroll| {
                     let position_parents = graph.x_position_parent_recursion(idx);
-                    if is_already_offset(position_parents) { 0.0 } else { scroll.offset }
+                    if is_already_offset(position_parents) { 0.0 }
+                    else { scroll.offset + scroll.overscroll }
                 }).unwrap_or(0.0);
 
                 let y_offset = depth_parent_widget.maybe_y_scroll_state.map(|scroll| {
                     let position_parents = graph.y_position_parent_recursion(idx);
-                    if is_already_offset(position_parents) { 0.0 } else { scroll.offset }
+                    if is_already_offset(position_parents) { 0.0 }
+                    else { scroll.offset + scroll.overscroll }
                 }).unwrap_or(0.0);
 
                 return [x_offset, y_offset];
@@ -326,3 +328,25 @@ pub fn scroll_offset(graph: &Graph, idx: widget::Id) -> Point {
 
     NO_OFFSET
 }
+
+
+/// Find the innermost widget that is either equal to or a **Depth** ancestor of both `a` and `b`.
+///
+/// Returns `None` if the widgets do not share a **Depth** ancestor.
+pub fn common_depth_ancestor(graph: &Graph, a: widget::Id, b: widget::Id) -> Option<widget::Id> {
+    let mut a_ancestors = vec![a];
+    let mut maybe_id = graph.depth_parent(a);
+    while let Some(id) = maybe_id {
+        a_ancestors.push(id);
+        maybe_id = graph.depth_parent(id);
+    }
+
+    let mut maybe_id = Some(b);
+    while let Some(id) = maybe_id {
+        if a_ancestors.contains(&id) {
+            return Some(id);
+        }
+        maybe_id = graph.depth_parent(id);
+    }
+    None
+}
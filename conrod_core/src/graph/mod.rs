@@ -99,6 +99,14 @@ pub struct Container {
     ///
     /// NOTE: See `Wiget::is_over` for more details and a note on possible future plans.
     pub is_over: IsOverFn,
+    /// Whether or not the widget belongs to the `Ui`'s keyboard focus chain.
+    pub is_focusable: bool,
+    /// Whether or not the widget accepts text input while capturing the keyboard.
+    pub is_text_input: bool,
+    /// Whether or not the widget handles keys bound to spatial navigation itself.
+    pub handles_navigation_keys: bool,
+    /// The position of the widget within the keyboard focus chain if one was given.
+    pub maybe_tab_index: Option<usize>,
 }
 
 /// A wrapper around a `widget::IsOverFn` to make implementing `Debug` easier for `Container`.
@@ -626,6 +634,7 @@ impl Graph {
             type_id, id, maybe_parent_id, maybe_x_positioned_relatively_id,
             maybe_y_positioned_relatively_id, rect, depth, kid_area, maybe_floating,
             crop_kids, maybe_x_scroll_state, maybe_y_scroll_state, maybe_graphics_for, is_over,
+            is_focusable, is_text_input, handles_navigation_keys, maybe_tab_index,
         } = widget;
 
         assert!(self.node(id).is_some(), "No node found for the given widget::Id {:?}", id);
@@ -643,6 +652,10 @@ impl Graph {
             maybe_y_scroll_state: maybe_y_scroll_state,
             instantiation_order_idx: instantiation_order_idx,
             is_over: IsOverFn(is_over),
+            is_focusable: is_focusable,
+            is_text_input: is_text_input,
+            handles_navigation_keys: handles_navigation_keys,
+            maybe_tab_index: maybe_tab_index,
         };
 
         // Retrieves the widget's parent index.
@@ -695,6 +708,10 @@ impl Graph {
                 container.maybe_y_scroll_state = maybe_y_scroll_state;
                 container.instantiation_order_idx = instantiation_order_idx;
                 container.is_over = IsOverFn(is_over);
+                container.is_focusable = is_focusable;
+                container.is_text_input = is_text_input;
+                container.handles_navigation_keys = handles_navigation_keys;
+                container.maybe_tab_index = maybe_tab_index;
             },
 
         }
@@ -9,7 +9,8 @@ In this chapter we'll make sure that your environment is setup correctly for usi
 ## Installing Rust and Cargo
 
 Conrod is a Rust library (aka crate), so you'll need Rust! Conrod tracks the stable branch, so you
-can be assured that we'll always be compatible with the latest stable version of rustc.
+can be assured that we'll always be compatible with the latest stable version of rustc. The oldest
+supported version is Rust 1.60, the first whose Cargo understands the manifest of `conrod_core`.
 
 We also rely on the Rust package manager [Cargo](https://crates.io/) for managing dependencies
 and hosting the latest version of conrod.
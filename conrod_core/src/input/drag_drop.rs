@@ -0,0 +1,95 @@
+//! Dragging typed payloads from one widget and dropping them onto another.
+//!
+//! A source widget begins a drag by passing a `Drag` to `UiCell::start_drag` while the left mouse
+//! button is held, usually in response to its first `event::Drag`. While the drag is in progress,
+//! widgets under the mouse may inspect it via `Ui::drag_over` and call `UiCell::accept_drop` if
+//! they accept its payload.
+//!
+//! When the left mouse button is released over a widget that accepted the drag during the last
+//! `set_widgets` stage, an `event::Ui::Drop` is delivered to both the target and the source and
+//! only the target may take the payload via `UiCell::take_dropped_payload`. Otherwise, or if
+//! `Escape` is pressed, the source receives an `event::Ui::DragCancel` instead.
+
+use std::any::Any;
+use widget;
+
+/// The way in which the `Ui` displays an in-progress `Drag` near the mouse cursor.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Preview {
+    /// Nothing is displayed by the `Ui`.
+    ///
+    /// The source widget may display its own preview at the mouse position while `Ui::drag`
+    /// returns `Some`.
+    None,
+    /// The given text is displayed within a `widget::Tooltip`.
+    Label(String),
+}
+
+/// A payload being dragged from one widget to another.
+#[derive(Debug)]
+pub struct Drag {
+    source: widget::Id,
+    payload: Box<dyn Any + Send>,
+    preview: Preview,
+}
+
+impl Drag {
+
+    /// Begin describing a drag of the given `payload` from the `source` widget.
+    pub fn new<T>(source: widget::Id, payload: T) -> Self
+        where T: Any + Send,
+    {
+        Drag {
+            source: source,
+            payload: Box::new(payload),
+            preview: Preview::None,
+        }
+    }
+
+    /// Specify how the drag should be displayed near the mouse cursor.
+    pub fn preview(mut self, preview: Preview) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Display the given text near the mouse cursor during the drag.
+    pub fn label(self, text: &str) -> Self {
+        self.preview(Preview::Label(text.to_owned()))
+    }
+
+    /// The widget from which the drag began.
+    pub fn source(&self) -> widget::Id {
+        self.source
+    }
+
+    /// The preview displayed near the mouse cursor.
+    pub fn get_preview(&self) -> &Preview {
+        &self.preview
+    }
+
+    /// Whether or not the payload is of type `T`.
+    pub fn payload_is<T>(&self) -> bool
+        where T: Any,
+    {
+        self.payload.is::<T>()
+    }
+
+    /// A reference to the payload if it is of type `T`.
+    pub fn payload<T>(&self) -> Option<&T>
+        where T: Any,
+    {
+        self.payload.downcast_ref::<T>()
+    }
+
+    /// Consume the drag, producing the payload if it is of type `T`.
+    pub fn into_payload<T>(self) -> Result<T, Self>
+        where T: Any,
+    {
+        let Drag { source, payload, preview } = self;
+        match payload.downcast::<T>() {
+            Ok(payload) => Ok(*payload),
+            Err(payload) => Err(Drag { source: source, payload: payload, preview: preview }),
+        }
+    }
+
+}
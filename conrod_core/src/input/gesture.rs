@@ -0,0 +1,164 @@
+//! Recognition of gestures that are made up of one or more `Touch` interactions.
+//!
+//! The `Recognizer` is stored within the `input::Global` and is driven by the `Ui` as it handles
+//! `Touch` input. Two simultaneous touches are interpreted together as pinch, rotate and pan
+//! gestures, while a single touch held in place produces a long press.
+
+use fnv;
+use input::touch;
+use position::{Point, Scalar};
+use std;
+use utils::vec2_sub;
+use widget;
+
+/// The distance a touch may move from where it began while still producing a long press.
+pub const LONG_PRESS_SLOP: Scalar = 10.0;
+
+/// Tracks the state of all touch gestures in progress.
+#[derive(Clone, Debug, Default)]
+pub struct Recognizer {
+    maybe_pair: Option<Pair>,
+    // Touches that may no longer produce a long press, either because they already have, they
+    // have moved too far or they belong to a `Pair`.
+    settled: fnv::FnvHashSet<touch::Id>,
+}
+
+/// Two touches interpreted together as a pinch, rotate and pan gesture.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Pair {
+    /// The touches that make up the pair.
+    pub touches: [touch::Id; 2],
+    /// The widget to which the gesture is delivered.
+    ///
+    /// This is the innermost widget containing the beginning of both touches, if any.
+    pub widget: Option<widget::Id>,
+    /// The last known position of each touch.
+    pub xys: [Point; 2],
+}
+
+/// The change in the geometry of a `Pair` caused by one of its touches moving.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PairDelta {
+    /// The point midway between the two touches after the move.
+    pub center: Point,
+    /// The movement of the `center`.
+    pub translation: Point,
+    /// The ratio of the new distance between the touches to the previous distance.
+    pub scale: Scalar,
+    /// The change in the angle of the line between the touches in radians (counter-clockwise).
+    pub rotation: Scalar,
+}
+
+impl Pair {
+
+    /// The point midway between the two touches.
+    pub fn center(&self) -> Point {
+        let [a, b] = self.xys;
+        [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0]
+    }
+
+    /// The distance between the two touches.
+    pub fn distance(&self) -> Scalar {
+        let [dx, dy] = vec2_sub(self.xys[1], self.xys[0]);
+        (dx * dx + dy * dy).sqrt()
+    }
+
+    /// The angle of the line from the first touch to the second in radians.
+    pub fn angle(&self) -> Scalar {
+        let [dx, dy] = vec2_sub(self.xys[1], self.xys[0]);
+        dy.atan2(dx)
+    }
+
+    /// Move the touch with the given `id` to `xy`, returning the change in the pair's geometry.
+    ///
+    /// Returns `None` if the touch does not belong to the pair.
+    pub fn move_touch(&mut self, id: touch::Id, xy: Point) -> Option<PairDelta> {
+        let idx = match self.touches.iter().position(|&touch| touch == id) {
+            Some(idx) => idx,
+            None => return None,
+        };
+
+        let (prev_center, prev_distance, prev_angle) = (self.center(), self.distance(), self.angle());
+        self.xys[idx] = xy;
+        let (center, distance, angle) = (self.center(), self.distance(), self.angle());
+
+        let scale = if prev_distance > 0.0 { distance / prev_distance } else { 1.0 };
+
+        // Wrap the rotation to the range `-PI..PI` so that crossing the negative *x* axis does
+        // not produce a full turn.
+        let pi = std::f64::consts::PI;
+        let mut rotation = angle - prev_angle;
+        if rotation > pi {
+            rotation -= 2.0 * pi;
+        } else if rotation < -pi {
+            rotation += 2.0 * pi;
+        }
+
+        Some(PairDelta {
+            center: center,
+            translation: vec2_sub(center, prev_center),
+            scale: scale,
+            rotation: rotation,
+        })
+    }
+
+}
+
+impl Recognizer {
+
+    /// A recognizer with no gestures in progress.
+    pub fn new() -> Self {
+        Recognizer::default()
+    }
+
+    /// The pair of touches currently being interpreted as a two-finger gesture, if any.
+    pub fn pair(&self) -> Option<&Pair> {
+        self.maybe_pair.as_ref()
+    }
+
+    /// Begin interpreting the given `pair` of touches as a two-finger gesture.
+    ///
+    /// Neither touch may produce a long press from this point on.
+    pub fn begin_pair(&mut self, pair: Pair) {
+        self.settled.insert(pair.touches[0]);
+        self.settled.insert(pair.touches[1]);
+        self.maybe_pair = Some(pair);
+    }
+
+    /// Update the recognizer with the movement of a touch that began at `start_xy` to `xy`.
+    ///
+    /// Returns the widget to deliver the gesture to along with the change in the pair geometry if
+    /// the touch belongs to the current `Pair`.
+    pub fn move_touch(&mut self,
+                      id: touch::Id,
+                      start_xy: Point,
+                      xy: Point) -> Option<(Option<widget::Id>, PairDelta)>
+    {
+        let [dx, dy] = vec2_sub(xy, start_xy);
+        if (dx * dx + dy * dy).sqrt() > LONG_PRESS_SLOP {
+            self.settled.insert(id);
+        }
+        self.maybe_pair.as_mut()
+            .and_then(|pair| pair.move_touch(id, xy).map(|delta| (pair.widget, delta)))
+    }
+
+    /// Update the recognizer with the end or cancellation of a touch.
+    pub fn end_touch(&mut self, id: touch::Id) {
+        self.settled.remove(&id);
+        let ends_pair = self.maybe_pair.map(|pair| pair.touches.contains(&id)).unwrap_or(false);
+        if ends_pair {
+            self.maybe_pair = None;
+        }
+    }
+
+    /// Whether or not the touch with the given `id` may still produce a long press.
+    pub fn is_long_press_candidate(&self, id: touch::Id) -> bool {
+        !self.settled.contains(&id)
+    }
+
+    /// Indicate that the touch with the given `id` has produced a long press.
+    pub fn long_pressed(&mut self, id: touch::Id) {
+        self.settled.insert(id);
+    }
+
+}
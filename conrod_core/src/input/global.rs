@@ -21,6 +21,8 @@ pub struct Global {
     /// Tracks the last click that occurred and the time at which it occurred in order to create
     /// double-click events.
     pub last_click: Option<(std::time::Instant, event::Click)>,
+    /// Tracks touch gestures such as pinching and long-pressing that are in progress.
+    pub gestures: input::gesture::Recognizer,
 }
 
 /// Iterator over all global `event::Event`s that have occurred since the last time
@@ -46,6 +48,7 @@ impl Global {
             start: input::State::new(),
             current: input::State::new(),
             last_click: None,
+            gestures: input::gesture::Recognizer::new(),
         }
     }
 
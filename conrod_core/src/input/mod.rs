@@ -18,6 +18,12 @@
 pub mod state;
 pub mod widget;
 pub mod global;
+pub mod drag_drop;
+pub mod gesture;
+pub mod navigation;
+#[cfg(feature = "serde")]
+pub mod record;
+pub mod shortcut;
 
 use Scalar;
 pub use self::state::State;
@@ -55,6 +61,7 @@ pub enum Source {
 /// Different kinds of motion input.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Motion {
     /// Absolute cursor position within the window.
     ///
@@ -75,10 +82,12 @@ pub mod touch {
 
     /// A type for uniquely identifying the source of a touch interaction.
     #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct Id(u64);
 
     /// The stage of the touch interaction.
     #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub enum Phase {
         /// The start of a touch interaction.
         Start,
@@ -102,6 +111,7 @@ pub mod touch {
     /// indicate that the same finger was used. `Id`s are only used to distinguish between
     /// overlapping touch interactions.
     #[derive(Copy, Clone, Debug, PartialEq)]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     pub struct Touch {
         /// The stage of the touch interaction.
         pub phase: Phase,
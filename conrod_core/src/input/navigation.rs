@@ -0,0 +1,173 @@
+//! Spatial navigation of keyboard focus between widgets using arrow keys or a gamepad.
+//!
+//! Navigation is configured via the `Ui`'s `navigation` field. Whenever a bound input is received,
+//! the `Ui` moves keyboard capture to the nearest focusable widget in the bound `Direction`, or
+//! activates the focused widget by delivering it a left `Click`.
+//!
+//! By default no inputs are bound. See `Navigation::standard` for arrow key and gamepad bindings.
+
+use input::{self, Key};
+use position::{Rect, Scalar};
+use widget;
+
+/// A direction in which focus may be moved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Towards the top of the window.
+    Up,
+    /// Towards the bottom of the window.
+    Down,
+    /// Towards the left of the window.
+    Left,
+    /// Towards the right of the window.
+    Right,
+}
+
+/// The actions that may be bound to inputs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Move keyboard focus to the nearest focusable widget in the given direction.
+    Move(Direction),
+    /// Activate the focused widget as though it were clicked.
+    Activate,
+}
+
+/// An input that may trigger an `Action`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Binding {
+    /// A key on the keyboard.
+    ///
+    /// Keys are ignored while a widget that accepts text input is capturing the keyboard, or while
+    /// the capturing widget or one of its ancestors handles navigation keys itself (see
+    /// `Widget::handles_navigation_keys`).
+    Key(Key),
+    /// The button with the given index on any controller.
+    ControllerButton(u8),
+    /// The axis with the given index on any controller, moved beyond the `axis_threshold` in the
+    /// positive or negative direction.
+    ControllerAxis {
+        /// The index of the axis.
+        axis: u8,
+        /// Whether the axis must be moved in the positive direction.
+        positive: bool,
+    },
+}
+
+/// Describes which inputs trigger navigation `Action`s.
+#[derive(Clone, Debug)]
+pub struct Navigation {
+    /// The inputs bound to each action.
+    pub bindings: Vec<(Binding, Action)>,
+    /// The distance from rest that a controller axis must be moved before triggering its action.
+    ///
+    /// The axis must return within this distance before it may trigger again.
+    pub axis_threshold: Scalar,
+    // Controller axes that are currently beyond the threshold.
+    active_axes: Vec<(input::ControllerAxisArgs, bool)>,
+}
+
+impl Navigation {
+
+    /// Navigation with no bindings.
+    pub fn new() -> Self {
+        Navigation {
+            bindings: Vec::new(),
+            axis_threshold: 0.5,
+            active_axes: Vec::new(),
+        }
+    }
+
+    /// Arrow keys and the `Return` key, along with the D-pad, left stick and `A` button of a
+    /// controller using the common SDL game controller layout.
+    pub fn standard() -> Self {
+        use self::Action::{Activate, Move};
+        use self::Binding::{ControllerAxis, ControllerButton};
+        use self::Direction::{Down, Left, Right, Up};
+        let bindings = vec![
+            (Binding::Key(Key::Up), Move(Up)),
+            (Binding::Key(Key::Down), Move(Down)),
+            (Binding::Key(Key::Left), Move(Left)),
+            (Binding::Key(Key::Right), Move(Right)),
+            (Binding::Key(Key::Return), Activate),
+            (ControllerButton(11), Move(Up)),
+            (ControllerButton(12), Move(Down)),
+            (ControllerButton(13), Move(Left)),
+            (ControllerButton(14), Move(Right)),
+            (ControllerButton(0), Activate),
+            (ControllerAxis { axis: 0, positive: false }, Move(Left)),
+            (ControllerAxis { axis: 0, positive: true }, Move(Right)),
+            (ControllerAxis { axis: 1, positive: false }, Move(Up)),
+            (ControllerAxis { axis: 1, positive: true }, Move(Down)),
+        ];
+        Navigation { bindings: bindings, ..Navigation::new() }
+    }
+
+    /// The action bound to the given binding, if any.
+    pub fn action(&self, binding: Binding) -> Option<Action> {
+        self.bindings.iter()
+            .find(|&&(b, _)| b == binding)
+            .map(|&(_, action)| action)
+    }
+
+    /// The action triggered by the given controller axis motion, if any.
+    ///
+    /// An action is only triggered when the axis first moves beyond the `axis_threshold`.
+    pub fn axis_action(&mut self, args: input::ControllerAxisArgs) -> Option<Action> {
+        let maybe_idx = self.active_axes.iter()
+            .position(|&(active, _)| active.id == args.id && active.axis == args.axis);
+
+        // Wait for active axes to return to rest.
+        if let Some(idx) = maybe_idx {
+            if args.position.abs() < self.axis_threshold {
+                self.active_axes.remove(idx);
+            }
+            return None;
+        }
+
+        if args.position.abs() < self.axis_threshold {
+            return None;
+        }
+        let positive = args.position > 0.0;
+        self.active_axes.push((args, positive));
+        self.action(Binding::ControllerAxis { axis: args.axis, positive: positive })
+    }
+
+}
+
+impl Default for Navigation {
+    fn default() -> Self {
+        Navigation::new()
+    }
+}
+
+/// Find the candidate nearest to `from` in the given `direction`.
+///
+/// Only candidates whose centre lies beyond the centre of `from` in the given direction are
+/// considered. Candidates are weighted by their distance along the direction plus twice their
+/// offset across it, favouring widgets that are aligned with `from`.
+pub fn nearest<I>(from: Rect, direction: Direction, candidates: I) -> Option<widget::Id>
+    where I: IntoIterator<Item=(widget::Id, Rect)>,
+{
+    let [from_x, from_y] = from.xy();
+    candidates.into_iter()
+        .filter_map(|(id, rect)| {
+            let [x, y] = rect.xy();
+            let (dx, dy) = (x - from_x, y - from_y);
+            let (along, across) = match direction {
+                Direction::Up => (dy, dx),
+                Direction::Down => (-dy, dx),
+                Direction::Left => (-dx, dy),
+                Direction::Right => (dx, dy),
+            };
+            if along > 0.0 {
+                Some((id, along + across.abs() * 2.0))
+            } else {
+                None
+            }
+        })
+        .fold(None, |nearest: Option<(widget::Id, Scalar)>, (id, distance)| match nearest {
+            Some((_, nearest_distance)) if nearest_distance <= distance => nearest,
+            _ => Some((id, distance)),
+        })
+        .map(|(id, _)| id)
+}
@@ -0,0 +1,282 @@
+//! Recording and replaying sessions of `event::Input`.
+//!
+//! A `Recorder` writes each `Input` passed to `Ui::handle_event` along with the time at which it
+//! was received relative to the beginning of the recording. Recordings may be loaded as a
+//! `Recording` and fed back into a `Ui` via a `Player`, making them useful for reproducing bug
+//! reports and for writing end-to-end tests.
+//!
+//! Recordings are stored as [JSON Lines](http://jsonlines.org/). The first line is a `Header`
+//! describing the window dimensions at the beginning of the recording. Every following line is an
+//! `Entry`.
+
+use clock;
+use event::Input;
+use position::Dimensions;
+use serde_json;
+use std;
+use std::io::{BufRead, Write};
+use Ui;
+
+/// The first line of every recording.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Header {
+    /// The dimensions of the window at the beginning of the recording.
+    pub window_dimensions: Dimensions,
+}
+
+/// A single recorded `Input`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    /// The duration between the beginning of the recording and the receipt of the `input`.
+    pub time: std::time::Duration,
+    /// The input that was received.
+    pub input: Input,
+}
+
+/// A complete recording of an input session.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recording {
+    /// The dimensions of the window at the beginning of the recording.
+    pub window_dimensions: Dimensions,
+    /// Every recorded input, in the order in which it was received.
+    pub entries: Vec<Entry>,
+}
+
+/// Writes a recording of each `Input` passed to `record`.
+///
+/// If an error occurs while writing, no further inputs are written and the error is returned by
+/// `finish`.
+pub struct Recorder {
+    writer: Box<dyn Write + Send>,
+    start: std::time::Instant,
+    maybe_error: Option<Error>,
+}
+
+/// Feeds the entries of a `Recording` into a `Ui`.
+#[derive(Clone, Debug)]
+pub struct Player {
+    recording: Recording,
+    next_entry: usize,
+    has_resized: bool,
+    // The clock advanced to the time of each entry, along with its elapsed time when given.
+    maybe_clock: Option<(clock::Manual, std::time::Duration)>,
+}
+
+/// The errors that may occur while writing or reading a recording.
+#[derive(Debug)]
+pub enum Error {
+    /// An error occurred while writing to or reading from the underlying stream.
+    Io(std::io::Error),
+    /// A line of the recording could not be serialized or deserialized.
+    Json(serde_json::Error),
+    /// The recording did not begin with a `Header`.
+    MissingHeader,
+}
+
+impl Recording {
+
+    /// Read a recording from the given stream.
+    pub fn read<R>(reader: R) -> Result<Self, Error>
+        where R: BufRead,
+    {
+        let mut lines = reader.lines();
+        let header: Header = match lines.next() {
+            Some(line) => serde_json::from_str(&line?)?,
+            None => return Err(Error::MissingHeader),
+        };
+        let mut entries = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(serde_json::from_str(&line)?);
+        }
+        Ok(Recording {
+            window_dimensions: header.window_dimensions,
+            entries: entries,
+        })
+    }
+
+    /// Read a recording from the file at the given path.
+    pub fn open<P>(path: P) -> Result<Self, Error>
+        where P: AsRef<std::path::Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        Self::read(std::io::BufReader::new(file))
+    }
+
+    /// The time at which the last input was received, relative to the beginning of the recording.
+    pub fn duration(&self) -> std::time::Duration {
+        self.entries.last()
+            .map(|entry| entry.time)
+            .unwrap_or_else(|| std::time::Duration::from_secs(0))
+    }
+
+}
+
+impl Recorder {
+
+    /// Begin a new recording, writing the `Header` to the given `writer` immediately.
+    pub fn new<W>(writer: W, window_dimensions: Dimensions) -> Self
+        where W: Write + Send + 'static,
+    {
+        let mut recorder = Recorder {
+            writer: Box::new(writer),
+            start: std::time::Instant::now(),
+            maybe_error: None,
+        };
+        let header = Header { window_dimensions: window_dimensions };
+        recorder.write_line(&header);
+        recorder
+    }
+
+    /// Begin a new recording, written to a file created at the given path.
+    pub fn create<P>(path: P, window_dimensions: Dimensions) -> Result<Self, Error>
+        where P: AsRef<std::path::Path>,
+    {
+        let file = std::fs::File::create(path)?;
+        Ok(Self::new(std::io::BufWriter::new(file), window_dimensions))
+    }
+
+    /// Record the given `input` as having been received now.
+    pub fn record(&mut self, input: &Input) {
+        let entry = Entry {
+            time: self.start.elapsed(),
+            input: input.clone(),
+        };
+        self.write_line(&entry);
+    }
+
+    /// Flush the recording, returning the first error that occurred while writing if any.
+    pub fn finish(mut self) -> Result<(), Error> {
+        if let Some(err) = self.maybe_error.take() {
+            return Err(err);
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn write_line<T>(&mut self, value: &T)
+        where T: ::serde::Serialize,
+    {
+        if self.maybe_error.is_some() {
+            return;
+        }
+        let result = serde_json::to_writer(&mut self.writer, value)
+            .map_err(Error::from)
+            .and_then(|()| self.writer.write_all(b"\n").map_err(Error::from));
+        if let Err(err) = result {
+            self.maybe_error = Some(err);
+        }
+    }
+
+}
+
+impl Player {
+
+    /// A player that will begin from the first entry of the given recording.
+    pub fn new(recording: Recording) -> Self {
+        Player {
+            recording: recording,
+            next_entry: 0,
+            has_resized: false,
+            maybe_clock: None,
+        }
+    }
+
+    /// Advance the given clock to the recorded time of each entry before it is fed to the `Ui`.
+    ///
+    /// When the same clock is given to the `UiBuilder`, timing-dependent events such as
+    /// `DoubleClick`s are reproduced exactly as they were recorded.
+    pub fn clock(mut self, clock: clock::Manual) -> Self {
+        let elapsed = clock.elapsed();
+        self.maybe_clock = Some((clock, elapsed));
+        self
+    }
+
+    /// The recording being played.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Whether or not every entry has been fed to the `Ui`.
+    pub fn is_finished(&self) -> bool {
+        self.next_entry >= self.recording.entries.len()
+    }
+
+    /// Feed every entry received at or before the given `time` into the `ui`.
+    ///
+    /// Before the first entry is fed, the `ui` is resized to the recorded window dimensions.
+    ///
+    /// Returns the number of entries that were fed.
+    pub fn play_until(&mut self, ui: &mut Ui, time: std::time::Duration) -> usize {
+        if !self.has_resized {
+            let [w, h] = self.recording.window_dimensions;
+            ui.handle_event(Input::Resize(w, h));
+            self.has_resized = true;
+        }
+        let start = self.next_entry;
+        while let Some(entry) = self.recording.entries.get(self.next_entry) {
+            if entry.time > time {
+                break;
+            }
+            if let Some((ref clock, elapsed)) = self.maybe_clock {
+                clock.set_elapsed(elapsed + entry.time);
+            }
+            ui.handle_event(entry.input.clone());
+            self.next_entry += 1;
+        }
+        self.next_entry - start
+    }
+
+    /// Feed all remaining entries into the `ui`.
+    ///
+    /// Returns the number of entries that were fed.
+    pub fn play_all(&mut self, ui: &mut Ui) -> usize {
+        let end = self.recording.duration();
+        self.play_until(ui, end)
+    }
+
+}
+
+impl std::fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("Recorder")
+            .field("start", &self.start)
+            .field("maybe_error", &self.maybe_error)
+            .finish()
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Error::Io(ref e) => Some(e),
+            Error::Json(ref e) => Some(e),
+            Error::MissingHeader => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            Error::Io(ref e) => std::fmt::Display::fmt(e, f),
+            Error::Json(ref e) => std::fmt::Display::fmt(e, f),
+            Error::MissingHeader => write!(f, "The recording did not begin with a header."),
+        }
+    }
+}
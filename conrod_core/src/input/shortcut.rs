@@ -0,0 +1,226 @@
+//! Keyboard shortcuts (also known as accelerators) that may be registered with the `Ui`.
+//!
+//! A shortcut associates a key `Chord` with a `Scope` in which it is active. Shortcuts are
+//! registered via the `Ui`'s `shortcuts` field. Whenever a registered chord is pressed within its
+//! scope, the `Ui` emits an `event::Ui::Shortcut` rather than delivering the key press to the
+//! widget capturing the keyboard.
+
+use graph::Graph;
+use input::{Key, ModifierKey};
+use std;
+use widget;
+
+/// A unique identifier for a shortcut within a `Registry`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Id(usize);
+
+/// A key along with the modifier keys that must be held when it is pressed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Chord {
+    /// The key that triggers the shortcut.
+    pub key: Key,
+    /// The exact set of modifier keys that must be held when the `key` is pressed.
+    pub modifiers: ModifierKey,
+}
+
+/// Describes where a shortcut is active.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// The shortcut is active regardless of which widget is capturing the keyboard.
+    Global,
+    /// The shortcut is only active while the given widget or one of its descendants is capturing
+    /// the keyboard.
+    Widget(widget::Id),
+}
+
+/// A chord registered within some scope.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Shortcut {
+    /// The chord that triggers the shortcut.
+    pub chord: Chord,
+    /// Where the shortcut is active.
+    pub scope: Scope,
+}
+
+/// Returned when attempting to register a chord that is already registered within the same
+/// scope.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Conflict {
+    /// The shortcut that is already registered with the chord.
+    pub existing: Id,
+    /// The shortcut that could not be registered.
+    pub shortcut: Shortcut,
+}
+
+/// Stores all shortcuts registered with a `Ui`.
+#[derive(Clone, Debug, Default)]
+pub struct Registry {
+    shortcuts: Vec<(Id, Shortcut)>,
+    next_id: usize,
+}
+
+/// The modifier key used for common shortcuts on the target platform.
+///
+/// This is `GUI` (i.e. the command key) on macOS and `CTRL` everywhere else.
+pub fn primary_modifier() -> ModifierKey {
+    if cfg!(target_os = "macos") {
+        ModifierKey::GUI
+    } else {
+        ModifierKey::CTRL
+    }
+}
+
+impl Chord {
+
+    /// A chord for the given key with no modifiers.
+    pub fn new(key: Key) -> Self {
+        Chord {
+            key: key,
+            modifiers: ModifierKey::NO_MODIFIER,
+        }
+    }
+
+    /// Require the platform's primary modifier (see `primary_modifier`).
+    pub fn primary(mut self) -> Self {
+        self.modifiers.insert(primary_modifier());
+        self
+    }
+
+    /// Require the `CTRL` modifier.
+    pub fn ctrl(mut self) -> Self {
+        self.modifiers.insert(ModifierKey::CTRL);
+        self
+    }
+
+    /// Require the `SHIFT` modifier.
+    pub fn shift(mut self) -> Self {
+        self.modifiers.insert(ModifierKey::SHIFT);
+        self
+    }
+
+    /// Require the `ALT` modifier.
+    pub fn alt(mut self) -> Self {
+        self.modifiers.insert(ModifierKey::ALT);
+        self
+    }
+
+    /// Require the `GUI` modifier.
+    pub fn gui(mut self) -> Self {
+        self.modifiers.insert(ModifierKey::GUI);
+        self
+    }
+
+    /// Whether or not pressing the chord would otherwise enter text, i.e. it is a printable key
+    /// held with no modifiers other than `SHIFT`.
+    ///
+    /// Such chords are not triggered while a widget that accepts text input is capturing the
+    /// keyboard so that they do not interfere with typing.
+    pub fn is_text_entry(&self) -> bool {
+        let code = self.key as u32;
+        let is_printable = code >= 0x20 && code < 0x7F;
+        let command_modifiers = ModifierKey::CTRL | ModifierKey::ALT | ModifierKey::GUI;
+        is_printable && !self.modifiers.intersects(command_modifiers)
+    }
+
+}
+
+impl Registry {
+
+    /// An empty registry.
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    /// Register a shortcut triggered by the given `chord` within the given `scope`.
+    ///
+    /// Returns a `Conflict` if the chord is already registered within the same scope. Registering
+    /// the same chord within different scopes is allowed, in which case the innermost scope
+    /// containing the widget capturing the keyboard takes precedence.
+    pub fn register(&mut self, chord: Chord, scope: Scope) -> Result<Id, Conflict> {
+        let shortcut = Shortcut { chord: chord, scope: scope };
+        if let Some(existing) = self.find_in_scope(chord, scope) {
+            return Err(Conflict { existing: existing, shortcut: shortcut });
+        }
+        let id = Id(self.next_id);
+        self.next_id += 1;
+        self.shortcuts.push((id, shortcut));
+        Ok(id)
+    }
+
+    /// Remove the shortcut with the given `Id`, returning it if it was registered.
+    pub fn unregister(&mut self, id: Id) -> Option<Shortcut> {
+        self.shortcuts.iter()
+            .position(|&(shortcut_id, _)| shortcut_id == id)
+            .map(|idx| self.shortcuts.remove(idx).1)
+    }
+
+    /// Remove all registered shortcuts.
+    pub fn clear(&mut self) {
+        self.shortcuts.clear();
+    }
+
+    /// The shortcut with the given `Id` if it is registered.
+    pub fn get(&self, id: Id) -> Option<&Shortcut> {
+        self.shortcuts.iter()
+            .find(|&&(shortcut_id, _)| shortcut_id == id)
+            .map(|&(_, ref shortcut)| shortcut)
+    }
+
+    /// The shortcut registered with the given `chord` within exactly the given `scope`.
+    pub fn find_in_scope(&self, chord: Chord, scope: Scope) -> Option<Id> {
+        self.shortcuts.iter()
+            .find(|&&(_, shortcut)| shortcut.chord == chord && shortcut.scope == scope)
+            .map(|&(id, _)| id)
+    }
+
+    /// Find the shortcut triggered by pressing `chord` while the given widget is capturing the
+    /// keyboard.
+    ///
+    /// Widget scopes are searched from the capturing widget up through its ancestors within the
+    /// `graph`, before falling back to the `Global` scope.
+    pub fn find(&self,
+                chord: Chord,
+                maybe_capturing: Option<widget::Id>,
+                graph: &Graph) -> Option<(Id, Scope)>
+    {
+        if self.shortcuts.is_empty() {
+            return None;
+        }
+
+        // Leave text entry to widgets that accept text input.
+        if chord.is_text_entry() {
+            let is_text_input = maybe_capturing
+                .and_then(|id| graph.widget(id))
+                .map(|container| container.is_text_input)
+                .unwrap_or(false);
+            if is_text_input {
+                return None;
+            }
+        }
+
+        let mut maybe_id = maybe_capturing;
+        while let Some(id) = maybe_id {
+            let scope = Scope::Widget(id);
+            if let Some(shortcut_id) = self.find_in_scope(chord, scope) {
+                return Some((shortcut_id, scope));
+            }
+            maybe_id = graph.depth_parent(id);
+        }
+
+        self.find_in_scope(chord, Scope::Global).map(|id| (id, Scope::Global))
+    }
+
+}
+
+impl std::error::Error for Conflict {
+    fn description(&self) -> &str {
+        "The chord is already registered within the same scope."
+    }
+}
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "The chord {:?} is already registered within the scope {:?} by {:?}",
+               self.shortcut.chord, self.shortcut.scope, self.existing)
+    }
+}
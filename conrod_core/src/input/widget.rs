@@ -127,6 +127,34 @@ pub struct Taps<'a> {
     events: Events<'a>,
 }
 
+/// An `Iterator` yielding all touch screen long presses occuring within the given sequence of
+/// `widget::Event`s.
+#[derive(Clone)]
+pub struct LongPresses<'a> {
+    events: Events<'a>,
+}
+
+/// An `Iterator` yielding all two-finger pinches occuring within the given sequence of
+/// `widget::Event`s.
+#[derive(Clone)]
+pub struct Pinches<'a> {
+    events: Events<'a>,
+}
+
+/// An `Iterator` yielding all two-finger rotations occuring within the given sequence of
+/// `widget::Event`s.
+#[derive(Clone)]
+pub struct Rotations<'a> {
+    events: Events<'a>,
+}
+
+/// An `Iterator` yielding all two-finger pans occuring within the given sequence of
+/// `widget::Event`s.
+#[derive(Clone)]
+pub struct Pans<'a> {
+    events: Events<'a>,
+}
+
 /// An iterator that yields all `event::Drag` events yielded by the `Events` iterator.
 ///
 /// Only events that occurred while the widget was capturing the device that did the dragging will
@@ -136,6 +164,30 @@ pub struct Drags<'a> {
     events: Events<'a>,
 }
 
+/// An iterator that yields all `event::DragCancel` events yielded by the `Events` iterator.
+#[derive(Clone)]
+pub struct DragCancels<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::DropEvent` events yielded by the `Events` iterator.
+#[derive(Clone)]
+pub struct Drops<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::FileHover` events yielded by the `Events` iterator.
+#[derive(Clone)]
+pub struct FileHovers<'a> {
+    events: Events<'a>,
+}
+
+/// An iterator that yields all `event::FileDrop` events yielded by the `Events` iterator.
+#[derive(Clone)]
+pub struct FileDrops<'a> {
+    events: Events<'a>,
+}
+
 /// An `Iterator` yielding all mouse `button` drags occuring within the given sequence of
 /// `widget::Drag`s.
 #[derive(Clone)]
@@ -232,6 +284,31 @@ impl<'a> Widget<'a> {
         Taps { events: self.events() }
     }
 
+    /// Filters all events yielded by `Self::events` for all `event::LongPress`es.
+    ///
+    /// A _long press_ occurs when a touch that began over the widget is held in place for the
+    /// `theme.long_press_duration`.
+    pub fn long_presses(&self) -> LongPresses<'a> {
+        LongPresses { events: self.events() }
+    }
+
+    /// Filters all events yielded by `Self::events` for all `event::Pinch`es.
+    ///
+    /// Useful for zooming in response to two touches moving towards or away from one another.
+    pub fn pinches(&self) -> Pinches<'a> {
+        Pinches { events: self.events() }
+    }
+
+    /// Filters all events yielded by `Self::events` for all `event::Rotate`s.
+    pub fn rotations(&self) -> Rotations<'a> {
+        Rotations { events: self.events() }
+    }
+
+    /// Filters all events yielded by `Self::events` for all `event::Pan`s.
+    pub fn pans(&self) -> Pans<'a> {
+        Pans { events: self.events() }
+    }
+
     /// Produces an iterator that yields all `event::Drag` events yielded by the `Events` iterator.
     ///
     /// Only events that occurred while the widget was capturing the device that did the dragging
@@ -240,6 +317,42 @@ impl<'a> Widget<'a> {
         Drags { events: self.events() }
     }
 
+    /// Produces an iterator that yields all `event::DragCancel` events yielded by the `Events`
+    /// iterator.
+    ///
+    /// These are produced for drags that began over the widget and were cancelled before the
+    /// dragging button was released.
+    pub fn drag_cancels(&self) -> DragCancels<'a> {
+        DragCancels { events: self.events() }
+    }
+
+    /// Produces an iterator that yields all `event::DropEvent` events yielded by the `Events`
+    /// iterator.
+    ///
+    /// These are produced for drags that were dropped onto the widget, as well as for drags that
+    /// began from the widget and were dropped elsewhere.
+    pub fn drops(&self) -> Drops<'a> {
+        Drops { events: self.events() }
+    }
+
+    /// Produces an iterator that yields all `event::FileHover` events yielded by the `Events`
+    /// iterator.
+    ///
+    /// These are produced for files dragged over the window from the OS while the widget is under
+    /// the mouse cursor.
+    pub fn file_hovers(&self) -> FileHovers<'a> {
+        FileHovers { events: self.events() }
+    }
+
+    /// Produces an iterator that yields all `event::FileDrop` events yielded by the `Events`
+    /// iterator.
+    ///
+    /// These are produced for files dropped onto the window from the OS while the widget is under
+    /// the mouse cursor.
+    pub fn file_drops(&self) -> FileDrops<'a> {
+        FileDrops { events: self.events() }
+    }
+
     /// Produces an iterator that yields all `Input::Text` events that have occurred as `&str`s
     /// since the last time `Ui::set_widgets` was called.
     ///
@@ -447,6 +560,12 @@ impl<'a> Iterator for Events<'a> {
                     }
                 },
 
+                event::Ui::MouseEnter(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseEnter),
+
+                event::Ui::MouseLeave(idx) if idx == self.idx =>
+                    return Some(event::Widget::MouseLeave),
+
                 event::Ui::WindowResized(dim) =>
                     return Some(event::Widget::WindowResized(dim)),
 
@@ -474,9 +593,39 @@ impl<'a> Iterator for Events<'a> {
                 event::Ui::Tap(idx, ref tap) if idx == Some(self.idx) =>
                     return Some(tap.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::LongPress(idx, long_press) if idx == Some(self.idx) =>
+                    return Some(long_press.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Pinch(idx, pinch) if idx == Some(self.idx) =>
+                    return Some(pinch.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Rotate(idx, rotate) if idx == Some(self.idx) =>
+                    return Some(rotate.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Pan(idx, pan) if idx == Some(self.idx) =>
+                    return Some(pan.relative_to(self.rect.xy()).into()),
+
                 event::Ui::Drag(idx, ref drag) if idx == Some(self.idx) =>
                     return Some(drag.clone().relative_to(self.rect.xy()).into()),
 
+                event::Ui::DragCancel(idx, ref drag_cancel) if idx == Some(self.idx) =>
+                    return Some(drag_cancel.relative_to(self.rect.xy()).into()),
+
+                event::Ui::Drop(idx, drop) if idx == self.idx || drop.source == self.idx =>
+                    return Some(drop.relative_to(self.rect.xy()).into()),
+
+                event::Ui::FileHover(idx, ref file_hover) if idx == Some(self.idx) =>
+                    return Some(file_hover.relative_to(self.rect.xy()).into()),
+
+                event::Ui::FileDrop(idx, ref file_drop) if idx == Some(self.idx) =>
+                    return Some(file_drop.relative_to(self.rect.xy()).into()),
+
+                event::Ui::FileHoverCancel(idx) if idx == Some(self.idx) =>
+                    return Some(event::Widget::FileHoverCancel),
+
+                event::Ui::Shortcut(idx, shortcut) if idx == Some(self.idx) =>
+                    return Some(event::Widget::Shortcut(shortcut)),
+
                 event::Ui::Scroll(idx, ref scroll) if idx == Some(self.idx) =>
                     return Some(scroll.clone().into()),
 
@@ -622,6 +771,54 @@ impl<'a> Iterator for Taps<'a> {
     }
 }
 
+impl<'a> Iterator for LongPresses<'a> {
+    type Item = event::LongPress;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::LongPress(long_press) = event {
+                return Some(long_press);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Pinches<'a> {
+    type Item = event::Pinch;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Pinch(pinch) = event {
+                return Some(pinch);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Rotations<'a> {
+    type Item = event::Rotate;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Rotate(rotate) = event {
+                return Some(rotate);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Pans<'a> {
+    type Item = event::Pan;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Pan(pan) = event {
+                return Some(pan);
+            }
+        }
+        None
+    }
+}
+
 impl<'a> Iterator for Drags<'a> {
     type Item = event::Drag;
     fn next(&mut self) -> Option<Self::Item> {
@@ -646,6 +843,54 @@ impl<'a> Iterator for ButtonDrags<'a> {
     }
 }
 
+impl<'a> Iterator for DragCancels<'a> {
+    type Item = event::DragCancel;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::DragCancel(drag_cancel) = event {
+                return Some(drag_cancel);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for Drops<'a> {
+    type Item = event::DropEvent;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::Drop(drop) = event {
+                return Some(drop);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for FileHovers<'a> {
+    type Item = event::FileHover;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::FileHover(file_hover) = event {
+                return Some(file_hover);
+            }
+        }
+        None
+    }
+}
+
+impl<'a> Iterator for FileDrops<'a> {
+    type Item = event::FileDrop;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(event) = self.events.next() {
+            if let event::Widget::FileDrop(file_drop) = event {
+                return Some(file_drop);
+            }
+        }
+        None
+    }
+}
+
 impl<'a> Iterator for Texts<'a> {
     type Item = event::Text;
     fn next(&mut self) -> Option<Self::Item> {
@@ -14,6 +14,9 @@ extern crate fnv;
 extern crate num;
 extern crate input as piston_input;
 extern crate rusttype;
+#[cfg(feature = "serde")] extern crate serde;
+#[cfg(feature = "serde")] #[macro_use] extern crate serde_derive;
+#[cfg(feature = "serde")] extern crate serde_json;
 
 pub use color::{Color, Colorable};
 pub use conrod_derive::*;
@@ -25,6 +28,7 @@ pub use ui::{Ui, UiCell, UiBuilder};
 pub use widget::{scroll, Widget};
 
 mod border;
+pub mod clock;
 pub mod color;
 pub mod event;
 pub mod graph;
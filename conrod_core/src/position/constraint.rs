@@ -0,0 +1,748 @@
+//! A linear constraint layout solver for relating the edges and dimensions of many widgets.
+//!
+//! The `Position` and `Dimension` types can only describe a widget relative to a single other
+//! widget on each axis. Constraints lift this restriction by allowing arbitrary linear
+//! relationships between the **x**, **y**, **width** and **height** of any number of widgets, e.g.
+//! "A's width equals B's width and together they fill the row minus padding".
+//!
+//! Constraints are built from `Expression`s over the `Variable`s of widgets and are given a
+//! `Strength`. Required constraints must always be satisfied, while non-required constraints are
+//! satisfied as closely as possible in order of strength. This is the same model as the Cassowary
+//! constraint solving toolkit and the `Solver` is an implementation of its incremental simplex
+//! algorithm.
+//!
+//! Constraints are registered during each `set_widgets` stage via `UiCell::constrain` and are
+//! resolved by the `Ui` before each constrained widget is positioned.
+//!
+//! ```ignore
+//! use conrod_core::position::constraint::{left, right, width};
+//!
+//! // Both buttons share the row equally.
+//! ui.constrain(width(ids.a).eq(width(ids.b)));
+//! ui.constrain(left(ids.a).eq(left(ids.canvas) + 10.0));
+//! ui.constrain(left(ids.b).eq(right(ids.a) + 10.0));
+//! ui.constrain(right(ids.b).eq(right(ids.canvas) - 10.0));
+//! ```
+
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul, Neg, Sub};
+use widget;
+use super::Scalar;
+
+/// The strength of a constraint, used to resolve conflicts between constraints.
+pub type Strength = f64;
+
+/// A constraint that must always be satisfied.
+///
+/// Required constraints that conflict with previously added required constraints are ignored.
+pub const REQUIRED: Strength = 1_001_001_000.0;
+/// A strong preference that will only be broken by required constraints.
+pub const STRONG: Strength = 1_000_000.0;
+/// A medium preference.
+pub const MEDIUM: Strength = 1_000.0;
+/// A weak preference, broken in favour of any stronger constraint.
+pub const WEAK: Strength = 1.0;
+
+/// An attribute of a widget that may be solved for.
+///
+/// **X** and **Y** describe the middle of the widget's `Rect`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Variable {
+    /// The position of the middle of the widget along the *x* axis.
+    X(widget::Id),
+    /// The position of the middle of the widget along the *y* axis.
+    Y(widget::Id),
+    /// The width of the widget.
+    W(widget::Id),
+    /// The height of the widget.
+    H(widget::Id),
+}
+
+/// A linear combination of `Variable`s plus some constant.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Expression {
+    /// Each variable paired with its coefficient.
+    pub terms: Vec<(Variable, Scalar)>,
+    /// The constant added to the sum of the terms.
+    pub constant: Scalar,
+}
+
+/// The relation between both sides of a `Constraint`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Relation {
+    /// `lhs == rhs`
+    Equal,
+    /// `lhs <= rhs`
+    LessOrEqual,
+    /// `lhs >= rhs`
+    GreaterOrEqual,
+}
+
+/// A linear relationship between two `Expression`s along with the strength with which it should
+/// be satisfied.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraint {
+    /// The left hand side of the constraint subtracted by the right hand side.
+    pub expression: Expression,
+    /// How the `expression` relates to zero.
+    pub relation: Relation,
+    /// The strength with which the constraint should be satisfied.
+    pub strength: Strength,
+}
+
+/// An error returned by the `Solver` when a constraint or edit cannot be applied.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The required constraint conflicts with the required constraints already added.
+    Unsatisfiable,
+    /// The constraints leave some variable unbounded in the direction being optimised.
+    Unbounded,
+    /// The variable has already been added to the solver as an edit variable.
+    DuplicateEditVariable,
+    /// A value was suggested for a variable that was not added as an edit variable.
+    UnknownEditVariable,
+}
+
+/// An expression describing the position of the left edge of the widget.
+pub fn left(id: widget::Id) -> Expression {
+    Expression::from(Variable::X(id)) - width(id) * 0.5
+}
+
+/// An expression describing the position of the right edge of the widget.
+pub fn right(id: widget::Id) -> Expression {
+    Expression::from(Variable::X(id)) + width(id) * 0.5
+}
+
+/// An expression describing the position of the bottom edge of the widget.
+pub fn bottom(id: widget::Id) -> Expression {
+    Expression::from(Variable::Y(id)) - height(id) * 0.5
+}
+
+/// An expression describing the position of the top edge of the widget.
+pub fn top(id: widget::Id) -> Expression {
+    Expression::from(Variable::Y(id)) + height(id) * 0.5
+}
+
+/// An expression describing the middle of the widget along the *x* axis.
+pub fn middle_x(id: widget::Id) -> Expression {
+    Variable::X(id).into()
+}
+
+/// An expression describing the middle of the widget along the *y* axis.
+pub fn middle_y(id: widget::Id) -> Expression {
+    Variable::Y(id).into()
+}
+
+/// An expression describing the width of the widget.
+pub fn width(id: widget::Id) -> Expression {
+    Variable::W(id).into()
+}
+
+/// An expression describing the height of the widget.
+pub fn height(id: widget::Id) -> Expression {
+    Variable::H(id).into()
+}
+
+
+impl Variable {
+
+    /// The widget to which the variable belongs.
+    pub fn widget_id(&self) -> widget::Id {
+        match *self {
+            Variable::X(id) | Variable::Y(id) | Variable::W(id) | Variable::H(id) => id,
+        }
+    }
+
+}
+
+impl Expression {
+
+    /// An expression with no terms and the given constant.
+    pub fn constant(constant: Scalar) -> Self {
+        Expression { terms: Vec::new(), constant: constant }
+    }
+
+    /// Constrain this expression to be equal to the given expression.
+    pub fn eq<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Constraint::new(self - rhs.into(), Relation::Equal)
+    }
+
+    /// Constrain this expression to be less than or equal to the given expression.
+    pub fn le<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Constraint::new(self - rhs.into(), Relation::LessOrEqual)
+    }
+
+    /// Constrain this expression to be greater than or equal to the given expression.
+    pub fn ge<E: Into<Expression>>(self, rhs: E) -> Constraint {
+        Constraint::new(self - rhs.into(), Relation::GreaterOrEqual)
+    }
+
+}
+
+impl Constraint {
+
+    /// A new **REQUIRED** constraint relating the given expression to zero.
+    pub fn new(expression: Expression, relation: Relation) -> Self {
+        Constraint {
+            expression: expression,
+            relation: relation,
+            strength: REQUIRED,
+        }
+    }
+
+    /// Specify the strength with which the constraint should be satisfied.
+    ///
+    /// The strength is clamped to the range `0.0..REQUIRED`.
+    pub fn strength(mut self, strength: Strength) -> Self {
+        self.strength = ::utils::clamp(strength, 0.0, REQUIRED);
+        self
+    }
+
+}
+
+
+impl From<Variable> for Expression {
+    fn from(variable: Variable) -> Self {
+        Expression { terms: vec![(variable, 1.0)], constant: 0.0 }
+    }
+}
+
+impl From<Scalar> for Expression {
+    fn from(constant: Scalar) -> Self {
+        Expression::constant(constant)
+    }
+}
+
+impl<E: Into<Expression>> Add<E> for Expression {
+    type Output = Expression;
+    fn add(mut self, rhs: E) -> Expression {
+        let rhs = rhs.into();
+        self.terms.extend(rhs.terms);
+        self.constant += rhs.constant;
+        self
+    }
+}
+
+impl<E: Into<Expression>> Sub<E> for Expression {
+    type Output = Expression;
+    fn sub(self, rhs: E) -> Expression {
+        self + -rhs.into()
+    }
+}
+
+impl Mul<Scalar> for Expression {
+    type Output = Expression;
+    fn mul(mut self, rhs: Scalar) -> Expression {
+        for term in &mut self.terms {
+            term.1 *= rhs;
+        }
+        self.constant *= rhs;
+        self
+    }
+}
+
+impl Neg for Expression {
+    type Output = Expression;
+    fn neg(self) -> Expression {
+        self * -1.0
+    }
+}
+
+
+/// An incremental simplex solver for a system of `Constraint`s.
+///
+/// Constraints may be added one at a time, after which the value of each variable may be
+/// retrieved. Variables that are not fully determined by the constraints are given whichever value
+/// the solver finds first, so it is worth adding a weak constraint for each variable describing
+/// its preferred value.
+///
+/// Variables that change often (e.g. while dragging) may be added as edit variables, after which
+/// new values may be suggested for them without rebuilding the system.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    variables: BTreeMap<Variable, Symbol>,
+    rows: BTreeMap<Symbol, Row>,
+    objective: Row,
+    artificial: Option<Row>,
+    edits: BTreeMap<Variable, Edit>,
+    infeasible_rows: Vec<Symbol>,
+    next_symbol: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum SymbolKind {
+    Invalid,
+    External,
+    Slack,
+    Error,
+    Dummy,
+}
+
+/// The kind is kept after the index so that ordering between symbols reflects their creation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Symbol(usize, SymbolKind);
+
+/// A row within the simplex tableau, i.e. `constant + sum(coefficient * symbol)`.
+#[derive(Clone, Debug, Default)]
+struct Row {
+    cells: BTreeMap<Symbol, f64>,
+    constant: f64,
+}
+
+/// Marks the symbols introduced for a constraint.
+#[derive(Copy, Clone, Debug)]
+struct Tag {
+    marker: Symbol,
+    other: Symbol,
+}
+
+/// The error symbols of an edit variable's constraint along with its last suggested value.
+#[derive(Copy, Clone, Debug)]
+struct Edit {
+    tag: Tag,
+    constant: f64,
+}
+
+const INVALID: Symbol = Symbol(0, SymbolKind::Invalid);
+
+fn near_zero(value: f64) -> bool {
+    value.abs() < 1.0e-8
+}
+
+
+impl Row {
+
+    fn new(constant: f64) -> Self {
+        Row { cells: BTreeMap::new(), constant: constant }
+    }
+
+    fn insert_symbol(&mut self, symbol: Symbol, coefficient: f64) {
+        let is_zero = {
+            let cell = self.cells.entry(symbol).or_insert(0.0);
+            *cell += coefficient;
+            near_zero(*cell)
+        };
+        if is_zero {
+            self.cells.remove(&symbol);
+        }
+    }
+
+    fn insert_row(&mut self, other: &Row, coefficient: f64) {
+        self.constant += other.constant * coefficient;
+        for (&symbol, &c) in &other.cells {
+            self.insert_symbol(symbol, c * coefficient);
+        }
+    }
+
+    /// Add the given value to the row's constant, returning the result.
+    fn add(&mut self, value: f64) -> f64 {
+        self.constant += value;
+        self.constant
+    }
+
+    fn remove(&mut self, symbol: Symbol) {
+        self.cells.remove(&symbol);
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+        for c in self.cells.values_mut() {
+            *c = -*c;
+        }
+    }
+
+    fn coefficient_for(&self, symbol: Symbol) -> f64 {
+        self.cells.get(&symbol).cloned().unwrap_or(0.0)
+    }
+
+    /// Solve the row (which is equal to zero) for the given symbol.
+    fn solve_for(&mut self, symbol: Symbol) {
+        let coefficient = -1.0 / self.cells.remove(&symbol).unwrap_or(1.0);
+        self.constant *= coefficient;
+        for c in self.cells.values_mut() {
+            *c *= coefficient;
+        }
+    }
+
+    /// Solve the row (which is equal to `lhs`) for `rhs`.
+    fn solve_for_symbols(&mut self, lhs: Symbol, rhs: Symbol) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for(rhs);
+    }
+
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        if let Some(coefficient) = self.cells.remove(&symbol) {
+            self.insert_row(row, coefficient);
+        }
+    }
+
+}
+
+
+impl Solver {
+
+    /// Construct a new, empty solver.
+    pub fn new() -> Self {
+        Solver {
+            variables: BTreeMap::new(),
+            rows: BTreeMap::new(),
+            objective: Row::default(),
+            artificial: None,
+            edits: BTreeMap::new(),
+            infeasible_rows: Vec::new(),
+            next_symbol: 1,
+        }
+    }
+
+    /// Add the given constraint to the system.
+    ///
+    /// If the constraint is required and conflicts with the required constraints already added,
+    /// `Error::Unsatisfiable` is returned and the system is left unchanged.
+    pub fn add_constraint(&mut self, constraint: &Constraint) -> Result<(), Error> {
+        self.add_tagged_constraint(constraint).map(|_| ())
+    }
+
+    /// Add the given variable as an edit variable, allowing for values to be suggested for it via
+    /// `suggest_value`.
+    ///
+    /// Suggested values are held with the given `strength`, which is limited to `STRONG` so that
+    /// suggestions never conflict with required constraints.
+    pub fn add_edit_variable(&mut self, variable: Variable, strength: Strength) -> Result<(), Error> {
+        if self.edits.contains_key(&variable) {
+            return Err(Error::DuplicateEditVariable);
+        }
+        let constraint = Expression::from(variable).eq(0.0).strength(strength.min(STRONG));
+        let tag = self.add_tagged_constraint(&constraint)?;
+        self.edits.insert(variable, Edit { tag: tag, constant: 0.0 });
+        Ok(())
+    }
+
+    /// Suggest a new value for the given edit variable.
+    ///
+    /// The rest of the system is updated incrementally in order to satisfy the suggestion as
+    /// closely as its strength allows.
+    pub fn suggest_value(&mut self, variable: Variable, value: Scalar) -> Result<(), Error> {
+        let (tag, delta) = match self.edits.get_mut(&variable) {
+            Some(edit) => {
+                let delta = value - edit.constant;
+                edit.constant = value;
+                (edit.tag, delta)
+            },
+            None => return Err(Error::UnknownEditVariable),
+        };
+
+        // If either error symbol is basic, only its own row needs updating.
+        if let Some(row) = self.rows.get_mut(&tag.marker) {
+            if row.add(-delta) < 0.0 {
+                self.infeasible_rows.push(tag.marker);
+            }
+            return self.dual_optimize();
+        }
+        if let Some(row) = self.rows.get_mut(&tag.other) {
+            if row.add(delta) < 0.0 {
+                self.infeasible_rows.push(tag.other);
+            }
+            return self.dual_optimize();
+        }
+
+        // Otherwise update each row in which the error symbols appear.
+        for (&symbol, row) in &mut self.rows {
+            let coefficient = row.coefficient_for(tag.marker);
+            if coefficient != 0.0
+            && row.add(delta * coefficient) < 0.0
+            && symbol.1 != SymbolKind::External {
+                self.infeasible_rows.push(symbol);
+            }
+        }
+        self.dual_optimize()
+    }
+
+    /// Add the given constraint to the system, returning the symbols that were introduced for it.
+    fn add_tagged_constraint(&mut self, constraint: &Constraint) -> Result<Tag, Error> {
+        let next_symbol = self.next_symbol;
+        let (mut row, tag) = self.create_row(constraint);
+        let mut subject = choose_subject(&row, &tag);
+
+        if subject == INVALID && row.cells.keys().all(|s| s.1 == SymbolKind::Dummy) {
+            if !near_zero(row.constant) {
+                self.forget_variables_since(next_symbol);
+                return Err(Error::Unsatisfiable);
+            }
+            subject = tag.marker;
+        }
+
+        if subject == INVALID {
+            // Only required constraints get here, so the objective is yet to be touched. Keep a
+            // copy of the tableau in case the row cannot be satisfied.
+            let rows = self.rows.clone();
+            let objective = self.objective.clone();
+            let num_infeasible_rows = self.infeasible_rows.len();
+            let result = match self.add_with_artificial_variable(row) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(Error::Unsatisfiable),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = result {
+                self.rows = rows;
+                self.objective = objective;
+                self.artificial = None;
+                self.infeasible_rows.truncate(num_infeasible_rows);
+                self.forget_variables_since(next_symbol);
+                return Err(err);
+            }
+        } else {
+            row.solve_for(subject);
+            self.substitute(subject, &row);
+            self.rows.insert(subject, row);
+        }
+
+        self.optimize(false)?;
+        Ok(tag)
+    }
+
+    /// The current value of the given variable.
+    ///
+    /// Returns `None` if the variable is not referred to by any constraint.
+    pub fn value(&self, variable: Variable) -> Option<Scalar> {
+        self.variables.get(&variable).map(|symbol| {
+            self.rows.get(symbol).map(|row| row.constant).unwrap_or(0.0)
+        })
+    }
+
+    /// Forget the variables that were first referred to by a constraint that was rejected.
+    fn forget_variables_since(&mut self, next_symbol: usize) {
+        let forgotten: Vec<_> = self.variables.iter()
+            .filter(|&(_, symbol)| symbol.0 >= next_symbol)
+            .map(|(&variable, _)| variable)
+            .collect();
+        for variable in forgotten {
+            self.variables.remove(&variable);
+        }
+    }
+
+    fn new_symbol(&mut self, kind: SymbolKind) -> Symbol {
+        let symbol = Symbol(self.next_symbol, kind);
+        self.next_symbol += 1;
+        symbol
+    }
+
+    fn variable_symbol(&mut self, variable: Variable) -> Symbol {
+        if let Some(&symbol) = self.variables.get(&variable) {
+            return symbol;
+        }
+        let symbol = self.new_symbol(SymbolKind::External);
+        self.variables.insert(variable, symbol);
+        symbol
+    }
+
+    /// Convert the constraint into a row expressed in terms of the current non-basic symbols.
+    fn create_row(&mut self, constraint: &Constraint) -> (Row, Tag) {
+        let mut row = Row::new(constraint.expression.constant);
+        for &(variable, coefficient) in &constraint.expression.terms {
+            if near_zero(coefficient) {
+                continue;
+            }
+            let symbol = self.variable_symbol(variable);
+            match self.rows.get(&symbol) {
+                Some(basic) => row.insert_row(basic, coefficient),
+                None => row.insert_symbol(symbol, coefficient),
+            }
+        }
+
+        let is_required = constraint.strength >= REQUIRED;
+        let mut tag = Tag { marker: INVALID, other: INVALID };
+        match constraint.relation {
+            Relation::LessOrEqual | Relation::GreaterOrEqual => {
+                let coefficient = match constraint.relation {
+                    Relation::LessOrEqual => 1.0,
+                    _ => -1.0,
+                };
+                let slack = self.new_symbol(SymbolKind::Slack);
+                tag.marker = slack;
+                row.insert_symbol(slack, coefficient);
+                if !is_required {
+                    let error = self.new_symbol(SymbolKind::Error);
+                    tag.other = error;
+                    row.insert_symbol(error, -coefficient);
+                    self.objective.insert_symbol(error, constraint.strength);
+                }
+            },
+            Relation::Equal => {
+                if is_required {
+                    let dummy = self.new_symbol(SymbolKind::Dummy);
+                    tag.marker = dummy;
+                    row.insert_symbol(dummy, 1.0);
+                } else {
+                    let plus = self.new_symbol(SymbolKind::Error);
+                    let minus = self.new_symbol(SymbolKind::Error);
+                    tag.marker = plus;
+                    tag.other = minus;
+                    row.insert_symbol(plus, -1.0);
+                    row.insert_symbol(minus, 1.0);
+                    self.objective.insert_symbol(plus, constraint.strength);
+                    self.objective.insert_symbol(minus, constraint.strength);
+                }
+            },
+        }
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+
+        (row, tag)
+    }
+
+    /// Add the row to the tableau using an artificial variable to find a feasible basis.
+    ///
+    /// Returns `false` if the row could not be satisfied.
+    fn add_with_artificial_variable(&mut self, row: Row) -> Result<bool, Error> {
+        let artificial = self.new_symbol(SymbolKind::Slack);
+        self.rows.insert(artificial, row.clone());
+        self.artificial = Some(row);
+        self.optimize(true)?;
+        let is_satisfied = self.artificial.take().map(|a| near_zero(a.constant)).unwrap_or(false);
+
+        if let Some(mut row) = self.rows.remove(&artificial) {
+            if row.cells.is_empty() {
+                return Ok(is_satisfied);
+            }
+            let entering = row.cells.keys()
+                .find(|s| s.1 == SymbolKind::Slack || s.1 == SymbolKind::Error)
+                .cloned();
+            match entering {
+                None => return Ok(false),
+                Some(entering) => {
+                    row.solve_for_symbols(artificial, entering);
+                    self.substitute(entering, &row);
+                    self.rows.insert(entering, row);
+                },
+            }
+        }
+
+        for row in self.rows.values_mut() {
+            row.remove(artificial);
+        }
+        self.objective.remove(artificial);
+        Ok(is_satisfied)
+    }
+
+    /// Substitute the row for the given symbol throughout the tableau and objectives.
+    ///
+    /// Rows left with a negative constant are marked as infeasible for the next `dual_optimize`.
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        for (&other_symbol, other) in &mut self.rows {
+            other.substitute(symbol, row);
+            if other_symbol.1 != SymbolKind::External && other.constant < 0.0 {
+                self.infeasible_rows.push(other_symbol);
+            }
+        }
+        self.objective.substitute(symbol, row);
+        if let Some(ref mut artificial) = self.artificial {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    /// Pivot until the objective (or the artificial objective) can no longer be reduced.
+    fn optimize(&mut self, artificial: bool) -> Result<(), Error> {
+        loop {
+            let entering = {
+                let objective = match artificial {
+                    true => match self.artificial {
+                        Some(ref row) => row,
+                        None => return Ok(()),
+                    },
+                    false => &self.objective,
+                };
+                objective.cells.iter()
+                    .find(|&(s, &c)| s.1 != SymbolKind::Dummy && c < 0.0)
+                    .map(|(&s, _)| s)
+            };
+            let entering = match entering {
+                Some(symbol) => symbol,
+                None => return Ok(()),
+            };
+
+            // Find the row whose constant most restricts the entering symbol.
+            let mut ratio = f64::MAX;
+            let mut leaving = None;
+            for (&symbol, row) in &self.rows {
+                if symbol.1 == SymbolKind::External {
+                    continue;
+                }
+                let coefficient = row.coefficient_for(entering);
+                if coefficient < 0.0 {
+                    let r = -row.constant / coefficient;
+                    if r < ratio {
+                        ratio = r;
+                        leaving = Some(symbol);
+                    }
+                }
+            }
+            let leaving = match leaving {
+                Some(symbol) => symbol,
+                None => return Err(Error::Unbounded),
+            };
+
+            let mut row = self.rows.remove(&leaving).expect("leaving row must exist");
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+
+    /// Pivot infeasible rows back to feasibility while keeping the objective optimal.
+    ///
+    /// Used after suggested values have changed the constants of the tableau.
+    fn dual_optimize(&mut self) -> Result<(), Error> {
+        while let Some(leaving) = self.infeasible_rows.pop() {
+            let entering = match self.rows.get(&leaving) {
+                Some(row) if !near_zero(row.constant) && row.constant < 0.0 => {
+                    let mut ratio = f64::MAX;
+                    let mut entering = None;
+                    for (&symbol, &coefficient) in &row.cells {
+                        if coefficient > 0.0 && symbol.1 != SymbolKind::Dummy {
+                            let r = self.objective.coefficient_for(symbol) / coefficient;
+                            if r < ratio {
+                                ratio = r;
+                                entering = Some(symbol);
+                            }
+                        }
+                    }
+                    match entering {
+                        Some(symbol) => symbol,
+                        None => return Err(Error::Unsatisfiable),
+                    }
+                },
+                _ => continue,
+            };
+
+            let mut row = self.rows.remove(&leaving).expect("leaving row must exist");
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+        Ok(())
+    }
+
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+/// Choose the symbol for which the new row should be solved, or `INVALID` if there is none.
+fn choose_subject(row: &Row, tag: &Tag) -> Symbol {
+    if let Some(&symbol) = row.cells.keys().find(|s| s.1 == SymbolKind::External) {
+        return symbol;
+    }
+    for &symbol in &[tag.marker, tag.other] {
+        match symbol.1 {
+            SymbolKind::Slack | SymbolKind::Error if row.coefficient_for(symbol) < 0.0 =>
+                return symbol,
+            _ => (),
+        }
+    }
+    INVALID
+}
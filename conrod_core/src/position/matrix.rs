@@ -1,149 +1,412 @@
-use {Backend, CharacterCache, Ui};
-use super::{Depth, Dimension, Dimensions, Point, Position, Positionable, Scalar, Sizeable};
+//! A grid layout for placing widgets of any type within cells of explicitly sized columns and
+//! rows.
+//!
+//! Unlike `widget::Matrix`, whose elements all share the same size and widget type, the
+//! `position::Matrix` allows each column and row **Track** to be sized independently and each
+//! **Cell** to span several tracks and align its widget within the spanned area.
+//!
+//! ```ignore
+//! use conrod_core::position::matrix::{Cell, Matrix, Track};
+//!
+//! let columns = [Track::Fixed(100.0), Track::Weight(1.0)];
+//! let rows = [Track::Auto, Track::Weight(1.0)];
+//! let cells = [
+//!     Cell::new(0, 0).span(2, 1).content_wh([0.0, 30.0]),
+//!     Cell::new(0, 1),
+//!     Cell::new(1, 1).align(Align::Middle, Align::Middle).content_wh([80.0, 30.0]),
+//! ];
+//! let mut elements = Matrix::new(&columns, &rows)
+//!     .gap(4.0, 4.0)
+//!     .kid_area_wh_of(ids.canvas)
+//!     .middle_of(ids.canvas)
+//!     .layout(&cells, ui);
+//! elements.next().unwrap().set(widget::Text::new("Title"), ids.title, ui);
+//! elements.next().unwrap().set(widget::Canvas::new(), ids.side, ui);
+//! elements.next().unwrap().set(widget::Button::new(), ids.button, ui);
+//! ```
+
+use {Ui, UiCell, Widget};
+use super::{Align, Depth, Dimension, Dimensions, Position, Positionable, Range, Rect, Scalar,
+            Sizeable};
+use std;
 use ui;
 use widget;
 
-pub type WidgetNum = usize;
-pub type ColNum = usize;
-pub type RowNum = usize;
-pub type Width = f64;
-pub type Height = f64;
-pub type PosX = f64;
-pub type PosY = f64;
-
 /// A type to simplify placement of various widgets in a matrix or grid layout.
 #[derive(Copy, Clone, Debug)]
-pub struct Matrix {
-    cols: usize,
-    rows: usize,
+pub struct Matrix<'a> {
+    columns: &'a [Track],
+    rows: &'a [Track],
     maybe_x_position: Option<Position>,
     maybe_y_position: Option<Position>,
     maybe_x_dimension: Option<Dimension>,
     maybe_y_dimension: Option<Dimension>,
+    maybe_min_x_dimension: Option<Dimension>,
+    maybe_max_x_dimension: Option<Dimension>,
+    maybe_min_y_dimension: Option<Dimension>,
+    maybe_max_y_dimension: Option<Dimension>,
+    depth: Depth,
+    gap_w: Scalar,
+    gap_h: Scalar,
     cell_pad_w: Scalar,
     cell_pad_h: Scalar,
 }
 
-impl Matrix {
+/// Describes how the length of a single column or row is determined.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Track {
+    /// The track has the given absolute length.
+    Fixed(Scalar),
+    /// The track shares the length remaining after all `Fixed` and `Auto` tracks in proportion to
+    /// its weight.
+    Weight(Scalar),
+    /// The track fits the largest `content_wh` of the cells that it contains.
+    Auto,
+}
+
+/// Describes the area occupied by a single widget within the **Matrix**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cell {
+    /// The index of the first column occupied by the cell.
+    pub col: usize,
+    /// The index of the first row occupied by the cell, starting from the top.
+    pub row: usize,
+    /// The number of columns spanned by the cell.
+    pub col_span: usize,
+    /// The number of rows spanned by the cell.
+    pub row_span: usize,
+    /// The dimensions of the cell's content, used to size `Auto` tracks and aligned widgets.
+    pub maybe_content_wh: Option<Dimensions>,
+    /// The alignment of the widget within the cell along the *x* and *y* axes.
+    ///
+    /// If `None`, the widget fills the cell.
+    pub maybe_align: Option<(Align, Align)>,
+}
+
+/// An iterator yielding an **Element** for each **Cell** given to **Matrix::layout**, in order.
+#[derive(Clone, Debug)]
+pub struct Elements {
+    elements: std::vec::IntoIter<Element>,
+}
+
+/// Data necessary for instantiating a widget within a single **Cell**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Element {
+    /// The index of the **Cell** for which the element was produced.
+    pub index: usize,
+    /// The absolute area that should be occupied by the widget.
+    pub rect: Rect,
+    /// The parent inferred from the **Matrix**'s position.
+    pub parent_id: widget::Id,
+    /// The depth given to the **Matrix**.
+    pub depth: Depth,
+}
+
 
-    /// Start building a new position **Matrix**.
-    pub fn new(cols: usize, rows: usize) -> Matrix {
+impl<'a> Matrix<'a> {
+
+    /// Start building a new position **Matrix** with the given column and row tracks.
+    ///
+    /// Columns flow from left to right and rows from top to bottom.
+    pub fn new(columns: &'a [Track], rows: &'a [Track]) -> Self {
         Matrix {
-            cols: cols,
+            columns: columns,
             rows: rows,
             maybe_x_position: None,
             maybe_y_position: None,
             maybe_x_dimension: None,
             maybe_y_dimension: None,
+            maybe_min_x_dimension: None,
+            maybe_max_x_dimension: None,
+            maybe_min_y_dimension: None,
+            maybe_max_y_dimension: None,
+            depth: 0.0,
+            gap_w: 0.0,
+            gap_h: 0.0,
             cell_pad_w: 0.0,
             cell_pad_h: 0.0,
         }
     }
 
+    /// Produce the matrix with the given gap between columns and rows respectively.
+    pub fn gap(mut self, w: Scalar, h: Scalar) -> Self {
+        self.gap_w = w;
+        self.gap_h = h;
+        self
+    }
+
     /// Produce the matrix with the given cell padding.
-    pub fn cell_padding(mut self, w: Scalar, h: Scalar) -> Matrix {
+    pub fn cell_padding(mut self, w: Scalar, h: Scalar) -> Self {
         self.cell_pad_w = w;
         self.cell_pad_h = h;
         self
     }
 
-    /// Call the given function for every element in the Matrix.
-    pub fn each_widget<C, F>(self, ui: &mut Ui<C>, mut f: F) where
-        C: CharacterCache,
-        F: FnMut(&mut Ui<C>, WidgetNum, ColNum, RowNum, Point, Dimensions),
-    {
-        use utils::map_range;
-
+    /// Lay out the given cells, producing an **Element** for each in order.
+    ///
+    /// Cells that begin outside of the matrix's tracks are given an empty `Rect` at the matrix's
+    /// centre. Spans are truncated to the last track.
+    pub fn layout(self, cells: &[Cell], ui: &Ui) -> Elements {
         let x_pos = self.get_x_position(ui);
         let y_pos = self.get_y_position(ui);
         let dim = self.get_wh(ui).unwrap_or([0.0, 0.0]);
+        let parent_id = ui::infer_parent_unchecked(ui, x_pos, y_pos);
+        let xy = ui.calc_xy(None, x_pos, y_pos, dim, true);
+        let rect = Rect::from_xy_dim(xy, dim);
 
-        // If we can infer some new current parent from the position, set that as the current
-        // parent within the given `Ui`.
-        let parent_idx = ui::infer_parent_unchecked(ui, x_pos, y_pos);
-        ui::set_current_parent_idx(ui, parent_idx);
+        let pad = [self.cell_pad_w, self.cell_pad_h];
+        let content = |cell: &Cell, axis: usize| {
+            cell.maybe_content_wh.map(|wh| wh[axis]).unwrap_or(0.0) + pad[axis] * 2.0
+        };
+        let col_spans: Vec<_> = cells.iter().map(|c| (c.col, c.col_span, content(c, 0))).collect();
+        let row_spans: Vec<_> = cells.iter().map(|c| (c.row, c.row_span, content(c, 1))).collect();
+        let cols = tracks(self.columns, &col_spans, rect.x, self.gap_w, false);
+        let rows = tracks(self.rows, &row_spans, rect.y, self.gap_h, true);
 
-        let xy = ui.calc_xy(None, x_pos, y_pos, dim, true);
-        let (half_w, half_h) = (dim[0] / 2.0, dim[1] / 2.0);
-        let widget_w = dim[0] / self.cols as f64;
-        let widget_h = dim[1] / self.rows as f64;
-        let x_min = -half_w + widget_w / 2.0;
-        let x_max = half_w + widget_w / 2.0;
-        let y_min = -half_h - widget_h / 2.0;
-        let y_max = half_h - widget_h / 2.0;
-        let mut widget_num = 0;
-        for col in 0..self.cols {
-            for row in 0..self.rows {
-                let x = xy[0] + map_range(col as f64, 0.0, self.cols as f64, x_min, x_max);
-                let y = xy[1] + map_range(row as f64, 0.0, self.rows as f64, y_max, y_min);
-                let w = widget_w - self.cell_pad_w * 2.0;
-                let h = widget_h - self.cell_pad_h * 2.0;
-                f(ui, widget_num, col, row, [x, y], [w, h]);
-                widget_num += 1;
+        let elements = cells.iter().enumerate().map(|(index, cell)| {
+            let x = span_range(&cols, cell.col, cell.col_span, rect.x);
+            let y = span_range(&rows, cell.row, cell.row_span, rect.y);
+            let x = x.pad(self.cell_pad_w);
+            let y = y.pad(self.cell_pad_h);
+            let rect = match (cell.maybe_align, cell.maybe_content_wh) {
+                (Some((align_x, align_y)), Some(wh)) => Rect {
+                    x: Range::from_pos_and_len(0.0, wh[0].min(x.len())).align_to(align_x, x),
+                    y: Range::from_pos_and_len(0.0, wh[1].min(y.len())).align_to(align_y, y),
+                },
+                _ => Rect { x: x, y: y },
+            };
+            Element {
+                index: index,
+                rect: rect,
+                parent_id: parent_id,
+                depth: self.depth,
             }
+        }).collect::<Vec<_>>();
+
+        Elements { elements: elements.into_iter() }
+    }
+
+}
+
+impl Cell {
+
+    /// A cell occupying the single track at the given column and row.
+    pub fn new(col: usize, row: usize) -> Self {
+        Cell {
+            col: col,
+            row: row,
+            col_span: 1,
+            row_span: 1,
+            maybe_content_wh: None,
+            maybe_align: None,
         }
     }
 
+    /// Span the given number of columns and rows.
+    pub fn span(mut self, cols: usize, rows: usize) -> Self {
+        self.col_span = cols;
+        self.row_span = rows;
+        self
+    }
+
+    /// Specify the dimensions of the cell's content.
+    ///
+    /// These are used to size any `Auto` tracks spanned by the cell and, if the cell is aligned,
+    /// the widget itself.
+    pub fn content_wh(mut self, wh: Dimensions) -> Self {
+        self.maybe_content_wh = Some(wh);
+        self
+    }
+
+    /// Align the widget within the cell along the *x* and *y* axes, rather than filling it.
+    ///
+    /// The widget is given the cell's `content_wh`, limited to the size of the cell.
+    pub fn align(mut self, x: Align, y: Align) -> Self {
+        self.maybe_align = Some((x, y));
+        self
+    }
+
 }
 
-impl<B> Positionable<B> for Matrix
-    where B: Backend,
+impl Iterator for Elements {
+    type Item = Element;
+    fn next(&mut self) -> Option<Element> {
+        self.elements.next()
+    }
+}
+
+impl Element {
+
+    /// Sets the given widget within the element's area.
+    ///
+    /// Sets the:
+    /// - position of the widget.
+    /// - dimensions of the widget.
+    /// - parent and depth of the widget.
+    /// - and finally sets the widget within the `Ui`.
+    pub fn set<W>(self, widget: W, widget_id: widget::Id, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        let Element { rect, parent_id, depth, .. } = self;
+        widget
+            .wh(rect.dim())
+            .xy(rect.xy())
+            .parent(parent_id)
+            .depth(depth)
+            .set(widget_id, ui)
+    }
+
+}
+
+
+/// The range occupied by each track along an axis.
+///
+/// Ranges flow from the start of the axis, or from the end if `is_reversed`.
+fn tracks(tracks: &[Track],
+          spans: &[(usize, usize, Scalar)],
+          range: Range,
+          gap: Scalar,
+          is_reversed: bool) -> Vec<Range>
 {
-    #[inline]
+    let n = tracks.len();
+    let mut lengths: Vec<Scalar> = tracks.iter().map(|track| match *track {
+        Track::Fixed(length) => length,
+        Track::Weight(_) | Track::Auto => 0.0,
+    }).collect();
+    let is_auto = |i: usize| tracks[i] == Track::Auto;
+
+    // Fit the `Auto` tracks to their single-track cells first, then grow them evenly to fit any
+    // spanning cells that are still too large.
+    for &(start, _, content) in spans.iter().filter(|s| s.1 == 1 && s.0 < n) {
+        if is_auto(start) {
+            lengths[start] = lengths[start].max(content);
+        }
+    }
+    for &(start, span, content) in spans.iter().filter(|s| s.1 > 1 && s.0 < n) {
+        let end = std::cmp::min(start + span, n);
+        let autos: Vec<usize> = (start..end).filter(|&i| is_auto(i)).collect();
+        if autos.is_empty() {
+            continue;
+        }
+        let spanned = lengths[start..end].iter().sum::<Scalar>() + gap * (end - start - 1) as Scalar;
+        if content > spanned {
+            let extra = (content - spanned) / autos.len() as Scalar;
+            for i in autos {
+                lengths[i] += extra;
+            }
+        }
+    }
+
+    // Share the remaining length between the weighted tracks.
+    let total_gap = gap * n.saturating_sub(1) as Scalar;
+    let remaining = (range.len() - total_gap - lengths.iter().sum::<Scalar>()).max(0.0);
+    let total_weight: Scalar = tracks.iter().map(|t| match *t {
+        Track::Weight(weight) => weight,
+        _ => 0.0,
+    }).sum();
+    if total_weight > 0.0 {
+        for (length, track) in lengths.iter_mut().zip(tracks) {
+            if let Track::Weight(weight) = *track {
+                *length = remaining * weight / total_weight;
+            }
+        }
+    }
+
+    let mut start = if is_reversed { range.end } else { range.start };
+    lengths.iter().map(|&length| {
+        let track = if is_reversed {
+            Range::new(start - length, start)
+        } else {
+            Range::new(start, start + length)
+        };
+        start = if is_reversed { track.start - gap } else { track.end + gap };
+        track
+    }).collect()
+}
+
+/// The range covered by the given span of tracks, including the gaps between them.
+fn span_range(tracks: &[Range], start: usize, span: usize, range: Range) -> Range {
+    if start >= tracks.len() {
+        return Range::from_pos_and_len(range.middle(), 0.0);
+    }
+    let end = std::cmp::min(start + span.max(1), tracks.len()) - 1;
+    tracks[start].max(tracks[end])
+}
+
+
+impl<'a> Positionable for Matrix<'a> {
     fn x_position(mut self, pos: Position) -> Self {
         self.maybe_x_position = Some(pos);
         self
     }
-    #[inline]
     fn y_position(mut self, pos: Position) -> Self {
         self.maybe_y_position = Some(pos);
         self
     }
-    #[inline]
-    fn get_x_position(&self, ui: &Ui<B>) -> Position {
+    fn get_x_position(&self, ui: &Ui) -> Position {
         self.maybe_x_position.unwrap_or(ui.theme.x_position)
     }
-    #[inline]
-    fn get_y_position(&self, ui: &Ui<B>) -> Position {
+    fn get_y_position(&self, ui: &Ui) -> Position {
         self.maybe_y_position.unwrap_or(ui.theme.y_position)
     }
-    #[inline]
-    fn depth(self, _: Depth) -> Self {
-        unimplemented!();
+    fn depth(mut self, depth: Depth) -> Self {
+        self.depth = depth;
+        self
     }
-    #[inline]
     fn get_depth(&self) -> Depth {
-        unimplemented!();
+        self.depth
     }
 }
 
-impl<B> Sizeable<B> for Matrix
-    where B: Backend,
-{
-    #[inline]
+impl<'a> Sizeable for Matrix<'a> {
     fn x_dimension(mut self, w: Dimension) -> Self {
         self.maybe_x_dimension = Some(w);
         self
     }
-    #[inline]
     fn y_dimension(mut self, h: Dimension) -> Self {
         self.maybe_y_dimension = Some(h);
         self
     }
-    #[inline]
-    fn get_x_dimension(&self, ui: &Ui<B>) -> Dimension {
+    fn get_x_dimension(&self, ui: &Ui) -> Dimension {
         const DEFAULT_WIDTH: Dimension = Dimension::Absolute(256.0);
         self.maybe_x_dimension.or_else(|| {
-            ui.theme.widget_style::<widget::matrix::Style>(widget::matrix::KIND)
-                .map(|default| default.common.maybe_x_dimension.unwrap_or(DEFAULT_WIDTH))
+            ui.theme.widget_style::<widget::matrix::Style>()
+                .and_then(|default| default.common.maybe_x_dimension)
         }).unwrap_or(DEFAULT_WIDTH)
     }
-    #[inline]
-    fn get_y_dimension(&self, ui: &Ui<B>) -> Dimension {
+    fn get_y_dimension(&self, ui: &Ui) -> Dimension {
         const DEFAULT_HEIGHT: Dimension = Dimension::Absolute(256.0);
         self.maybe_y_dimension.or_else(|| {
-            ui.theme.widget_style::<widget::matrix::Style>(widget::matrix::KIND)
-                .map(|default| default.common.maybe_y_dimension.unwrap_or(DEFAULT_HEIGHT))
+            ui.theme.widget_style::<widget::matrix::Style>()
+                .and_then(|default| default.common.maybe_y_dimension)
         }).unwrap_or(DEFAULT_HEIGHT)
     }
+    fn min_x_dimension(mut self, min: Dimension) -> Self {
+        self.maybe_min_x_dimension = Some(min);
+        self
+    }
+    fn max_x_dimension(mut self, max: Dimension) -> Self {
+        self.maybe_max_x_dimension = Some(max);
+        self
+    }
+    fn min_y_dimension(mut self, min: Dimension) -> Self {
+        self.maybe_min_y_dimension = Some(min);
+        self
+    }
+    fn max_y_dimension(mut self, max: Dimension) -> Self {
+        self.maybe_max_y_dimension = Some(max);
+        self
+    }
+    fn get_min_x_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        self.maybe_min_x_dimension
+    }
+    fn get_max_x_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        self.maybe_max_x_dimension
+    }
+    fn get_min_y_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        self.maybe_min_y_dimension
+    }
+    fn get_max_y_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        self.maybe_max_y_dimension
+    }
 }
@@ -5,10 +5,11 @@ use widget;
 
 pub use self::range::{Edge, Range};
 pub use self::rect::{Corner, Rect};
-//pub use self::matrix::Matrix;
+pub use self::matrix::Matrix;
 
 
-//pub mod matrix;
+pub mod matrix;
+pub mod constraint;
 pub mod range;
 pub mod rect;
 
@@ -137,6 +138,17 @@ pub enum Dimension {
     /// The `Option<Scalar>` is an optional padding argument which when `Some`, will subtract the
     /// scalar from both ends of the other widget's dimension.
     KidAreaOf(widget::Id, Option<Scalar>),
+    /// The dimension should fit the widget's content.
+    ///
+    /// Widgets that can measure their own content (the labels of `Button`, `Toggle` and
+    /// `DropDownList` and the text of `Text` and `TextBox`) are sized via `Widget::content_wh`.
+    /// All other widgets are fit around the bounding box of their children as soon as the next
+    /// widget that is not one of their descendants is set. Such widgets without children collapse
+    /// to the given padding.
+    ///
+    /// The `Option<Scalar>` is an optional padding argument which when `Some`, will add the scalar
+    /// to both ends of the content's dimension.
+    ToContent(Option<Scalar>),
 }
 
 /// Widgets that are positionable.
@@ -687,6 +699,64 @@ pub trait Sizeable: Sized {
 
     // Provided defaults.
 
+    /// Set the minimum length along the x axis.
+    ///
+    /// By default the minimum is ignored. Types that support minimum and maximum lengths should
+    /// override this along with the other `min_*_dimension`, `max_*_dimension` and `get_*`
+    /// counterparts.
+    fn min_x_dimension(self, _min: Dimension) -> Self {
+        self
+    }
+
+    /// Set the maximum length along the x axis.
+    ///
+    /// By default the maximum is ignored.
+    fn max_x_dimension(self, _max: Dimension) -> Self {
+        self
+    }
+
+    /// Set the minimum length along the y axis.
+    ///
+    /// By default the minimum is ignored.
+    fn min_y_dimension(self, _min: Dimension) -> Self {
+        self
+    }
+
+    /// Set the maximum length along the y axis.
+    ///
+    /// By default the maximum is ignored.
+    fn max_y_dimension(self, _max: Dimension) -> Self {
+        self
+    }
+
+    /// The widget's minimum length along the x axis as a Dimension, if any.
+    ///
+    /// By default this returns `None`.
+    fn get_min_x_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        None
+    }
+
+    /// The widget's maximum length along the x axis as a Dimension, if any.
+    ///
+    /// By default this returns `None`.
+    fn get_max_x_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        None
+    }
+
+    /// The widget's minimum length along the y axis as a Dimension, if any.
+    ///
+    /// By default this returns `None`.
+    fn get_min_y_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        None
+    }
+
+    /// The widget's maximum length along the y axis as a Dimension, if any.
+    ///
+    /// By default this returns `None`.
+    fn get_max_y_dimension(&self, _ui: &Ui) -> Option<Dimension> {
+        None
+    }
+
     /// Set the absolute width for the widget.
     fn w(self, w: Scalar) -> Self {
         self.x_dimension(Dimension::Absolute(w))
@@ -773,26 +843,127 @@ pub trait Sizeable: Sized {
         self.padded_kid_area_w_of(idx, pad).padded_kid_area_h_of(idx, pad)
     }
 
+    /// Set the width to fit the widget's content.
+    fn w_to_content(self) -> Self {
+        self.x_dimension(Dimension::ToContent(None))
+    }
+
+    /// Set the width to fit the widget's content padded at both ends by the given scalar.
+    fn padded_w_to_content(self, pad: Scalar) -> Self {
+        self.x_dimension(Dimension::ToContent(Some(pad)))
+    }
+
+    /// Set the height to fit the widget's content.
+    fn h_to_content(self) -> Self {
+        self.y_dimension(Dimension::ToContent(None))
+    }
+
+    /// Set the height to fit the widget's content padded at both ends by the given scalar.
+    fn padded_h_to_content(self, pad: Scalar) -> Self {
+        self.y_dimension(Dimension::ToContent(Some(pad)))
+    }
+
+    /// Set the dimensions to fit the widget's content.
+    fn wh_to_content(self) -> Self {
+        self.w_to_content().h_to_content()
+    }
+
+    /// Set the dimensions to fit the widget's content with all four edges padded by the given
+    /// scalar.
+    fn padded_wh_to_content(self, pad: Scalar) -> Self {
+        self.padded_w_to_content(pad).padded_h_to_content(pad)
+    }
+
+    /// Set the minimum width for the widget.
+    fn min_w(self, w: Scalar) -> Self {
+        self.min_x_dimension(Dimension::Absolute(w))
+    }
+
+    /// Set the maximum width for the widget.
+    fn max_w(self, w: Scalar) -> Self {
+        self.max_x_dimension(Dimension::Absolute(w))
+    }
+
+    /// Set the minimum height for the widget.
+    fn min_h(self, h: Scalar) -> Self {
+        self.min_y_dimension(Dimension::Absolute(h))
+    }
+
+    /// Set the maximum height for the widget.
+    fn max_h(self, h: Scalar) -> Self {
+        self.max_y_dimension(Dimension::Absolute(h))
+    }
+
+    /// Set the minimum width as the width of the widget at the given index.
+    fn min_w_of(self, idx: widget::Id) -> Self {
+        self.min_x_dimension(Dimension::Of(idx, None))
+    }
+
+    /// Set the maximum width as the width of the widget at the given index.
+    fn max_w_of(self, idx: widget::Id) -> Self {
+        self.max_x_dimension(Dimension::Of(idx, None))
+    }
+
+    /// Set the minimum height as the height of the widget at the given index.
+    fn min_h_of(self, idx: widget::Id) -> Self {
+        self.min_y_dimension(Dimension::Of(idx, None))
+    }
+
+    /// Set the maximum height as the height of the widget at the given index.
+    fn max_h_of(self, idx: widget::Id) -> Self {
+        self.max_y_dimension(Dimension::Of(idx, None))
+    }
+
     /// Get the absolute width of the widget as a Scalar value.
+    ///
+    /// The width is clamped to the widget's minimum and maximum widths, if any.
+    ///
+    /// Returns `None` for `Dimension::ToContent`, which can only be resolved as the widget is set.
     fn get_w(&self, ui: &Ui) -> Option<Scalar> {
-        match self.get_x_dimension(ui) {
-            Dimension::Absolute(width) => Some(width),
-            Dimension::Of(idx, None) => ui.w_of(idx),
-            Dimension::Of(idx, Some(pad)) => ui.w_of(idx).map(|w| w - pad * 2.0),
-            Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(|r| r.w()),
-            Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| r.w() - pad * 2.0),
-        }
+        length(self.get_x_dimension(ui), Axis::X, ui).map(|w| self.clamp_w(w, ui))
     }
 
     /// Get the height of the widget.
+    ///
+    /// The height is clamped to the widget's minimum and maximum heights, if any.
+    ///
+    /// Returns `None` for `Dimension::ToContent`, which can only be resolved as the widget is set.
     fn get_h(&self, ui: &Ui) -> Option<Scalar> {
-        match self.get_y_dimension(ui) {
-            Dimension::Absolute(height) => Some(height),
-            Dimension::Of(idx, None) => ui.h_of(idx),
-            Dimension::Of(idx, Some(pad)) => ui.h_of(idx).map(|w| w - pad * 2.0),
-            Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(|r| r.h()),
-            Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| r.h() - pad * 2.0),
-        }
+        length(self.get_y_dimension(ui), Axis::Y, ui).map(|h| self.clamp_h(h, ui))
+    }
+
+    /// The absolute minimum width of the widget, if any.
+    fn get_min_w(&self, ui: &Ui) -> Option<Scalar> {
+        self.get_min_x_dimension(ui).and_then(|min| length(min, Axis::X, ui))
+    }
+
+    /// The absolute maximum width of the widget, if any.
+    fn get_max_w(&self, ui: &Ui) -> Option<Scalar> {
+        self.get_max_x_dimension(ui).and_then(|max| length(max, Axis::X, ui))
+    }
+
+    /// The absolute minimum height of the widget, if any.
+    fn get_min_h(&self, ui: &Ui) -> Option<Scalar> {
+        self.get_min_y_dimension(ui).and_then(|min| length(min, Axis::Y, ui))
+    }
+
+    /// The absolute maximum height of the widget, if any.
+    fn get_max_h(&self, ui: &Ui) -> Option<Scalar> {
+        self.get_max_y_dimension(ui).and_then(|max| length(max, Axis::Y, ui))
+    }
+
+    /// Clamp the given width to the widget's minimum and maximum widths.
+    ///
+    /// If the minimum is greater than the maximum, the minimum takes precedence.
+    fn clamp_w(&self, w: Scalar, ui: &Ui) -> Scalar {
+        clamp_length(w, self.get_min_w(ui), self.get_max_w(ui))
+    }
+
+    /// Clamp the given height to the widget's minimum and maximum heights.
+    ///
+    /// If the minimum is greater than the maximum, the minimum takes precedence.
+    fn clamp_h(&self, h: Scalar, ui: &Ui) -> Scalar {
+        clamp_length(h, self.get_min_h(ui), self.get_max_h(ui))
     }
 
     /// The dimensions for the widget.
@@ -802,6 +973,30 @@ pub trait Sizeable: Sized {
 
 }
 
+/// Resolve the absolute length of the given `Dimension` along the given axis.
+fn length(dimension: Dimension, axis: Axis, ui: &Ui) -> Option<Scalar> {
+    let len = |rect: Rect| match axis {
+        Axis::X => rect.w(),
+        Axis::Y => rect.h(),
+    };
+    match dimension {
+        Dimension::Absolute(length) => Some(length),
+        Dimension::Of(idx, None) => ui.rect_of(idx).map(len),
+        Dimension::Of(idx, Some(pad)) => ui.rect_of(idx).map(|r| len(r) - pad * 2.0),
+        Dimension::KidAreaOf(idx, None) => ui.kid_area_of(idx).map(len),
+        Dimension::KidAreaOf(idx, Some(pad)) => ui.kid_area_of(idx).map(|r| len(r) - pad * 2.0),
+        Dimension::ToContent(_) => None,
+    }
+}
+
+/// Clamp the length to the given bounds, where the minimum takes precedence over the maximum.
+///
+/// This function is only for internal use by `Sizeable` and the `Ui`.
+pub fn clamp_length(length: Scalar, min: Option<Scalar>, max: Option<Scalar>) -> Scalar {
+    let length = max.map(|max| length.min(max)).unwrap_or(length);
+    min.map(|min| length.max(min)).unwrap_or(length)
+}
+
 /// The distance between the inner edge of a border and the outer edge of the inner content.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Padding {
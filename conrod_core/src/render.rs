@@ -138,6 +138,8 @@ pub enum PrimitiveKind<'a> {
         color: Color,
         /// All glyphs within the `Text` laid out in their correct positions in order from top-left
         /// to bottom right.
+        ///
+        /// Color glyphs may be cached within a `text::bitmap::Atlas` via `text.bitmap_source()`.
         text: Text<'a>,
         /// The unique identifier for the font, useful for the `glyph_cache.rect_for(id, glyph)`
         /// method when using the `conrod::text::GlyphCache` (rusttype's GPU `Cache`).
@@ -168,6 +170,7 @@ pub struct Text<'a> {
     text: &'a str,
     line_infos: &'a [text::line::Info],
     font: &'a text::Font,
+    bitmap_source: Option<&'a std::sync::Arc<dyn text::bitmap::Source>>,
     font_size: FontSize,
     rect: Rect,
     justify: text::Justify,
@@ -214,6 +217,7 @@ struct OwnedText {
     line_infos_range: std::ops::Range<usize>,
     window_dim: Dimensions,
     font: text::Font,
+    bitmap_source: Option<std::sync::Arc<dyn text::bitmap::Source>>,
     font_size: FontSize,
     rect: Rect,
     justify: text::Justify,
@@ -234,6 +238,14 @@ pub struct WalkOwnedPrimitives<'a> {
 
 impl<'a> Text<'a> {
 
+    /// The source of color bitmaps for the `Text`'s font, if there is one.
+    ///
+    /// Glyphs for which the source produces a bitmap should be cached within a
+    /// `text::bitmap::Atlas` and drawn without tinting, rather than via the `GlyphCache`.
+    pub fn bitmap_source(&self) -> Option<&'a dyn text::bitmap::Source> {
+        self.bitmap_source.map(|source| &**source)
+    }
+
     /// Produces a list of `PositionedGlyph`s which may be used to cache and render the text.
     ///
     /// `dpi_factor`, aka "dots per inch factor" is a multiplier representing the density of
@@ -257,6 +269,7 @@ impl<'a> Text<'a> {
             justify,
             y_align,
             line_spacing,
+            ..
         } = self;
 
         // Convert conrod coordinates to pixel coordinates.
@@ -592,6 +605,7 @@ impl<'a> Primitives<'a> {
                         text: &state.string,
                         line_infos: &state.line_infos,
                         font: font,
+                        bitmap_source: fonts.bitmap_source(font_id),
                         font_size: font_size,
                         rect: rect,
                         justify: justify,
@@ -692,6 +706,7 @@ impl<'a> Primitives<'a> {
                         text,
                         line_infos,
                         font,
+                        bitmap_source,
                         font_size,
                         rect,
                         justify,
@@ -719,6 +734,7 @@ impl<'a> Primitives<'a> {
                         line_infos_range: start_line_info_idx..end_line_info_idx,
                         window_dim: window_dim,
                         font: font.clone(),
+                        bitmap_source: bitmap_source.cloned(),
                         font_size: font_size,
                         rect: rect,
                         justify: justify,
@@ -825,6 +841,7 @@ impl<'a> WalkOwnedPrimitives<'a> {
                         ref str_byte_range,
                         ref line_infos_range,
                         ref font,
+                        ref bitmap_source,
                         window_dim,
                         font_size,
                         rect,
@@ -842,6 +859,7 @@ impl<'a> WalkOwnedPrimitives<'a> {
                         text: text_str,
                         line_infos: line_infos,
                         font: font,
+                        bitmap_source: bitmap_source.as_ref(),
                         font_size: font_size,
                         rect: rect,
                         justify: justify,
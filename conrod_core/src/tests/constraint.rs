@@ -0,0 +1,103 @@
+use position::constraint::{left, right, width, Error, Expression, Solver, Variable};
+use position::constraint::{MEDIUM, STRONG, WEAK};
+use widget;
+
+fn ids() -> (widget::Id, widget::Id) {
+    (widget::Id::new(0), widget::Id::new(1))
+}
+
+fn assert_near(a: f64, b: f64) {
+    assert!((a - b).abs() < 1.0e-6, "{} is not near {}", a, b);
+}
+
+#[test]
+fn solver_should_satisfy_inequalities() {
+    let (a, b) = ids();
+    let mut solver = Solver::new();
+    solver.add_constraint(&width(a).ge(100.0)).unwrap();
+    solver.add_constraint(&width(a).eq(50.0).strength(WEAK)).unwrap();
+    solver.add_constraint(&width(b).le(300.0)).unwrap();
+    solver.add_constraint(&width(b).eq(400.0).strength(WEAK)).unwrap();
+    assert_near(solver.value(Variable::W(a)).unwrap(), 100.0);
+    assert_near(solver.value(Variable::W(b)).unwrap(), 300.0);
+
+    // Inequalities that are already satisfied leave the preferred value alone.
+    solver.add_constraint(&width(a).le(1000.0)).unwrap();
+    assert_near(solver.value(Variable::W(a)).unwrap(), 100.0);
+}
+
+#[test]
+fn solver_should_favour_stronger_constraints() {
+    let (a, b) = ids();
+    let mut solver = Solver::new();
+    solver.add_constraint(&width(a).eq(10.0).strength(WEAK)).unwrap();
+    solver.add_constraint(&width(a).eq(20.0).strength(STRONG)).unwrap();
+    solver.add_constraint(&width(a).eq(30.0).strength(MEDIUM)).unwrap();
+    assert_near(solver.value(Variable::W(a)).unwrap(), 20.0);
+
+    // The order in which constraints are added makes no difference.
+    solver.add_constraint(&width(b).eq(30.0).strength(MEDIUM)).unwrap();
+    solver.add_constraint(&width(b).eq(10.0).strength(WEAK)).unwrap();
+    assert_near(solver.value(Variable::W(b)).unwrap(), 30.0);
+}
+
+#[test]
+fn solver_should_reject_conflicting_required_constraints() {
+    let (a, b) = ids();
+    let mut solver = Solver::new();
+    solver.add_constraint(&width(a).eq(10.0)).unwrap();
+    assert_eq!(solver.add_constraint(&width(a).eq(20.0)), Err(Error::Unsatisfiable));
+    assert_near(solver.value(Variable::W(a)).unwrap(), 10.0);
+
+    solver.add_constraint(&width(b).ge(100.0)).unwrap();
+    assert_eq!(solver.add_constraint(&width(b).le(50.0)), Err(Error::Unsatisfiable));
+    assert!(solver.value(Variable::W(b)).unwrap() >= 100.0);
+}
+
+#[test]
+fn solver_should_update_the_system_with_suggested_values() {
+    let (a, b) = ids();
+    let mut solver = Solver::default();
+    solver.add_constraint(&width(a).eq(100.0)).unwrap();
+    solver.add_constraint(&width(b).eq(50.0)).unwrap();
+    solver.add_constraint(&left(b).eq(right(a) + 10.0)).unwrap();
+    solver.add_constraint(&right(b).le(300.0)).unwrap();
+    solver.add_edit_variable(Variable::X(a), STRONG).unwrap();
+
+    solver.suggest_value(Variable::X(a), 0.0).unwrap();
+    assert_near(solver.value(Variable::X(a)).unwrap(), 0.0);
+    assert_near(solver.value(Variable::X(b)).unwrap(), 85.0);
+
+    solver.suggest_value(Variable::X(a), 100.0).unwrap();
+    assert_near(solver.value(Variable::X(b)).unwrap(), 185.0);
+
+    // Suggestions give way to required constraints.
+    solver.suggest_value(Variable::X(a), 250.0).unwrap();
+    assert_near(solver.value(Variable::X(a)).unwrap(), 190.0);
+    assert_near(solver.value(Variable::X(b)).unwrap(), 275.0);
+
+    solver.suggest_value(Variable::X(a), -20.0).unwrap();
+    assert_near(solver.value(Variable::X(b)).unwrap(), 65.0);
+
+    assert_eq!(solver.add_edit_variable(Variable::X(a), WEAK), Err(Error::DuplicateEditVariable));
+    assert_eq!(solver.suggest_value(Variable::X(b), 0.0), Err(Error::UnknownEditVariable));
+}
+
+#[test]
+fn solver_should_find_some_solution_for_underdetermined_systems() {
+    let (a, b) = ids();
+    let mut solver = Solver::new();
+    let sum = Expression::from(Variable::X(a)) + Variable::X(b);
+    solver.add_constraint(&sum.eq(100.0)).unwrap();
+    let x_a = solver.value(Variable::X(a)).unwrap();
+    let x_b = solver.value(Variable::X(b)).unwrap();
+    assert_near(x_a + x_b, 100.0);
+
+    // Variables that no constraint refers to have no value.
+    assert_eq!(solver.value(Variable::Y(a)), None);
+
+    // A preference for one of the variables determines the other.
+    solver.add_constraint(&Expression::from(Variable::X(a)).eq(30.0).strength(WEAK)).unwrap();
+    assert_near(solver.value(Variable::X(a)).unwrap(), 30.0);
+    assert_near(solver.value(Variable::X(b)).unwrap(), 70.0);
+}
@@ -1,3 +1,5 @@
+mod constraint;
 mod global_input;
 mod widget_input;
+mod text;
 mod ui;
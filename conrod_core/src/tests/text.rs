@@ -0,0 +1,202 @@
+use text::{self, rt, sdf};
+
+
+fn noto_sans() -> text::Font {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    text::font::from_file(path).unwrap()
+}
+
+
+#[test]
+fn distance_field_should_rise_from_outside_to_inside_through_the_edge() {
+    // A vertical edge lying on the half covered pixel of each row.
+    let row = [0, 0, 0, 128, 255, 255, 255, 255, 255, 255];
+    let coverage: Vec<u8> = (0..7).flat_map(|_| row.iter().cloned()).collect();
+    let field = sdf::distance_field(&coverage, row.len(), 7, 2.0);
+
+    // Check the middle row, far enough from the top and bottom of the bitmap.
+    let field = &field[row.len() * 3..row.len() * 4];
+
+    // The edge lies at the centre of the range.
+    assert_eq!(field[3], 128);
+    // Pixels beyond the spread are clamped.
+    assert_eq!(field[0], 0);
+    assert_eq!(field[6], 255);
+    // The field rises steadily from outside to inside.
+    assert_eq!(&field[..7], &[0, 32, 96, 128, 223, 255, 255]);
+}
+
+#[test]
+fn distance_field_should_fall_off_on_all_sides_of_a_glyph() {
+    // A 2x2 glyph in the centre of a 6x6 bitmap.
+    let mut coverage = [0; 36];
+    for &i in &[14, 15, 20, 21] {
+        coverage[i] = 255;
+    }
+    let field = sdf::distance_field(&coverage, 6, 6, 1.0);
+    for y in 0..6 {
+        for x in 0..6 {
+            let value = field[y * 6 + x];
+            if coverage[y * 6 + x] == 255 {
+                assert!(value > 128, "expected ({}, {}) to be inside: {}", x, y, value);
+            } else {
+                assert!(value < 128, "expected ({}, {}) to be outside: {}", x, y, value);
+            }
+        }
+    }
+    // The field is symmetrical about the glyph.
+    assert_eq!(field[0], field[35]);
+    assert_eq!(field[14], field[21]);
+}
+
+#[test]
+fn padded_distance_field_should_extend_the_spread_beyond_the_glyph() {
+    // A 4x4 glyph within the single texel of padding added to its slot by the cache.
+    let mut coverage = [0; 36];
+    for y in 1..5 {
+        for x in 1..5 {
+            coverage[y * 6 + x] = 255;
+        }
+    }
+
+    // The slot now covers the glyph along with the full padding of 4 pixels on either side, so
+    // its border samples the field 3 pixels outside of the glyph rather than 1.
+    let field = sdf::padded_distance_field(&coverage, 6, 6, 4.0);
+    let row = &field[2 * 6..3 * 6];
+    assert!(row[0] > 0 && row[0] < 128, "expected the border to be within the spread: {:?}", row);
+    assert!(row[0] < row[1] && row[1] < 128 && row[2] > 128);
+    assert_eq!(row[0], row[5]);
+
+    // Spreads of a single pixel fit within the cache's own padding.
+    assert_eq!(sdf::padded_distance_field(&coverage, 6, 6, 1.0),
+               sdf::distance_field(&coverage, 6, 6, 1.0));
+
+    // The texture coordinates and pixel-space rectangle grow to cover the whole slot.
+    let uv_rect = rt::Rect { min: rt::point(0.25, 0.25), max: rt::point(0.5, 0.5) };
+    let normalized_rect = rt::Rect { min: rt::point(0, -4), max: rt::point(4, 0) };
+    let (uv_rect, normalized_rect) = sdf::padded_rects(uv_rect, normalized_rect, (16, 16), 4.0);
+    assert_eq!(uv_rect.min, rt::point(0.1875, 0.1875));
+    assert_eq!(uv_rect.max, rt::point(0.5625, 0.5625));
+    assert_eq!(normalized_rect.min, rt::point(-4, -8));
+    assert_eq!(normalized_rect.max, rt::point(8, 4));
+}
+
+#[test]
+fn sdf_screen_rect_should_scale_the_normalized_rect_to_the_glyph() {
+    let font = noto_sans();
+    let glyph = font.glyph('a')
+        .scaled(text::Scale::uniform(24.0))
+        .positioned(rt::point(10.0, 20.0));
+
+    // The glyph is half the size of the reference scale.
+    let normalized_rect = rt::Rect { min: rt::point(-2, -30), max: rt::point(20, 2) };
+    let screen_rect = sdf::screen_rect(&glyph, 48.0, normalized_rect);
+    assert_eq!(screen_rect.min, rt::point(9.0, 5.0));
+    assert_eq!(screen_rect.max, rt::point(20.0, 21.0));
+}
+
+#[test]
+fn bitmap_atlas_should_pack_reuse_and_evict_bitmap_glyphs() {
+    use text::bitmap::{Atlas, Bitmap};
+
+    let font = noto_sans();
+    let glyph = |c: char| font.glyph(c).scaled(text::Scale::uniform(16.0)).positioned(rt::point(0.0, 0.0));
+    let color_glyphs: Vec<_> = "abcde".chars().map(|c| font.glyph(c).id()).collect();
+    let source = |id, _ppem| if color_glyphs.contains(&id) {
+        Some(Bitmap { width: 16, height: 16, rgba: vec![255; 16 * 16 * 4], ppem: 16.0, offset: [0.0, -16.0] })
+    } else {
+        None
+    };
+    let rect = |x, y| rt::Rect { min: rt::point(x, y), max: rt::point(x + 16, y + 16) };
+
+    let mut atlas = Atlas::new(32, 32);
+    let cache = |atlas: &mut Atlas, chars: &str| {
+        let mut uploaded = Vec::new();
+        for c in chars.chars() {
+            assert!(atlas.queue_glyph(0, &glyph(c), &source));
+        }
+        atlas.cache_queued(|rect, data| {
+            assert_eq!(data.len(), 16 * 16 * 4);
+            uploaded.push(rect);
+        }).unwrap();
+        uploaded
+    };
+
+    // Glyphs without a bitmap are left to the `GlyphCache`.
+    assert!(!atlas.queue_glyph(0, &glyph('z'), &source));
+
+    // Glyphs are packed along shelves.
+    assert_eq!(cache(&mut atlas, "ab"), vec![rect(0, 0), rect(16, 0)]);
+    let (uv_rect, _) = atlas.rect_for(0, &glyph('b')).unwrap();
+    assert_eq!(uv_rect, rt::Rect { min: rt::point(0.5, 0.0), max: rt::point(1.0, 0.5) });
+
+    // Glyphs that are already packed are not uploaded again.
+    assert_eq!(cache(&mut atlas, "ba"), vec![]);
+    assert_eq!(cache(&mut atlas, "ac"), vec![rect(0, 16)]);
+
+    // When there is no room left, glyphs that are not queued are evicted.
+    assert_eq!(cache(&mut atlas, "cde"), vec![rect(0, 0), rect(16, 0), rect(0, 16)]);
+    assert!(atlas.rect_for(0, &glyph('a')).is_none());
+    assert!(atlas.rect_for(0, &glyph('e')).is_some());
+}
+
+#[test]
+fn bitmap_glyphs_should_be_sized_by_their_pixels_per_em() {
+    use text::bitmap::{self, Atlas, Bitmap};
+
+    let font = noto_sans();
+    let id = font.glyph('a').id();
+    let source = |_, _| Some(Bitmap { width: 20, height: 20, rgba: vec![255; 20 * 20 * 4], ppem: 20.0, offset: [1.0, -18.0] });
+
+    // The scale of a glyph spans the font's ascent to descent, which is larger than its em square.
+    let v_metrics = font.v_metrics_unscaled();
+    let em_to_pixel_height = (v_metrics.ascent - v_metrics.descent) / font.units_per_em() as f32;
+    let glyph = font.glyph(id)
+        .scaled(text::Scale::uniform(40.0 * em_to_pixel_height))
+        .positioned(rt::point(100.0, 50.0));
+    assert!((bitmap::ppem(&glyph) - 40.0).abs() < 1e-3);
+
+    // A bitmap designed at 20 ppem is drawn at twice its size for a 40 ppem glyph.
+    let mut atlas = Atlas::new(64, 64);
+    assert!(atlas.queue_glyph(0, &glyph, &source));
+    atlas.cache_queued(|_, _| ()).unwrap();
+    let (_, screen_rect) = atlas.rect_for(0, &glyph).unwrap();
+    let approx_eq = |a: rt::Point<f32>, b: rt::Point<f32>| (a.x - b.x).abs() < 1e-3 && (a.y - b.y).abs() < 1e-3;
+    assert!(approx_eq(screen_rect.min, rt::point(102.0, 14.0)), "{:?}", screen_rect);
+    assert!(approx_eq(screen_rect.max, rt::point(142.0, 54.0)), "{:?}", screen_rect);
+}
+
+#[test]
+fn bitmap_atlas_should_cache_each_strike_of_a_glyph_separately() {
+    use text::bitmap::{self, Atlas, Bitmap};
+
+    let font = noto_sans();
+    let v_metrics = font.v_metrics_unscaled();
+    let em_to_pixel_height = (v_metrics.ascent - v_metrics.descent) / font.units_per_em() as f32;
+    let glyph = |ppem: f32| font.glyph('a')
+        .scaled(text::Scale::uniform(ppem * em_to_pixel_height))
+        .positioned(rt::point(0.0, 0.0));
+
+    // The font has a 16 ppem strike for small sizes and a 32 ppem strike for the rest.
+    let source = |_, ppem: f32| {
+        let strike = if ppem < 24.0 { 16 } else { 32 };
+        let rgba = vec![255; (strike * strike * 4) as usize];
+        Some(Bitmap { width: strike, height: strike, rgba: rgba, ppem: strike as f32, offset: [0.0, 0.0] })
+    };
+    let mut atlas = Atlas::new(64, 64);
+    let mut cache = |ppem: f32| {
+        let glyph = glyph(ppem);
+        assert!((bitmap::ppem(&glyph) - ppem).abs() < 1e-3);
+        assert!(atlas.queue_glyph(0, &glyph, &source));
+        let mut uploaded = Vec::new();
+        atlas.cache_queued(|rect, _| uploaded.push(rect)).unwrap();
+        let (uv_rect, _) = atlas.rect_for(0, &glyph).unwrap();
+        (uploaded.len(), uv_rect.max.x - uv_rect.min.x)
+    };
+
+    // Each strike is uploaded once and drawn from its own region of the atlas.
+    assert_eq!(cache(16.0), (1, 0.25));
+    assert_eq!(cache(40.0), (1, 0.5));
+    assert_eq!(cache(20.0), (0, 0.25));
+    assert_eq!(cache(32.0), (0, 0.5));
+}
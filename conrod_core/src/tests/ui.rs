@@ -1,4 +1,5 @@
 use {
+    Borderable,
     Color,
     Labelable,
     Positionable,
@@ -131,3 +132,1571 @@ fn high_level_scroll_event_should_be_created_from_a_raw_mouse_scroll() {
         assert_eq!(expected_scroll, scroll);
     }
 }
+
+#[test]
+fn selectable_text_should_be_selected_by_dragging_and_cleared_on_uncapture() {
+    use render::PrimitiveKind;
+    use text;
+
+    let ui = &mut windowless_ui();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    let font_id = ui.fonts.insert_from_file(font_path).unwrap();
+    let (text, other) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    fn set_widgets(ui: &mut Ui, text: widget::Id, other: widget::Id) {
+        let ui = &mut ui.set_widgets();
+        widget::Text::new("Hello world").font_size(20).selectable().x_y(0.0, 0.0).set(text, ui);
+        widget::Rectangle::fill([50.0, 50.0]).x_y(0.0, -200.0).set(other, ui);
+    }
+
+    set_widgets(ui, text, other);
+    assert_eq!(widget::text::selected_text(text, ui), None);
+
+    // Drag from the start of the text to the end of the first word.
+    let rect = ui.rect_of(text).unwrap();
+    let hello_w = text::line::width("Hello", ui.fonts.get(font_id).unwrap(), 20);
+    move_mouse_to_abs_coordinates(rect.left() + 0.5, rect.y(), ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(rect.left() + hello_w, rect.y(), ui);
+    release_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui, text, other);
+    assert_eq!(widget::text::selected_text(text, ui), Some("Hello"));
+
+    // The selection is highlighted beneath the glyphs rather than over them.
+    let kinds: Vec<_> = {
+        let mut primitives = ui.draw();
+        let mut kinds = Vec::new();
+        while let Some(primitive) = primitives.next() {
+            match primitive.kind {
+                PrimitiveKind::Rectangle { .. } if primitive.rect.left() == rect.left() =>
+                    kinds.push("highlight"),
+                PrimitiveKind::Text { .. } => kinds.push("text"),
+                _ => (),
+            }
+        }
+        kinds
+    };
+    assert_eq!(kinds, vec!["highlight", "text"]);
+
+    // Ctrl+A selects everything.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LCtrl)));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::A)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::A)));
+    ui.handle_event(Input::Release(Button::Keyboard(Key::LCtrl)));
+    set_widgets(ui, text, other);
+    assert_eq!(widget::text::selected_text(text, ui), Some("Hello world"));
+
+    // Clicking another widget takes the keyboard capture and clears the selection.
+    move_mouse_to_abs_coordinates(0.0, -200.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui, text, other);
+    assert_eq!(widget::text::selected_text(text, ui), None);
+}
+
+#[test]
+fn text_edit_search_should_select_and_replace_case_sensitive_matches() {
+    use text::cursor::Index;
+    use widget::text_edit::Cursor;
+
+    let ui = &mut windowless_ui();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+    let text_edit = ui.widget_id_generator().next();
+    let mut text = "cat Cat cat".to_string();
+
+    // Sets the `TextEdit` with the given search action, returning the resulting cursor.
+    let mut search = |ui: &mut Ui, action: &dyn Fn(widget::TextEdit) -> widget::TextEdit| {
+        {
+            let ui = &mut ui.set_widgets();
+            let edit = widget::TextEdit::new(&text).w_h(400.0, 50.0).search("cat");
+            if let Some(new_text) = action(edit).set(text_edit, ui) {
+                text = new_text;
+            }
+        }
+        (text.clone(), widget::text_edit::cursor(text_edit, ui).unwrap())
+    };
+    let selection = |start, end| Cursor::Selection {
+        start: Index { line: 0, char: start },
+        end: Index { line: 0, char: end },
+    };
+
+    // Matching is case sensitive, so "Cat" is skipped, and wraps around the ends of the text.
+    search(ui, &|edit| edit);
+    assert_eq!(search(ui, &|edit| edit.search_next()).1, selection(0, 3));
+    assert_eq!(search(ui, &|edit| edit.search_next()).1, selection(8, 11));
+    assert_eq!(search(ui, &|edit| edit.search_next()).1, selection(0, 3));
+    assert_eq!(search(ui, &|edit| edit.search_previous()).1, selection(8, 11));
+    assert_eq!(search(ui, &|edit| edit.search_previous()).1, selection(0, 3));
+
+    // Replacing selects the following match rather than one within the replacement itself.
+    assert_eq!(search(ui, &|edit| edit.replace("cats")), ("cats Cat cat".to_string(), selection(9, 12)));
+    assert_eq!(search(ui, &|edit| edit.replace("cats")), ("cats Cat cats".to_string(), selection(0, 3)));
+
+    // Replacing all matches does not recurse into the replacements.
+    let (text, cursor) = search(ui, &|edit| edit.replace_all("a cat"));
+    assert_eq!(text, "a cats Cat a cats");
+    assert_eq!(cursor, Cursor::Idx(Index { line: 0, char: 0 }));
+}
+
+#[test]
+fn tab_should_move_keyboard_capture_along_the_focus_chain() {
+    let ui = &mut windowless_ui();
+
+    let (a, b, c) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next())
+    };
+
+    {
+        let ui = &mut ui.set_widgets();
+        widget::TextEdit::new("a").set(a, ui);
+        widget::TextEdit::new("b").set(b, ui);
+        widget::TextEdit::new("c").tab_index(0).set(c, ui);
+    }
+
+    // Widgets with a tab index come before those ordered by instantiation.
+    assert_eq!(ui.focus_chain(), &[c, a, b]);
+
+    let keyboard = input::Source::Keyboard;
+    let tab = Input::Press(Button::Keyboard(Key::Tab));
+
+    ui.handle_event(tab.clone());
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(c));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::WidgetCapturesInputSource(c, keyboard)));
+
+    // The `Tab` press that moved focus, along with its text, is not delivered to any widget.
+    ui.handle_event(Input::Text("\t".to_string()));
+    let is_delivered = |event: &event::Event| match *event {
+        event::Event::Ui(event::Ui::Press(Some(_), _)) |
+        event::Event::Ui(event::Ui::Text(_, _)) => true,
+        _ => false,
+    };
+    assert!(!ui.global_input().events().any(is_delivered));
+    {
+        let ui = &mut ui.set_widgets();
+        widget::TextEdit::new("a").set(a, ui);
+        widget::TextEdit::new("b").set(b, ui);
+        assert_eq!(widget::TextEdit::new("c").tab_index(0).set(c, ui), None);
+    }
+
+    // Other text is delivered as usual.
+    ui.handle_event(Input::Text("x".to_string()));
+    assert!(ui.global_input().events().any(is_delivered));
+
+    ui.handle_event(tab.clone());
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(a));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::WidgetUncapturesInputSource(c, keyboard)));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::WidgetCapturesInputSource(a, keyboard)));
+
+    // Shift+Tab moves backwards, wrapping around the ends of the chain.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LShift)));
+    ui.handle_event(tab.clone());
+    ui.handle_event(tab.clone());
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(b));
+}
+
+#[test]
+fn escape_should_cancel_drags_and_release_mouse_capture() {
+    let ui = &mut windowless_ui();
+
+    let (canvas, button) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    let set = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::Button::new()
+            .w_h(100.0, 100.0)
+            .middle_of(canvas)
+            .set(button, ui);
+    };
+    set(ui);
+
+    // Press the button and drag the mouse out over the canvas.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(200.0, 200.0, ui);
+    set(ui);
+    assert_eq!(ui.global_input().current.widget_capturing_mouse, Some(button));
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    let found = ui.global_input().events().any(|event| match *event {
+        event::Event::Ui(event::Ui::DragCancel(Some(id), cancel)) =>
+            id == button && cancel.button == MouseButton::Left && cancel.xy == [200.0, 200.0],
+        _ => false,
+    });
+    assert!(found);
+    assert!(ui.global_input().current.mouse.buttons.left().is_up());
+    assert_eq!(ui.global_input().current.widget_capturing_mouse, Some(canvas));
+    set(ui);
+
+    // Neither moving nor releasing the cancelled button should produce a `Drag` or `Click`.
+    move_mouse_to_abs_coordinates(210.0, 210.0, ui);
+    release_mouse_button(MouseButton::Left, ui);
+    let found = ui.global_input().events().any(|event| match *event {
+        event::Event::Ui(event::Ui::Drag(..)) | event::Event::Ui(event::Ui::Click(..)) => true,
+        _ => false,
+    });
+    assert!(!found);
+    set(ui);
+
+    // Buttons that are held without dragging are released without a `DragCancel`.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    press_mouse_button(MouseButton::Right, ui);
+    set(ui);
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    let found = ui.global_input().events().any(|event| match *event {
+        event::Event::Ui(event::Ui::DragCancel(..)) => true,
+        _ => false,
+    });
+    assert!(!found);
+    assert!(ui.global_input().current.mouse.buttons.left().is_up());
+    assert!(ui.global_input().current.mouse.buttons.right().is_up());
+}
+
+#[test]
+fn escape_should_return_a_dragged_canvas_to_where_the_drag_began() {
+    let ui = &mut windowless_ui();
+    let canvas = ui.widget_id_generator().next();
+    let set = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .title_bar("Drag")
+            .floating(true)
+            .w_h(100.0, 100.0)
+            .x_y(0.0, 0.0)
+            .set(canvas, ui);
+    };
+    set(ui);
+
+    // Drag the canvas by its title bar much further than its own size.
+    let title_y = ui.rect_of(canvas).unwrap().top() - 5.0;
+    move_mouse_to_abs_coordinates(0.0, title_y, ui);
+    set(ui);
+    press_mouse_button(MouseButton::Left, ui);
+    for &(x, y) in &[(100.0, title_y - 60.0), (250.0, title_y - 180.0), (300.0, title_y - 200.0)] {
+        move_mouse_to_abs_coordinates(x, y, ui);
+        set(ui);
+    }
+    assert_eq!(ui.xy_of(canvas), Some([300.0, -200.0]));
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    set(ui);
+    assert_eq!(ui.xy_of(canvas), Some([0.0, 0.0]));
+}
+
+#[test]
+fn shortcuts_should_be_triggered_within_their_scope() {
+    use input::shortcut::{Chord, Scope};
+
+    let ui = &mut windowless_ui();
+
+    let (canvas, text_edit) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    let save = ui.shortcuts.register(Chord::new(Key::S).ctrl(), Scope::Global).unwrap();
+    let find = ui.shortcuts.register(Chord::new(Key::F).ctrl(), Scope::Widget(canvas)).unwrap();
+    let quit = ui.shortcuts.register(Chord::new(Key::Q), Scope::Global).unwrap();
+
+    // Chords may only be registered once within each scope.
+    let conflict = ui.shortcuts.register(Chord::new(Key::S).ctrl(), Scope::Global);
+    assert_eq!(conflict.map_err(|conflict| conflict.existing), Err(save));
+    assert!(ui.shortcuts.register(Chord::new(Key::S).ctrl(), Scope::Widget(canvas)).is_ok());
+
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        widget::TextEdit::new("").parent(canvas).set(text_edit, ui);
+    }
+    ui.keyboard_capture(text_edit);
+
+    let shortcut_triggered = |ui: &Ui, widget, id| ui.global_input().events().any(|event| {
+        *event == event::Event::Ui(event::Ui::Shortcut(widget, id))
+    });
+
+    // Plain keys are left to the focusable widget capturing the keyboard.
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Q)));
+    assert!(!shortcut_triggered(ui, None, quit));
+
+    ui.handle_event(Input::Press(Button::Keyboard(Key::LCtrl)));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::F)));
+    assert!(shortcut_triggered(ui, Some(canvas), find));
+
+    // The press that triggered the shortcut is not delivered to the capturing widget.
+    let press = event::Press {
+        button: event::Button::Keyboard(Key::F),
+        modifiers: ModifierKey::CTRL,
+    };
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::Press(None, press)));
+}
+
+#[test]
+fn touch_gestures_should_be_recognized() {
+    use input::touch::{Id, Phase, Touch};
+
+    let ui = &mut windowless_ui();
+    let canvas = ui.widget_id_generator().next();
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+    }
+
+    let touch = |ui: &mut Ui, phase, id, xy| {
+        ui.handle_event(Input::Touch(Touch { phase: phase, id: Id::new(id), xy: xy }));
+    };
+
+    // Moving two touches apart produces a pinch and a pan of their center.
+    touch(ui, Phase::Start, 0, [-10.0, 0.0]);
+    touch(ui, Phase::Start, 1, [10.0, 0.0]);
+    touch(ui, Phase::Move, 1, [30.0, 0.0]);
+    let pinch = event::Pinch { center: [10.0, 0.0], scale: 2.0 };
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::Pinch(Some(canvas), pinch)));
+    let pan = event::Pan { center: [10.0, 0.0], delta_xy: [10.0, 0.0] };
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::Pan(Some(canvas), pan)));
+
+    // Touches belonging to a pinch never produce a long press.
+    ui.theme.long_press_duration = std::time::Duration::from_millis(0);
+    ui.handle_event(Input::Redraw);
+    let is_long_press = |event: &event::Event| match *event {
+        event::Event::Ui(event::Ui::LongPress(..)) => true,
+        _ => false,
+    };
+    assert!(!ui.global_input().events().any(is_long_press));
+
+    touch(ui, Phase::End, 0, [-10.0, 0.0]);
+    touch(ui, Phase::End, 1, [30.0, 0.0]);
+    touch(ui, Phase::Start, 2, [5.0, 5.0]);
+    ui.handle_event(Input::Redraw);
+    let long_press = event::LongPress { id: Id::new(2), xy: [5.0, 5.0] };
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::LongPress(Some(canvas), long_press)));
+}
+
+#[test]
+fn long_presses_should_keep_the_ui_animating_until_they_fire() {
+    use clock::Manual;
+    use input::touch::{Id, Phase, Touch};
+
+    let clock = Manual::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let canvas = ui.widget_id_generator().next();
+    let set = |ui: &mut Ui| -> bool {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().set(canvas, ui);
+        ui.global_input().events().any(|event| match *event {
+            event::Event::Ui(event::Ui::LongPress(..)) => true,
+            _ => false,
+        })
+    };
+    set(ui);
+    assert!(!ui.is_animating());
+
+    // A touch held in place keeps the `Ui` animating while it may still become a long press.
+    let touch = Touch { phase: Phase::Start, id: Id::new(0), xy: [0.0, 0.0] };
+    ui.handle_event(Input::Touch(touch));
+    assert!(!set(ui));
+    assert!(ui.is_animating());
+
+    // Once the duration has elapsed, updating the `Ui` fires the long press without any input.
+    clock.advance(ui.theme.long_press_duration);
+    assert!(set(ui));
+    assert!(!ui.is_animating());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn recorded_input_should_replay_into_a_new_ui() {
+    use input::record::{Player, Recorder, Recording};
+    use std::io::{Cursor, Write};
+    use std::sync::{Arc, Mutex};
+
+    // A `Write`r whose bytes remain accessible once the `Recorder` has taken it.
+    #[derive(Clone)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+    impl Write for Buffer {
+        fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(bytes)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let inputs = vec![
+        Input::Motion(Motion::MouseCursor { x: 10.0, y: -20.0 }),
+        Input::Press(Button::Mouse(MouseButton::Left)),
+        Input::Release(Button::Mouse(MouseButton::Left)),
+        Input::Text("conrod".into()),
+    ];
+
+    let ui = &mut windowless_ui();
+    let buffer = Buffer(Arc::new(Mutex::new(Vec::new())));
+    let recorder = Recorder::new(buffer.clone(), [ui.win_w, ui.win_h]);
+    ui.start_recording(recorder);
+    for input in &inputs {
+        ui.handle_event(input.clone());
+    }
+    ui.stop_recording().unwrap().finish().unwrap();
+
+    let bytes = buffer.0.lock().unwrap().clone();
+    let recording = Recording::read(Cursor::new(bytes)).unwrap();
+    assert_eq!(recording.window_dimensions, [800.0, 600.0]);
+    let recorded: Vec<_> = recording.entries.iter().map(|entry| entry.input.clone()).collect();
+    assert_eq!(recorded, inputs);
+
+    let replayed = &mut UiBuilder::new([100.0, 100.0]).build();
+    let mut player = Player::new(recording);
+    assert_eq!(player.play_all(replayed), inputs.len());
+    assert!(player.is_finished());
+    assert_eq!([replayed.win_w, replayed.win_h], [800.0, 600.0]);
+    assert_eq!(replayed.global_input().current.mouse.xy, [10.0, -20.0]);
+}
+
+#[test]
+fn double_clicks_should_be_timed_by_the_ui_clock() {
+    use clock::Manual;
+    use std::time::Duration;
+
+    let clock = Manual::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let is_double_click = |event: &event::Event| match *event {
+        event::Event::Ui(event::Ui::DoubleClick(..)) => true,
+        _ => false,
+    };
+
+    // Two clicks separated by more than the threshold are not a double click.
+    left_click_mouse(ui);
+    clock.advance(ui.theme.double_click_threshold);
+    left_click_mouse(ui);
+    assert!(!ui.global_input().events().any(&is_double_click));
+
+    // However the same clicks separated by less than the threshold are.
+    clock.advance(ui.theme.double_click_threshold - Duration::from_millis(1));
+    left_click_mouse(ui);
+    assert!(ui.global_input().events().any(&is_double_click));
+}
+
+#[test]
+fn spatial_navigation_should_move_focus_and_activate_buttons() {
+    use input::navigation::Navigation;
+
+    let ui = &mut windowless_ui();
+    ui.navigation = Navigation::standard();
+
+    let ids = {
+        let mut id_generator = ui.widget_id_generator();
+        [id_generator.next(), id_generator.next(), id_generator.next(), id_generator.next()]
+    };
+    let (top_left, top_right, bottom_left, bottom_right) = (ids[0], ids[1], ids[2], ids[3]);
+
+    let set_buttons = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        let xys = [[-100.0, 100.0], [100.0, 100.0], [-100.0, -100.0], [100.0, -100.0]];
+        let mut times_clicked = [0; 4];
+        for (i, (&id, &xy)) in ids.iter().zip(xys.iter()).enumerate() {
+            times_clicked[i] = widget::Button::new().w_h(80.0, 40.0).xy(xy).set(id, ui).count();
+        }
+        times_clicked
+    };
+    set_buttons(ui);
+
+    let press = |ui: &mut Ui, key| ui.handle_event(Input::Press(Button::Keyboard(key)));
+
+    // With nothing focused, focus moves to the start of the focus chain.
+    press(ui, Key::Right);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(top_left));
+
+    press(ui, Key::Right);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(top_right));
+    press(ui, Key::Down);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(bottom_right));
+    press(ui, Key::Left);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(bottom_left));
+
+    // There is nothing further to the left.
+    press(ui, Key::Left);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(bottom_left));
+
+    press(ui, Key::Return);
+    assert_eq!(set_buttons(ui), [0, 0, 1, 0]);
+}
+
+#[test]
+fn spatial_navigation_should_leave_navigation_keys_to_menus_and_list_selects() {
+    use input::navigation::Navigation;
+    use widget::list_select::Event;
+    use widget::menu::Item;
+
+    let ui = &mut windowless_ui();
+    ui.navigation = Navigation::standard();
+
+    let (canvas, menu, button, list) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next(), id_generator.next())
+    };
+    let items = [Item::new("Cut"), Item::new("Copy"), Item::new("Paste")];
+    let selected = std::cell::Cell::new(None);
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(200.0, 200.0).middle_of(ui.window).set(canvas, ui);
+        let chosen = widget::Menu::new(&items).context_for(canvas).set(menu, ui);
+        widget::Button::new().w_h(80.0, 40.0).x_y(0.0, -200.0).set(button, ui);
+        let (mut events, _) = widget::ListSelect::single(3)
+            .flow_down()
+            .item_size(20.0)
+            .w_h(100.0, 60.0)
+            .x_y(300.0, -200.0)
+            .set(list, ui);
+        while let Some(event) = events.next(ui, |i| Some(i) == selected.get()) {
+            match event {
+                Event::Item(item) => {
+                    item.set(widget::Rectangle::fill([100.0, 20.0]), ui);
+                },
+                Event::Selection(i) => selected.set(Some(i)),
+                _ => (),
+            }
+        }
+        chosen
+    };
+    let press = |ui: &mut Ui, key| ui.handle_event(Input::Press(Button::Keyboard(key)));
+    set_widgets(ui);
+
+    // Arrow keys move the highlight within an open menu rather than moving focus to the button.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    press_mouse_button(MouseButton::Right, ui);
+    release_mouse_button(MouseButton::Right, ui);
+    assert_eq!(set_widgets(ui), None);
+    press(ui, Key::Down);
+    press(ui, Key::Down);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(menu));
+    press(ui, Key::Return);
+    assert_eq!(set_widgets(ui), Some(vec![1]));
+
+    // A focused list select is activated by `Return` and moves its selection with arrow keys.
+    press(ui, Key::Right);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(button));
+    press(ui, Key::Right);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(list));
+    press(ui, Key::Return);
+    set_widgets(ui);
+    assert_eq!(selected.get(), Some(0));
+    press(ui, Key::Down);
+    press(ui, Key::Down);
+    set_widgets(ui);
+    assert_eq!(selected.get(), Some(2));
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(list));
+}
+
+#[test]
+fn mouse_enter_and_leave_should_follow_the_widget_under_the_cursor() {
+    let ui = &mut windowless_ui();
+    let (a, b) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0]).x_y(-100.0, 0.0).set(a, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(100.0, 0.0).set(b, ui);
+    }
+
+    move_mouse_to_abs_coordinates(-100.0, 0.0, ui);
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::MouseEnter(a)));
+
+    move_mouse_to_abs_coordinates(100.0, 0.0, ui);
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::MouseLeave(a)));
+    assert_event_was_pushed(ui, event::Event::Ui(event::Ui::MouseEnter(b)));
+
+    let b_events: Vec<_> = ui.widget_input(b).events().collect();
+    assert!(b_events.contains(&event::Widget::MouseEnter));
+    assert!(!b_events.contains(&event::Widget::MouseLeave));
+}
+
+#[test]
+fn tooltips_should_appear_after_the_hover_delay_and_hide_on_motion() {
+    use clock::Manual;
+
+    let clock = Manual::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    ui.fonts.insert_from_file(font_path).unwrap();
+
+    let (canvas, button) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(400.0, 400.0).middle_of(ui.window).set(canvas, ui);
+        ui.tooltip(canvas, "A canvas");
+        widget::Button::new().w_h(100.0, 100.0).middle_of(canvas).set(button, ui);
+        ui.tooltip(button, "A button");
+    };
+    set_widgets(ui);
+
+    // The tooltip of the innermost widget appears once the mouse has rested for the delay. The
+    // `Ui` keeps animating until then so that it is updated without any further input.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip_target(), None);
+    assert!(ui.is_animating());
+    clock.advance(ui.theme.tooltip_delay);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip_target(), Some(button));
+    assert!(!ui.is_animating());
+
+    // Moving the mouse hides the tooltip until the mouse rests again.
+    move_mouse_to_abs_coordinates(150.0, 150.0, ui);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip_target(), None);
+    clock.advance(ui.theme.tooltip_delay);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip_target(), Some(canvas));
+
+    // As does pressing a button.
+    press_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip_target(), None);
+    release_mouse_button(MouseButton::Left, ui);
+    clock.advance(ui.theme.tooltip_delay);
+    set_widgets(ui);
+    assert_eq!(ui.tooltip_target(), Some(canvas));
+}
+
+#[test]
+fn drag_and_drop_should_deliver_payloads_to_accepting_targets() {
+    use input::drag_drop::Drag;
+
+    let ui = &mut windowless_ui();
+    let (source, target, other) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next())
+    };
+
+    // The source begins a drag of `42` once dragged, the target only accepts `u32` payloads and
+    // takes any payload dropped onto it.
+    let set_widgets = |ui: &mut Ui| -> Option<u32> {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0]).x_y(-200.0, 0.0).set(source, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(0.0, 0.0).set(target, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(200.0, 0.0).set(other, ui);
+        if ui.widget_input(source).drags().left().next().is_some() && ui.drag().is_none() {
+            ui.start_drag(Drag::new(source, 42u32).label("42"));
+        }
+        if ui.drag_over(target).map(|drag| drag.payload_is::<u32>()).unwrap_or(false) {
+            ui.accept_drop(target);
+        }
+        // The source also receives the drop but may not take the payload from the target.
+        if ui.widget_input(source).drops().next().is_some() {
+            assert_eq!(ui.take_dropped_payload::<u32>(source), None);
+        }
+        match ui.widget_input(target).drops().next() {
+            Some(_) => ui.take_dropped_payload::<u32>(target),
+            None => None,
+        }
+    };
+    let is_drop = |event: &event::Event| match *event {
+        event::Event::Ui(event::Ui::Drop(..)) => true,
+        _ => false,
+    };
+    let is_source_cancel = |event: &event::Event| match *event {
+        event::Event::Ui(event::Ui::DragCancel(Some(id), _)) => id == source,
+        _ => false,
+    };
+    set_widgets(ui);
+
+    // Drag from the source onto the target.
+    move_mouse_to_abs_coordinates(-200.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-190.0, 0.0, ui);
+    set_widgets(ui);
+    assert_eq!(ui.drag().map(|drag| drag.source()), Some(source));
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    set_widgets(ui);
+    assert_eq!(ui.drop_target(), Some(target));
+
+    // Releasing drops the payload onto the target, which receives it during the next update.
+    release_mouse_button(MouseButton::Left, ui);
+    assert!(ui.global_input().events().any(&is_drop));
+    assert!(ui.drag().is_none());
+    assert_eq!(set_widgets(ui), Some(42));
+    assert!(ui.dropped().is_none());
+
+    // Releasing over a widget that does not accept the drag cancels it.
+    move_mouse_to_abs_coordinates(-200.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-190.0, 0.0, ui);
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(200.0, 0.0, ui);
+    set_widgets(ui);
+    assert_eq!(ui.drop_target(), None);
+    release_mouse_button(MouseButton::Left, ui);
+    assert!(!ui.global_input().events().any(&is_drop));
+    assert!(ui.global_input().events().any(&is_source_cancel));
+    assert_eq!(set_widgets(ui), None);
+
+    // As does pressing `Escape`.
+    move_mouse_to_abs_coordinates(-200.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-190.0, 0.0, ui);
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    set_widgets(ui);
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    assert!(ui.drag().is_none());
+    assert!(ui.global_input().events().any(&is_source_cancel));
+    release_mouse_button(MouseButton::Left, ui);
+    assert!(!ui.global_input().events().any(&is_drop));
+}
+
+#[test]
+fn menus_should_open_on_right_click_and_return_the_chosen_item() {
+    use widget::menu::Item;
+
+    let ui = &mut windowless_ui();
+    let (canvas, menu) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let submenu = [Item::new("A"), Item::new("B")];
+    let items = [
+        Item::new("Cut").accelerator("Ctrl+X"),
+        Item::separator(),
+        Item::new("Disabled").enabled(false),
+        Item::check("Grid", true),
+        Item::submenu("More", &submenu),
+    ];
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().w_h(400.0, 400.0).middle_of(ui.window).set(canvas, ui);
+        widget::Menu::new(&items).context_for(canvas).set(menu, ui)
+    };
+    let open_at_origin = |ui: &mut Ui| {
+        move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+        press_mouse_button(MouseButton::Right, ui);
+        release_mouse_button(MouseButton::Right, ui);
+        assert_eq!(set_widgets(ui), None);
+        assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(menu));
+    };
+    let press = |ui: &mut Ui, key| ui.handle_event(Input::Press(Button::Keyboard(key)));
+    set_widgets(ui);
+
+    // Give the canvas keyboard capture so that we can check it is restored.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    set_widgets(ui);
+
+    // Keyboard navigation skips separators and disabled items.
+    open_at_origin(ui);
+    press(ui, Key::Down);
+    press(ui, Key::Down);
+    assert_eq!(set_widgets(ui), None);
+    press(ui, Key::Down);
+    press(ui, Key::Right);
+    press(ui, Key::Down);
+    press(ui, Key::Return);
+    assert_eq!(set_widgets(ui), Some(vec![4, 1]));
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(canvas));
+
+    // Escape closes the menu without choosing anything.
+    open_at_origin(ui);
+    press(ui, Key::Down);
+    press(ui, Key::Escape);
+    press(ui, Key::Return);
+    assert_eq!(set_widgets(ui), None);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(canvas));
+
+    // Clicking an item chooses it. The menu opens with its top left corner at the cursor.
+    open_at_origin(ui);
+    move_mouse_to_abs_coordinates(20.0, -12.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(set_widgets(ui), Some(vec![0]));
+
+    // Clicking outside of the menu closes it.
+    open_at_origin(ui);
+    move_mouse_to_abs_coordinates(-100.0, 100.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(set_widgets(ui), None);
+    move_mouse_to_abs_coordinates(20.0, -12.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(set_widgets(ui), None);
+}
+
+#[test]
+fn menus_opened_at_a_click_should_ignore_the_click_that_opened_them() {
+    use widget::menu::Item;
+
+    let ui = &mut windowless_ui();
+    let (button, menu) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let items = [Item::new("Cut"), Item::new("Copy")];
+    // The menu opens beneath the button when it is clicked.
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        let clicks = widget::Button::new().w_h(100.0, 100.0).middle_of(ui.window).set(button, ui);
+        let mut menu_widget = widget::Menu::new(&items);
+        if clicks.was_clicked() {
+            menu_widget = menu_widget.open_at([-50.0, -50.0]);
+        }
+        menu_widget.set(menu, ui)
+    };
+    set_widgets(ui);
+
+    // The click that opens the menu lies outside of it, yet does not close it.
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(set_widgets(ui), None);
+    assert_eq!(ui.global_input().current.widget_capturing_keyboard, Some(menu));
+
+    // Clicks after the menu has opened are handled as usual.
+    move_mouse_to_abs_coordinates(-30.0, -62.0, ui);
+    left_click_mouse(ui);
+    assert_eq!(set_widgets(ui), Some(vec![0]));
+}
+
+#[test]
+fn files_from_the_os_should_be_delivered_to_the_widget_under_the_cursor() {
+    use std::path::PathBuf;
+
+    let ui = &mut windowless_ui();
+    let (left, right) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let set_widgets = |ui: &mut Ui| -> (Vec<event::Widget>, Vec<event::Widget>) {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([100.0, 100.0]).x_y(-100.0, 0.0).set(left, ui);
+        widget::Rectangle::fill([100.0, 100.0]).x_y(100.0, 0.0).set(right, ui);
+        let is_file_event = |event: &event::Widget| match *event {
+            event::Widget::FileHover(_) |
+            event::Widget::FileDrop(_) |
+            event::Widget::FileHoverCancel => true,
+            _ => false,
+        };
+        (ui.widget_input(left).events().filter(&is_file_event).collect(),
+         ui.widget_input(right).events().filter(&is_file_event).collect())
+    };
+    let path = PathBuf::from("image.png");
+    set_widgets(ui);
+
+    // Hovering and then leaving the window notifies the widget that received the hover.
+    move_mouse_to_abs_coordinates(-90.0, 0.0, ui);
+    set_widgets(ui);
+    ui.handle_event(Input::FileHovered(path.clone()));
+    let (left_events, right_events) = set_widgets(ui);
+    let hover = event::FileHover { path: path.clone(), xy: [10.0, 0.0] };
+    assert_eq!(left_events, vec![event::Widget::FileHover(hover)]);
+    assert!(right_events.is_empty());
+    ui.handle_event(Input::FileHoverCancelled);
+    let (left_events, right_events) = set_widgets(ui);
+    assert_eq!(left_events, vec![event::Widget::FileHoverCancel]);
+    assert!(right_events.is_empty());
+
+    // Dropping delivers the file to the widget under the cursor.
+    move_mouse_to_abs_coordinates(100.0, 0.0, ui);
+    set_widgets(ui);
+    ui.handle_event(Input::FileHovered(path.clone()));
+    ui.handle_event(Input::FileDropped(path.clone()));
+    let (left_events, right_events) = set_widgets(ui);
+    assert!(left_events.is_empty());
+    let drops: Vec<_> = right_events.iter().filter_map(|event| match *event {
+        event::Widget::FileDrop(ref drop) => Some(drop.path.clone()),
+        _ => None,
+    }).collect();
+    assert_eq!(drops, vec![path]);
+    assert_eq!(right_events.len(), 2);
+}
+
+#[test]
+fn kinetic_scrolling_should_continue_with_momentum_and_bounce_off_the_ends() {
+    use clock::Manual;
+    use std::time::Duration;
+
+    let clock = Manual::new();
+    let ui = &mut UiBuilder::new([800.0, 600.0]).clock(clock.clone()).build();
+    let (canvas, content) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new()
+            .w_h(200.0, 200.0)
+            .x_y(0.0, 0.0)
+            .scroll_kids_vertically()
+            .scroll_kinetically()
+            .set(canvas, ui);
+        widget::Rectangle::fill([100.0, 1000.0]).mid_top_of(canvas).set(content, ui);
+    };
+    let y_scroll = |ui: &Ui| ui.widget_graph().widget(canvas)
+        .and_then(|container| container.maybe_y_scroll_state)
+        .expect("canvas should scroll vertically");
+    let frame = Duration::from_millis(16);
+    set_widgets(ui);
+    set_widgets(ui);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+
+    // Flick the canvas, after which it should continue scrolling without further input.
+    for _ in 0..5 {
+        ui.handle_event(Input::Motion(Motion::Scroll { x: 0.0, y: 20.0 }));
+        clock.advance(frame);
+        set_widgets(ui);
+    }
+    let released_offset = y_scroll(ui).offset;
+    assert!(released_offset != 0.0);
+    clock.advance(frame);
+    set_widgets(ui);
+    clock.advance(frame);
+    set_widgets(ui);
+    let coasted_offset = y_scroll(ui).offset;
+    assert!(ui.is_animating());
+    assert!(coasted_offset.abs() > released_offset.abs());
+
+    // The momentum decays until the canvas comes to rest.
+    let mut frames = 0;
+    while ui.is_animating() {
+        clock.advance(frame);
+        set_widgets(ui);
+        frames += 1;
+        assert!(frames < 1_000, "kinetic scrolling should come to rest");
+    }
+    set_widgets(ui);
+    assert_eq!(y_scroll(ui).overscroll, 0.0);
+
+    // A fast flick carries the content beyond the end before bouncing back within the bounds.
+    // The distance by which the content is displayed beyond the edge of the canvas.
+    let beyond_edge = |ui: &Ui| {
+        let canvas_rect = ui.rect_of(canvas).unwrap();
+        let content_rect = ui.rect_of(content).unwrap();
+        (canvas_rect.top() - content_rect.top())
+            .max(content_rect.bottom() - canvas_rect.bottom())
+            .max(0.0)
+    };
+    for _ in 0..5 {
+        ui.handle_event(Input::Motion(Motion::Scroll { x: 0.0, y: 150.0 }));
+        clock.advance(frame);
+        set_widgets(ui);
+    }
+    let mut max_beyond_edge: f64 = 0.0;
+    let mut frames = 0;
+    while ui.is_animating() {
+        clock.advance(frame);
+        set_widgets(ui);
+        let beyond_edge = beyond_edge(ui);
+        assert!((beyond_edge - y_scroll(ui).overscroll.abs()).abs() < 1e-6);
+        max_beyond_edge = max_beyond_edge.max(beyond_edge);
+        frames += 1;
+        assert!(frames < 1_000, "kinetic scrolling should come to rest");
+    }
+    set_widgets(ui);
+    let state = y_scroll(ui);
+    assert!(max_beyond_edge > 0.0);
+    assert_eq!(state.overscroll, 0.0);
+    assert_eq!(beyond_edge(ui), 0.0);
+    assert!(state.offset == state.offset_bounds.start || state.offset == state.offset_bounds.end);
+}
+
+#[test]
+fn flex_should_grow_and_wrap_its_items() {
+    use position::Rect;
+    use widget::flex::{self, Flex, Justify};
+
+    let ui = &mut windowless_ui();
+    let (row, wrapping) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let set_flex = |ui: &mut Ui, id: widget::Id, flex: Flex| -> Vec<Rect> {
+        let ids: Vec<widget::Id> = {
+            let ui = &mut ui.set_widgets();
+            flex.set(id, ui).map(|element| {
+                let widget_id = element.widget_id;
+                element.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+                widget_id
+            }).collect()
+        };
+        ids.iter().map(|&id| ui.rect_of(id).unwrap()).collect()
+    };
+
+    // Free space is shared between the items in proportion to their `grow`.
+    let items = [
+        flex::Item::new().basis(40.0).grow(1.0),
+        flex::Item::new().basis(40.0).grow(3.0),
+        flex::Item::new().basis(40.0),
+    ];
+    let flex = Flex::new(&items).w_h(300.0, 100.0).x_y(0.0, 0.0).gap(10.0);
+    let rects = set_flex(ui, row, flex);
+    let widths: Vec<_> = rects.iter().map(|r| r.w()).collect();
+    let xs: Vec<_> = rects.iter().map(|r| r.x()).collect();
+    assert_eq!(widths, vec![80.0, 160.0, 40.0]);
+    assert_eq!(xs, vec![-110.0, 20.0, 130.0]);
+    assert!(rects.iter().all(|r| r.h() == 100.0 && r.y() == 0.0));
+
+    // Items that overflow the line wrap onto the next.
+    let items = [flex::Item::new().basis(60.0).cross(20.0); 3];
+    let flex = Flex::new(&items)
+        .w_h(100.0, 80.0)
+        .x_y(0.0, 0.0)
+        .wrap(true)
+        .line_gap(10.0)
+        .justify(Justify::Middle);
+    let rects = set_flex(ui, wrapping, flex);
+    let xys: Vec<_> = rects.iter().map(|r| r.xy()).collect();
+    assert_eq!(xys, vec![[0.0, 30.0], [0.0, 0.0], [0.0, -30.0]]);
+}
+
+#[test]
+fn constraints_should_relate_the_edges_and_dimensions_of_many_widgets() {
+    use position::constraint::{height, left, right, top, width, WEAK};
+
+    let ui = &mut windowless_ui();
+    let (a, b) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+
+    {
+        let ui = &mut ui.set_widgets();
+        let window = ui.window;
+
+        // Two widgets of equal width filling the top of the window minus padding.
+        ui.constrain(width(a).eq(width(b)));
+        ui.constrain(left(a).eq(left(window) + 10.0));
+        ui.constrain(left(b).eq(right(a) + 10.0));
+        ui.constrain(right(b).eq(right(window) - 10.0));
+        ui.constrain(top(a).eq(top(window) - 10.0));
+        ui.constrain(top(b).eq(top(a)));
+        ui.constrain(height(b).le(20.0));
+
+        // Weaker constraints give way to the required ones.
+        ui.constrain(width(a).ge(500.0).strength(WEAK));
+
+        widget::Rectangle::fill([0.0, 30.0]).x_y(0.0, 0.0).set(a, ui);
+        widget::Rectangle::fill([0.0, 30.0]).x_y(0.0, 0.0).set(b, ui);
+    }
+
+    let rect_a = ui.rect_of(a).unwrap();
+    let rect_b = ui.rect_of(b).unwrap();
+    assert_eq!(rect_a.x_y_w_h(), (-197.5, 275.0, 385.0, 30.0));
+    assert_eq!(rect_b.x_y_w_h(), (197.5, 280.0, 385.0, 20.0));
+}
+
+#[test]
+fn widgets_sized_to_content_should_fit_their_label_or_children() {
+    use text;
+    use position::Rect;
+
+    let ui = &mut windowless_ui();
+    let font_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/fonts/NotoSans/NotoSans-Regular.ttf");
+    let font_id = ui.fonts.insert_from_file(font_path).unwrap();
+    let (button, canvas, a, b) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next(), id_generator.next())
+    };
+    let (below, toggle, text_box, drop_down_list) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next(), id_generator.next())
+    };
+    let items = ["One", "Three"];
+
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Button::new()
+            .label("Content")
+            .label_font_size(20)
+            .padded_wh_to_content(10.0)
+            .set(button, ui);
+
+        // The canvas is fit around its children within the same stage.
+        widget::Canvas::new().border(0.0).padded_wh_to_content(5.0).set(canvas, ui);
+        widget::Rectangle::fill([100.0, 50.0]).x_y(0.0, 0.0).parent(canvas).set(a, ui);
+        widget::Rectangle::fill([20.0, 20.0]).x_y(100.0, -50.0).parent(canvas).set(b, ui);
+
+        // Siblings are positioned relative to the fitted canvas, even during the first stage.
+        widget::Rectangle::fill([10.0, 10.0]).down_from(canvas, 10.0).set(below, ui);
+
+        widget::Toggle::new(true).label("On").label_font_size(20).wh_to_content().set(toggle, ui);
+        widget::TextBox::new("Text")
+            .font_size(20)
+            .border(2.0)
+            .wh_to_content()
+            .set(text_box, ui);
+        widget::DropDownList::new(&items, None)
+            .label_font_size(20)
+            .wh_to_content()
+            .set(drop_down_list, ui);
+    }
+
+    let font = ui.fonts.get(font_id).unwrap();
+    let label_w = text::line::width("Content", font, 20);
+    assert_eq!(ui.wh_of(button), Some([label_w + 20.0, 40.0]));
+    let expected = Rect::from_corners([-55.0, -65.0], [115.0, 30.0]);
+    assert_eq!(ui.rect_of(canvas), Some(expected));
+    assert_eq!(ui.kid_area_of(canvas), Some(expected));
+    assert_eq!(ui.rect_of(below).map(|rect| rect.top()), Some(expected.bottom() - 10.0));
+
+    let on_w = text::line::width("On", font, 20);
+    assert_eq!(ui.wh_of(toggle), Some([on_w, 20.0]));
+    let text_w = text::line::width("Text", font, 20);
+    assert_eq!(ui.wh_of(text_box), Some([text_w + 14.0, 25.0]));
+    let three_w = text::line::width("Three", font, 20);
+    assert_eq!(ui.wh_of(drop_down_list), Some([three_w, 20.0]));
+}
+
+#[test]
+fn widgets_sized_to_content_should_be_set_again_when_relatively_positioned_children_resize_them() {
+    use position::Rect;
+
+    let ui = &mut windowless_ui();
+    let (canvas, child) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next())
+    };
+    let set_widgets = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        widget::Canvas::new().border(0.0).padded_wh_to_content(5.0).x_y(0.0, 0.0).set(canvas, ui);
+        widget::Rectangle::fill([100.0, 50.0]).mid_top_of(canvas).set(child, ui);
+    };
+
+    // The child is first positioned against the canvas before it has been fit, so the widgets
+    // must be set again.
+    set_widgets(ui);
+    assert!(ui.is_animating());
+    assert_eq!(ui.wh_of(canvas), Some([110.0, 60.0]));
+
+    // Once the canvas has been resized, the child is positioned against its fitted `Rect` and the
+    // layout settles.
+    let expected_canvas = Rect::from_corners([-55.0, -25.0], [55.0, 35.0]);
+    let expected_child = Rect::from_corners([-50.0, -20.0], [50.0, 30.0]);
+    for _ in 0..2 {
+        set_widgets(ui);
+        assert!(!ui.is_animating());
+        assert_eq!(ui.rect_of(canvas), Some(expected_canvas));
+        assert_eq!(ui.rect_of(child), Some(expected_child));
+    }
+}
+
+#[test]
+fn min_and_max_dimensions_should_be_honored_by_widgets_splits_and_list_items() {
+    let ui = &mut windowless_ui();
+    let (clamped, root, left, right, list) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next(), id_generator.next(),
+         id_generator.next())
+    };
+    let mut item_ids = Vec::new();
+
+    {
+        let ui = &mut ui.set_widgets();
+        widget::Rectangle::fill([1000.0, 10.0]).max_w(300.0).min_h(50.0).set(clamped, ui);
+
+        // The left split would be 400 wide by weight, but may not be narrower than 500.
+        widget::Canvas::new()
+            .flow_right(&[
+                (left, widget::Canvas::new().min_w(500.0)),
+                (right, widget::Canvas::new()),
+            ])
+            .wh_of(ui.window)
+            .middle_of(ui.window)
+            .set(root, ui);
+
+        let (mut items, _) = widget::List::flow_down(2)
+            .item_size(20.0)
+            .w_h(200.0, 100.0)
+            .top_left_of(ui.window)
+            .set(list, ui);
+        while let Some(item) = items.next(ui) {
+            item_ids.push(item.widget_id);
+            item.set(widget::Rectangle::fill([0.0, 0.0]).max_w(120.0), ui);
+        }
+    }
+
+    assert_eq!(ui.wh_of(clamped), Some([300.0, 50.0]));
+    assert_eq!(ui.w_of(left), Some(500.0));
+    assert_eq!(ui.w_of(right), Some(300.0));
+    assert_eq!(ui.rect_of(right).map(|r| r.right()), Some(400.0));
+
+    // List items are sized to the list's breadth within their own bounds.
+    let item_whs: Vec<_> = item_ids.iter().map(|&id| ui.wh_of(id).unwrap()).collect();
+    assert_eq!(item_whs, vec![[120.0, 20.0], [120.0, 20.0]]);
+
+    // Items of a fixed size list that clamp their own length are laid out and scrolled by the
+    // length they occupy, rather than overlapping the following items.
+    let mut set_clamped_list = |ui: &mut Ui| {
+        let ui = &mut ui.set_widgets();
+        let (mut items, _) = widget::List::flow_down(10)
+            .item_size(20.0)
+            .scrollbar_on_top()
+            .w_h(200.0, 100.0)
+            .top_left_of(ui.window)
+            .set(list, ui);
+        let mut item_rects = Vec::new();
+        while let Some(item) = items.next(ui) {
+            let (i, item_id) = (item.i, item.widget_id);
+            item.set(widget::Rectangle::fill([0.0, 0.0]).min_h(30.0), ui);
+            item_rects.push((i, ui.rect_of(item_id).unwrap()));
+        }
+        item_rects
+    };
+    set_clamped_list(ui);
+    let item_rects = set_clamped_list(ui);
+    let item_tops: Vec<_> = item_rects.iter().map(|&(i, r)| (i, r.top(), r.h())).collect();
+    assert_eq!(item_tops, vec![(0, 300.0, 30.0), (1, 270.0, 30.0), (2, 240.0, 30.0), (3, 210.0, 30.0)]);
+
+    move_mouse_to_abs_coordinates(-300.0, 250.0, ui);
+    ui.handle_event(Input::Motion(Motion::Scroll { x: 0.0, y: 45.0 }));
+    set_clamped_list(ui);
+    let item_rects = set_clamped_list(ui);
+    let item_tops: Vec<_> = item_rects.iter().map(|&(i, r)| (i, r.top())).collect();
+    assert_eq!(item_tops, vec![(1, 315.0), (2, 285.0), (3, 255.0), (4, 225.0)]);
+}
+
+#[test]
+fn min_and_max_dimensions_should_bound_fitted_and_constrained_widgets() {
+    use position::Rect;
+    use position::constraint::width;
+
+    let ui = &mut windowless_ui();
+    let (canvas, child, a, b) = {
+        let mut id_generator = ui.widget_id_generator();
+        (id_generator.next(), id_generator.next(), id_generator.next(), id_generator.next())
+    };
+
+    {
+        let ui = &mut ui.set_widgets();
+
+        // The canvas is fit around its child within its bounds, keeping its left and top edges.
+        widget::Canvas::new()
+            .border(0.0)
+            .padded_wh_to_content(5.0)
+            .min_w(300.0)
+            .max_h(40.0)
+            .set(canvas, ui);
+        widget::Rectangle::fill([100.0, 50.0]).x_y(0.0, 0.0).parent(canvas).set(child, ui);
+
+        // Constrained widgets are solved within their bounds, as are the widgets related to them.
+        // Constraints that conflict with the bounds are rejected.
+        ui.constrain(width(a).eq(500.0));
+        ui.constrain(width(b).eq(width(a)));
+        let window = ui.window;
+        widget::Rectangle::fill([500.0, 10.0]).max_w(300.0).parent(window).set(a, ui);
+        widget::Rectangle::fill([10.0, 10.0]).parent(window).set(b, ui);
+    }
+
+    assert_eq!(ui.rect_of(canvas), Some(Rect::from_corners([-55.0, -10.0], [245.0, 30.0])));
+    assert_eq!(ui.w_of(a), Some(300.0));
+    assert_eq!(ui.w_of(b), Some(300.0));
+    let rejected: Vec<_> = ui.rejected_constraints().iter().map(|&(ref c, _)| c.clone()).collect();
+    assert_eq!(rejected, vec![width(a).eq(500.0)]);
+}
+
+#[test]
+fn matrix_layout_should_size_tracks_and_place_spanning_cells() {
+    use position::{Align, Rect};
+    use position::matrix::{Cell, Matrix, Track};
+
+    let ui = &mut windowless_ui();
+    let ids: Vec<widget::Id> = {
+        let mut id_generator = ui.widget_id_generator();
+        (0..4).map(|_| id_generator.next()).collect()
+    };
+
+    let columns = [Track::Fixed(100.0), Track::Weight(1.0), Track::Auto];
+    let rows = [Track::Auto, Track::Weight(1.0)];
+    let cells = [
+        Cell::new(0, 0).span(2, 1).content_wh([0.0, 40.0]),
+        Cell::new(2, 0).content_wh([60.0, 20.0]),
+        Cell::new(0, 1).span(3, 1),
+        Cell::new(2, 1).align(Align::Middle, Align::End).content_wh([30.0, 30.0]),
+    ];
+    {
+        let ui = &mut ui.set_widgets();
+        let window = ui.window;
+        let elements = Matrix::new(&columns, &rows)
+            .gap(10.0, 10.0)
+            .wh_of(window)
+            .middle_of(window)
+            .layout(&cells, ui);
+        for element in elements {
+            element.set(widget::Rectangle::fill([0.0, 0.0]), ids[element.index], ui);
+        }
+    }
+
+    let rects: Vec<_> = ids.iter().map(|&id| ui.rect_of(id).unwrap()).collect();
+    assert_eq!(rects, vec![
+        Rect::from_corners([-400.0, 260.0], [330.0, 300.0]),
+        Rect::from_corners([340.0, 260.0], [400.0, 300.0]),
+        Rect::from_corners([-400.0, -300.0], [400.0, 250.0]),
+        Rect::from_corners([355.0, 220.0], [385.0, 250.0]),
+    ]);
+}
+
+#[test]
+fn flow_should_wrap_its_children_into_rows_and_grow_to_fit_them() {
+    use position::Rect;
+
+    let ui = &mut windowless_ui();
+    let (flow, below, ids) = {
+        let mut id_generator = ui.widget_id_generator();
+        let flow = id_generator.next();
+        let below = id_generator.next();
+        let ids: Vec<widget::Id> = (0..4).map(|_| id_generator.next()).collect();
+        (flow, below, ids)
+    };
+
+    let sizes = [[100.0, 40.0], [100.0, 60.0], [100.0, 30.0], [50.0, 50.0]];
+    let flow_h = {
+        let ui = &mut ui.set_widgets();
+        let window = ui.window;
+        let mut children = widget::Flow::new()
+            .w(300.0)
+            .mid_top_of(window)
+            .pad(10.0)
+            .spacing(10.0, 20.0)
+            .set(flow, ui);
+        for (&id, &wh) in ids.iter().zip(sizes.iter()) {
+            children.set(widget::Rectangle::fill(wh), id, ui);
+        }
+        widget::Rectangle::fill([10.0, 10.0]).down_from(flow, 0.0).set(below, ui);
+        children.h()
+    };
+
+    let rects: Vec<_> = ids.iter().map(|&id| ui.rect_of(id).unwrap()).collect();
+    assert_eq!(rects, vec![
+        Rect::from_corners([-140.0, 250.0], [-40.0, 290.0]),
+        Rect::from_corners([-30.0, 230.0], [70.0, 290.0]),
+        Rect::from_corners([-140.0, 180.0], [-40.0, 210.0]),
+        Rect::from_corners([-30.0, 160.0], [20.0, 210.0]),
+    ]);
+    assert_eq!(flow_h, 150.0);
+    assert_eq!(ui.rect_of(flow), Some(Rect::from_corners([-150.0, 150.0], [150.0, 300.0])));
+    assert_eq!(ui.xy_of(below).map(|xy| xy[1]), Some(145.0));
+}
+
+#[test]
+fn splitter_handles_should_resize_and_collapse_their_panes() {
+    use Scalar;
+    use cursor::MouseCursor;
+    use widget::splitter::{Pane, Splitter};
+
+    let ui = &mut windowless_ui();
+    let splitter = ui.widget_id_generator().next();
+    let panes = [
+        Pane::new().min(100.0).collapsible(true),
+        Pane::new().weight(2.0),
+        Pane::new().min(150.0),
+    ];
+    let set_widgets = |ui: &mut Ui| -> Vec<(usize, [Scalar; 2])> {
+        let pane_ids = {
+            let ui = &mut ui.set_widgets();
+            let window = ui.window;
+            let elements = Splitter::new(&panes)
+                .handle_thickness(10.0)
+                .wh_of(window)
+                .middle_of(window)
+                .set(splitter, ui);
+            elements.map(|element| {
+                let (index, widget_id) = (element.index, element.widget_id);
+                element.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+                (index, widget_id)
+            }).collect::<Vec<_>>()
+        };
+        pane_ids.into_iter()
+            .map(|(index, id)| (index, ui.rect_of(id).map(|r| [r.left(), r.right()]).unwrap()))
+            .collect()
+    };
+    assert_eq!(set_widgets(ui), vec![
+        (0, [-400.0, -205.0]),
+        (1, [-195.0, 195.0]),
+        (2, [205.0, 400.0]),
+    ]);
+
+    // Dragging a handle resizes the panes on either side, showing the resize cursor.
+    move_mouse_to_abs_coordinates(-200.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-100.0, 0.0, ui);
+    assert_eq!(set_widgets(ui), vec![
+        (0, [-400.0, -105.0]),
+        (1, [-95.0, 195.0]),
+        (2, [205.0, 400.0]),
+    ]);
+    assert_eq!(ui.mouse_cursor(), MouseCursor::ResizeHorizontal);
+    release_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui);
+
+    // Pressing escape during a drag returns the panes to their lengths before the drag.
+    move_mouse_to_abs_coordinates(-100.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(50.0, 0.0, ui);
+    assert_eq!(set_widgets(ui)[0], (0, [-400.0, 45.0]));
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    assert_eq!(set_widgets(ui), vec![
+        (0, [-400.0, -105.0]),
+        (1, [-95.0, 195.0]),
+        (2, [205.0, 400.0]),
+    ]);
+    move_mouse_to_abs_coordinates(0.0, 0.0, ui);
+    assert_eq!(set_widgets(ui)[0], (0, [-400.0, -105.0]));
+    release_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui);
+
+    // The panes may not be resized below their minimum length.
+    move_mouse_to_abs_coordinates(200.0, 0.0, ui);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(380.0, 0.0, ui);
+    assert_eq!(set_widgets(ui), vec![
+        (0, [-400.0, -105.0]),
+        (1, [-95.0, 240.0]),
+        (2, [250.0, 400.0]),
+    ]);
+    release_mouse_button(MouseButton::Left, ui);
+    set_widgets(ui);
+
+    // Double-clicking a handle collapses the adjacent collapsible pane, preserving the ratio of
+    // the others, and double-clicking it again restores it.
+    move_mouse_to_abs_coordinates(-100.0, 0.0, ui);
+    set_widgets(ui);
+    left_click_mouse(ui);
+    left_click_mouse(ui);
+    let collapsed = set_widgets(ui);
+    assert_eq!(collapsed.iter().map(|&(i, _)| i).collect::<Vec<_>>(), vec![1, 2]);
+    let (pane_1, pane_2) = (collapsed[0].1, collapsed[1].1);
+    assert_eq!((pane_1[0], pane_2[1]), (-390.0, 400.0));
+    let ratio = (pane_1[1] - pane_1[0]) / (pane_2[1] - pane_2[0]);
+    assert!((ratio - 335.0 / 150.0).abs() < 1e-9);
+
+    move_mouse_to_abs_coordinates(-395.0, 0.0, ui);
+    set_widgets(ui);
+    left_click_mouse(ui);
+    left_click_mouse(ui);
+    assert_eq!(set_widgets(ui), vec![
+        (0, [-400.0, -105.0]),
+        (1, [-95.0, 240.0]),
+        (2, [250.0, 400.0]),
+    ]);
+}
+
+#[test]
+fn dock_should_move_dragged_panels_between_groups_and_windows() {
+    use position::Rect;
+    #[cfg(feature = "serde")]
+    use serde_json;
+    use widget::dock::{Dock, Group, Layout, Node, Window};
+
+    let ui = &mut windowless_ui();
+    let dock = ui.widget_id_generator().next();
+    let mut layout = Layout::docked(Node::row(0.5, Node::group(&["A", "B"]), Node::group(&["C"])));
+
+    // Set the content of each visible panel, then apply the actions produced by the dock.
+    let set_widgets = |ui: &mut Ui, layout: &mut Layout| -> Vec<(String, Rect)> {
+        let (actions, contents) = {
+            let ui = &mut ui.set_widgets();
+            let window = ui.window;
+            let panels = Dock::new(layout)
+                .bar_thickness(20.0)
+                .wh_of(window)
+                .middle_of(window)
+                .set(dock, ui);
+            let actions = panels.actions().to_vec();
+            let contents: Vec<_> = panels.map(|element| {
+                let (panel, id) = (element.panel.to_owned(), element.widget_id);
+                element.set(widget::Rectangle::fill([0.0, 0.0]), ui);
+                (panel, id)
+            }).collect();
+            (actions, contents)
+        };
+        for action in &actions {
+            layout.apply(action);
+        }
+        contents.into_iter().map(|(panel, id)| (panel, ui.rect_of(id).unwrap())).collect()
+    };
+    let drag = |ui: &mut Ui, layout: &mut Layout, from: Point, to: Point| {
+        move_mouse_to_abs_coordinates(from[0], from[1], ui);
+        set_widgets(ui, layout);
+        press_mouse_button(MouseButton::Left, ui);
+        move_mouse_to_abs_coordinates(from[0] + 10.0, from[1], ui);
+        set_widgets(ui, layout);
+        move_mouse_to_abs_coordinates(to[0], to[1], ui);
+        set_widgets(ui, layout);
+        release_mouse_button(MouseButton::Left, ui);
+        set_widgets(ui, layout)
+    };
+    assert_eq!(set_widgets(ui, &mut layout), vec![
+        ("A".to_owned(), Rect::from_corners([-400.0, -300.0], [0.0, 280.0])),
+        ("C".to_owned(), Rect::from_corners([0.0, -300.0], [400.0, 280.0])),
+    ]);
+
+    // Dropping the tab of `B` onto the right docking guide of `C` splits their group.
+    drag(ui, &mut layout, [-100.0, 290.0], [234.0, -10.0]);
+    assert_eq!(set_widgets(ui, &mut layout), vec![
+        ("A".to_owned(), Rect::from_corners([-400.0, -300.0], [0.0, 280.0])),
+        ("C".to_owned(), Rect::from_corners([0.0, -300.0], [200.0, 280.0])),
+        ("B".to_owned(), Rect::from_corners([200.0, -300.0], [400.0, 280.0])),
+    ]);
+
+    // Dropping the tab of `C` away from the guides moves it into a floating window, kept within
+    // the area of the dock.
+    drag(ui, &mut layout, [100.0, 290.0], [-300.0, 100.0]);
+    let title_h = widget::title_bar::calc_height(12);
+    assert_eq!(set_widgets(ui, &mut layout), vec![
+        ("A".to_owned(), Rect::from_corners([-400.0, -300.0], [0.0, 280.0])),
+        ("B".to_owned(), Rect::from_corners([0.0, -300.0], [400.0, 280.0])),
+        ("C".to_owned(), Rect::from_corners([-400.0, 10.0], [-160.0, 170.0 - title_h])),
+    ]);
+
+    // The window is moved by dragging its title bar.
+    move_mouse_to_abs_coordinates(-300.0, 190.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-250.0, 170.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    release_mouse_button(MouseButton::Left, ui);
+    assert_eq!(layout.windows[0].xy, [-230.0, 80.0]);
+
+    // Pressing escape during the drag returns the window to where the drag began.
+    set_widgets(ui, &mut layout);
+    move_mouse_to_abs_coordinates(-200.0, 170.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-130.0, 140.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    move_mouse_to_abs_coordinates(-100.0, 70.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    assert_eq!(layout.windows[0].xy, [-130.0, -20.0]);
+    ui.handle_event(Input::Press(Button::Keyboard(Key::Escape)));
+    set_widgets(ui, &mut layout);
+    assert_eq!(layout.windows[0].xy, [-230.0, 80.0]);
+    release_mouse_button(MouseButton::Left, ui);
+
+    // Dragging it beyond the edge of the dock leaves it at the edge.
+    set_widgets(ui, &mut layout);
+    move_mouse_to_abs_coordinates(-200.0, 170.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    press_mouse_button(MouseButton::Left, ui);
+    move_mouse_to_abs_coordinates(-350.0, 170.0 - title_h / 2.0, ui);
+    set_widgets(ui, &mut layout);
+    release_mouse_button(MouseButton::Left, ui);
+
+    // The resulting layout may be saved and restored.
+    let expected = Layout {
+        root: Some(Node::row(0.5, Node::group(&["A"]), Node::group(&["B"]))),
+        windows: vec![Window { xy: [-280.0, 80.0], dim: [240.0, 180.0], group: Group::new(&["C"]) }],
+    };
+    assert_eq!(layout, expected);
+
+    // The layout may be saved and restored.
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&layout).unwrap();
+        assert_eq!(serde_json::from_str::<Layout>(&json).unwrap(), expected);
+    }
+}
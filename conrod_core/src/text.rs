@@ -1,6 +1,6 @@
 //! Text layout logic.
 
-use {FontSize, Scalar};
+use {Dimensions, FontSize, Scalar};
 use std;
 
 // Re-export all relevant rusttype types here.
@@ -43,6 +43,26 @@ pub enum Justify {
     // TODO: Full,
 }
 
+/// The way in which glyphs are rasterised into a `GlyphCache` by a backend.
+///
+/// Backends default to `Coverage`. The `sdf` mode may be opted into via the backend's renderer
+/// constructor.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GlyphCacheMode {
+    /// Each glyph is cached as an anti-aliased coverage bitmap at the exact scale (font size and
+    /// DPI) at which it is drawn.
+    ///
+    /// This produces the crispest text at a fixed size, however every new scale requires the
+    /// glyph to be rasterised and uploaded again.
+    Coverage,
+    /// Each glyph is rasterised once at the reference `sdf::Settings::scale` and cached as a
+    /// signed distance field which may be sampled at any scale.
+    ///
+    /// This is useful for text that is frequently zoomed, e.g. by a `Camera`. The fragment shader
+    /// must reconstruct the glyph edge by thresholding the sampled distance at `0.5`.
+    Sdf(sdf::Settings),
+}
+
 
 /// Determine the total height of a block of text with the given number of lines, font size and
 /// `line_spacing` (the space that separates each line of text).
@@ -54,6 +74,16 @@ pub fn height(num_lines: usize, font_size: FontSize, line_spacing: Scalar) -> Sc
     }
 }
 
+/// Determine the width of the widest line and the total height of the given `text` (split at each
+/// `\n`) when laid out with the given font, font size and `line_spacing`.
+pub fn dimensions(text: &str, font: &Font, font_size: FontSize, line_spacing: Scalar) -> Dimensions {
+    let w = text.lines()
+        .map(|line| line::width(line, font, font_size))
+        .fold(0.0, ::utils::partial_max);
+    let h = height(text.lines().count(), font_size, line_spacing);
+    [w, h]
+}
+
 
 /// Produce an iterator yielding each line within the given `text` as a new `&str`, where the
 /// start and end indices into each line are provided by the given iterator.
@@ -67,6 +97,105 @@ pub fn lines<I>(text: &str, ranges: I) -> Lines<I>
 }
 
 
+impl GlyphCacheMode {
+
+    /// The signed distance field mode with the default `sdf::Settings`.
+    pub fn sdf() -> Self {
+        GlyphCacheMode::Sdf(sdf::Settings::default())
+    }
+
+    /// A builder for a `GlyphCache` suited to this mode with the given dimensions.
+    pub fn cache_builder(&self, width: u32, height: u32) -> rt::gpu_cache::CacheBuilder {
+        const SCALE_TOLERANCE: f32 = 0.1;
+        const POSITION_TOLERANCE: f32 = 0.1;
+        let builder = GlyphCache::builder()
+            .dimensions(width, height)
+            .scale_tolerance(SCALE_TOLERANCE)
+            .position_tolerance(POSITION_TOLERANCE);
+        match *self {
+            GlyphCacheMode::Coverage => builder,
+            // The cache may only pad each glyph by a single texel. This stops linear sampling at
+            // the edge of a glyph from reaching its neighbours, while `glyph_data` and `rect_for`
+            // fit the full `sdf::padding` for the spread within each padded slot.
+            GlyphCacheMode::Sdf(_) => builder.pad_glyphs(true),
+        }
+    }
+
+    /// Queue the given glyph to be cached within the given `GlyphCache`.
+    ///
+    /// In `Sdf` mode the glyph is first normalised to the reference scale and origin so that a
+    /// single cached glyph may serve all scales and positions.
+    pub fn queue_glyph<'a>(&self,
+                           cache: &mut GlyphCache<'a>,
+                           font_id: usize,
+                           glyph: &::rusttype::PositionedGlyph<'a>)
+    {
+        match *self {
+            GlyphCacheMode::Coverage => cache.queue_glyph(font_id, glyph.clone()),
+            GlyphCacheMode::Sdf(settings) =>
+                cache.queue_glyph(font_id, sdf::normalize_glyph(glyph, settings.scale)),
+        }
+    }
+
+    /// Convert the coverage `data` produced by `GlyphCache::cache_queued` for a glyph of the given
+    /// dimensions into the data that should be uploaded to the cache texture.
+    ///
+    /// In `Coverage` mode the data is returned as is.
+    pub fn glyph_data<'d>(&self, data: &'d [u8], width: u32, height: u32) -> std::borrow::Cow<'d, [u8]> {
+        match *self {
+            GlyphCacheMode::Coverage => std::borrow::Cow::Borrowed(data),
+            GlyphCacheMode::Sdf(settings) => {
+                let (w, h) = (width as usize, height as usize);
+                let sdf = sdf::padded_distance_field(data, w, h, settings.spread);
+                std::borrow::Cow::Owned(sdf)
+            },
+        }
+    }
+
+    /// Produces the texture coordinates of the given glyph within the cache texture along with
+    /// the pixel-space rectangle at which the glyph should be drawn.
+    ///
+    /// The glyph must have been queued via `queue_glyph` using this same mode.
+    pub fn rect_for<'a>(&self,
+                        cache: &GlyphCache<'a>,
+                        font_id: usize,
+                        glyph: &::rusttype::PositionedGlyph<'a>)
+        -> Result<Option<(rt::Rect<f32>, rt::Rect<f32>)>, rt::gpu_cache::CacheReadErr>
+    {
+        match *self {
+            GlyphCacheMode::Coverage => {
+                let rects = cache.rect_for(font_id, glyph)?;
+                Ok(rects.map(|(uv_rect, screen_rect)| {
+                    let screen_rect = rt::Rect {
+                        min: rt::point(screen_rect.min.x as f32, screen_rect.min.y as f32),
+                        max: rt::point(screen_rect.max.x as f32, screen_rect.max.y as f32),
+                    };
+                    (uv_rect, screen_rect)
+                }))
+            },
+            GlyphCacheMode::Sdf(settings) => {
+                let normalized = sdf::normalize_glyph(glyph, settings.scale);
+                let rects = cache.rect_for(font_id, &normalized)?;
+                let dimensions = cache.dimensions();
+                Ok(rects.map(|(uv_rect, normalized_rect)| {
+                    let (uv_rect, normalized_rect) =
+                        sdf::padded_rects(uv_rect, normalized_rect, dimensions, settings.spread);
+                    let screen_rect = sdf::screen_rect(glyph, settings.scale, normalized_rect);
+                    (uv_rect, screen_rect)
+                }))
+            },
+        }
+    }
+
+}
+
+impl Default for GlyphCacheMode {
+    fn default() -> Self {
+        GlyphCacheMode::Coverage
+    }
+}
+
+
 /// Converts the given font size in "points" to its font size in pixels.
 pub fn pt_to_px(font_size_in_points: FontSize) -> f32 {
     (font_size_in_points * 4) as f32 / 3.0
@@ -108,6 +237,7 @@ pub mod font {
     pub struct Map {
         next_index: usize,
         map: fnv::FnvHashMap<Id, super::Font>,
+        bitmap_sources: fnv::FnvHashMap<Id, std::sync::Arc<dyn super::bitmap::Source>>,
     }
 
     /// An iterator yielding an `Id` for each new `rusttype::Font` inserted into the `Map` via the
@@ -147,6 +277,7 @@ pub mod font {
             Map {
                 next_index: 0,
                 map: fnv::FnvHashMap::default(),
+                bitmap_sources: fnv::FnvHashMap::default(),
             }
         }
 
@@ -194,6 +325,21 @@ pub mod font {
             Ids { keys: self.map.keys() }
         }
 
+        /// Provide color bitmaps for the glyphs of the font with the given `Id`.
+        ///
+        /// This is necessary for fonts whose glyphs are stored as embedded bitmaps (e.g. color
+        /// emoji fonts) as RustType can only rasterise glyph outlines. See the `bitmap` module.
+        pub fn set_bitmap_source<S>(&mut self, id: Id, source: S)
+            where S: super::bitmap::Source + 'static,
+        {
+            self.bitmap_sources.insert(id, std::sync::Arc::new(source));
+        }
+
+        /// The source of color bitmaps for the font with the given `Id`, if there is one.
+        pub fn bitmap_source(&self, id: Id) -> Option<&std::sync::Arc<dyn super::bitmap::Source>> {
+            self.bitmap_sources.get(&id)
+        }
+
     }
 
 
@@ -1490,3 +1636,500 @@ pub mod line {
     }
 
 }
+
+
+/// Logic for caching glyphs as signed distance fields.
+///
+/// See `GlyphCacheMode::Sdf`.
+pub mod sdf {
+    use rusttype;
+    use super::rt;
+
+    /// The default scale in pixels at which glyphs are rasterised before conversion.
+    pub const DEFAULT_SCALE: f32 = 48.0;
+    /// The default distance in pixels (at the reference scale) covered by the distance field on
+    /// either side of a glyph's edge.
+    pub const DEFAULT_SPREAD: f32 = 4.0;
+
+    /// Parameters for the signed distance field glyph cache mode.
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Settings {
+        /// The uniform scale in pixels at which each glyph is rasterised.
+        ///
+        /// Larger scales retain more detail at the cost of more space within the cache texture.
+        pub scale: f32,
+        /// The distance in pixels (at the reference `scale`) covered by the field on either side
+        /// of a glyph's edge.
+        ///
+        /// Distances beyond the `spread` are clamped to `0` (outside) or `255` (inside).
+        ///
+        /// The field extends `padding(spread)` pixels beyond each side of the glyph's pixel
+        /// bounding box, so that outlines and glows up to the `spread` wide are not cut off.
+        pub spread: f32,
+    }
+
+    impl Default for Settings {
+        fn default() -> Self {
+            Settings {
+                scale: DEFAULT_SCALE,
+                spread: DEFAULT_SPREAD,
+            }
+        }
+    }
+
+    /// The number of pixels (at the reference `scale`) by which the field extends beyond each
+    /// side of a glyph's pixel bounding box in order to cover the given `spread`.
+    pub fn padding(spread: f32) -> u32 {
+        spread.max(1.0).ceil() as u32
+    }
+
+    /// Produce a copy of the given glyph rasterised at the given uniform reference `scale` and
+    /// positioned at the origin.
+    pub fn normalize_glyph<'a>(glyph: &rusttype::PositionedGlyph<'a>,
+                               scale: f32) -> rusttype::PositionedGlyph<'a>
+    {
+        glyph.unpositioned()
+            .unscaled()
+            .clone()
+            .scaled(rusttype::Scale::uniform(scale))
+            .positioned(rt::point(0.0, 0.0))
+    }
+
+    /// Determine the pixel-space rectangle at which the given glyph should be drawn given the
+    /// `normalized_rect` produced by the cache for its normalised counterpart.
+    pub fn screen_rect(glyph: &rusttype::PositionedGlyph,
+                       scale: f32,
+                       normalized_rect: rt::Rect<i32>) -> rt::Rect<f32>
+    {
+        let glyph_scale = glyph.scale();
+        let (sx, sy) = (glyph_scale.x / scale, glyph_scale.y / scale);
+        let position = glyph.position();
+        let point = |p: rt::Point<i32>| rt::point(position.x + p.x as f32 * sx,
+                                                  position.y + p.y as f32 * sy);
+        rt::Rect {
+            min: point(normalized_rect.min),
+            max: point(normalized_rect.max),
+        }
+    }
+
+    /// Convert the given row-major glyph coverage bitmap into a signed distance field of the same
+    /// dimensions.
+    ///
+    /// A value of `128` lies on the glyph's edge. Greater values lie inside the glyph and lesser
+    /// values outside, falling off linearly over the given `spread` in pixels.
+    pub fn distance_field(coverage: &[u8], width: usize, height: usize, spread: f32) -> Vec<u8> {
+        let spread = spread.max(1.0);
+        let radius = spread.ceil() as isize;
+        let is_inside = |x: isize, y: isize| {
+            x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height
+                && coverage[y as usize * width + x as usize] >= 128
+        };
+
+        let mut field = Vec::with_capacity(width * height);
+        for y in 0..height as isize {
+            for x in 0..width as isize {
+                let inside = is_inside(x, y);
+
+                // Find the distance to the nearest pixel on the other side of the edge, if there
+                // is one within the spread.
+                let mut min_dist_sq = f32::INFINITY;
+                for dy in -radius..radius + 1 {
+                    for dx in -radius..radius + 1 {
+                        if is_inside(x + dx, y + dy) != inside {
+                            let dist_sq = (dx * dx + dy * dy) as f32;
+                            if dist_sq < min_dist_sq {
+                                min_dist_sq = dist_sq;
+                            }
+                        }
+                    }
+                }
+                let dist = min_dist_sq.sqrt();
+
+                // Pixels adjacent to the edge use their coverage for sub-pixel accuracy.
+                let signed_dist = if dist <= 1.0 {
+                    coverage[y as usize * width + x as usize] as f32 / 255.0 - 0.5
+                } else if inside {
+                    dist - 0.5
+                } else {
+                    0.5 - dist
+                };
+
+                let value = (0.5 + signed_dist / (2.0 * spread)).max(0.0).min(1.0);
+                field.push((value * 255.0).round() as u8);
+            }
+        }
+        field
+    }
+
+    /// Convert the row-major coverage bitmap of a glyph's slot within the `GlyphCache` into a
+    /// signed distance field of the same dimensions that extends `padding(spread)` pixels beyond
+    /// each side of the glyph.
+    ///
+    /// The `GlyphCache` only pads each glyph by a single texel, so the field is produced for the
+    /// glyph surrounded by the full padding and then resampled to fit the slot. The slot then
+    /// covers the region described by `padded_rects`.
+    pub fn padded_distance_field(coverage: &[u8],
+                                 width: usize,
+                                 height: usize,
+                                 spread: f32) -> Vec<u8>
+    {
+        let pad = padding(spread) as usize;
+
+        // Surround the glyph's coverage, without the texel of padding added by the cache, by the
+        // full padding.
+        let (glyph_w, glyph_h) = (width.saturating_sub(2), height.saturating_sub(2));
+        let (field_w, field_h) = (glyph_w + pad * 2, glyph_h + pad * 2);
+        let mut padded = vec![0; field_w * field_h];
+        for y in 0..glyph_h {
+            let src = (y + 1) * width + 1;
+            let dst = (y + pad) * field_w + pad;
+            padded[dst..dst + glyph_w].copy_from_slice(&coverage[src..src + glyph_w]);
+        }
+        let field = distance_field(&padded, field_w, field_h, spread);
+
+        // Bilinearly sample the field at the centre of each texel within the slot.
+        let sample = |x: f32, y: f32| {
+            let x = x.max(0.0).min((field_w - 1) as f32);
+            let y = y.max(0.0).min((field_h - 1) as f32);
+            let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+            let (x1, y1) = ((x0 + 1).min(field_w - 1), (y0 + 1).min(field_h - 1));
+            let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+            let at = |x: usize, y: usize| field[y * field_w + x] as f32;
+            let top = at(x0, y0) * (1.0 - tx) + at(x1, y0) * tx;
+            let bottom = at(x0, y1) * (1.0 - tx) + at(x1, y1) * tx;
+            top * (1.0 - ty) + bottom * ty
+        };
+        let sx = field_w as f32 / width as f32;
+        let sy = field_h as f32 / height as f32;
+        let mut resampled = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let value = sample((x as f32 + 0.5) * sx - 0.5, (y as f32 + 0.5) * sy - 0.5);
+                resampled.push(value.round() as u8);
+            }
+        }
+        resampled
+    }
+
+    /// Expand the texture coordinates and the normalised pixel-space rectangle produced by the
+    /// `GlyphCache` for a glyph to cover its whole slot, as filled by `padded_distance_field`.
+    ///
+    /// The texture coordinates grow by the single texel of padding added by the cache, while the
+    /// pixel-space rectangle grows by the `padding` for the given `spread`.
+    pub fn padded_rects(uv_rect: rt::Rect<f32>,
+                        normalized_rect: rt::Rect<i32>,
+                        cache_dimensions: (u32, u32),
+                        spread: f32) -> (rt::Rect<f32>, rt::Rect<i32>)
+    {
+        let (texel_w, texel_h) = (1.0 / cache_dimensions.0 as f32, 1.0 / cache_dimensions.1 as f32);
+        let uv_rect = rt::Rect {
+            min: rt::point(uv_rect.min.x - texel_w, uv_rect.min.y - texel_h),
+            max: rt::point(uv_rect.max.x + texel_w, uv_rect.max.y + texel_h),
+        };
+        let pad = padding(spread) as i32;
+        let normalized_rect = rt::Rect {
+            min: rt::point(normalized_rect.min.x - pad, normalized_rect.min.y - pad),
+            max: rt::point(normalized_rect.max.x + pad, normalized_rect.max.y + pad),
+        };
+        (uv_rect, normalized_rect)
+    }
+
+}
+
+
+/// Support for color glyphs that are stored within fonts as RGBA bitmaps rather than outlines.
+///
+/// RustType can only rasterise glyph outlines, so glyphs that are stored as embedded bitmaps (e.g.
+/// within the `CBDT` or `sbix` tables of color emoji fonts) would otherwise appear empty. A
+/// `Source` of bitmaps may be associated with a font via `font::Map::set_bitmap_source`. Backends
+/// cache these bitmaps within an RGBA `Atlas` and draw them without tinting.
+pub mod bitmap {
+    use fnv;
+    use std;
+    use super::{GlyphId, rt};
+
+    /// An RGBA bitmap for a single glyph.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Bitmap {
+        /// The width of the bitmap in pixels.
+        pub width: u32,
+        /// The height of the bitmap in pixels.
+        pub height: u32,
+        /// Row-major, non-premultiplied RGBA pixel data, four bytes per pixel.
+        pub rgba: Vec<u8>,
+        /// The number of pixels per em at which the bitmap was designed.
+        ///
+        /// The bitmap is scaled by the ratio between the glyph's `ppem` and this `ppem` when drawn.
+        pub ppem: f32,
+        /// The offset from the glyph's origin on the baseline to the top-left corner of the bitmap
+        /// in pixels at `ppem`, where the *y* axis points down.
+        pub offset: [f32; 2],
+    }
+
+    /// A source of bitmaps for the glyphs of a single font.
+    ///
+    /// This is implemented for all functions of the form `Fn(GlyphId, f32) -> Option<Bitmap>`.
+    pub trait Source: Send + Sync {
+        /// Produce the bitmap for the glyph with the given `id` that best suits the given pixels
+        /// per em.
+        ///
+        /// Returns `None` if the glyph should be rasterised from its outline as usual.
+        fn bitmap(&self, id: GlyphId, ppem: f32) -> Option<Bitmap>;
+    }
+
+    /// A CPU-side packing of glyph `Bitmap`s into a single RGBA texture.
+    ///
+    /// Mirrors the `queue_glyph`, `cache_queued` and `rect_for` API of the `GlyphCache`.
+    #[derive(Debug)]
+    pub struct Atlas {
+        width: u32,
+        height: u32,
+        rows: Vec<Row>,
+        strikes: fnv::FnvHashMap<StrikeKey, Option<Key>>,
+        glyphs: fnv::FnvHashMap<Key, Cached>,
+        queue: Vec<Key>,
+    }
+
+    // A glyph drawn at some number of whole pixels per em.
+    type StrikeKey = (usize, GlyphId, u32);
+
+    // A glyph's bitmap, identified by the bits of the `ppem` at which it was designed so that
+    // each strike of a glyph is cached separately while the sizes that share a strike share it.
+    type Key = (usize, GlyphId, u32);
+
+    // A horizontal shelf of glyphs within the atlas.
+    #[derive(Debug)]
+    struct Row {
+        top: u32,
+        height: u32,
+        width: u32,
+    }
+
+    // A bitmap glyph along with its location within the atlas if it is currently packed.
+    #[derive(Debug)]
+    struct Cached {
+        bitmap: Bitmap,
+        rect: Option<rt::Rect<u32>>,
+    }
+
+    impl<F> Source for F
+        where F: Fn(GlyphId, f32) -> Option<Bitmap> + Send + Sync,
+    {
+        fn bitmap(&self, id: GlyphId, ppem: f32) -> Option<Bitmap> {
+            (*self)(id, ppem)
+        }
+    }
+
+    impl std::fmt::Debug for dyn Source {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "Source")
+        }
+    }
+
+    impl Atlas {
+
+        /// Construct an empty `Atlas` for a texture of the given dimensions.
+        pub fn new(width: u32, height: u32) -> Self {
+            Atlas {
+                width: width,
+                height: height,
+                rows: Vec::new(),
+                strikes: fnv::FnvHashMap::default(),
+                glyphs: fnv::FnvHashMap::default(),
+                queue: Vec::new(),
+            }
+        }
+
+        /// The dimensions of the atlas texture.
+        pub fn dimensions(&self) -> (u32, u32) {
+            (self.width, self.height)
+        }
+
+        /// Queue the given glyph to be cached if the `source` provides a bitmap for it.
+        ///
+        /// Returns `true` if the glyph is a bitmap glyph, in which case it should be drawn from the
+        /// atlas rather than queued within the `GlyphCache`.
+        ///
+        /// The `source` is only queried the first time a glyph is seen at each whole number of
+        /// pixels per em. Each distinct bitmap `ppem` (i.e. strike) that it returns for the glyph
+        /// is cached separately.
+        pub fn queue_glyph(&mut self,
+                           font_id: usize,
+                           glyph: &super::PositionedGlyph,
+                           source: &dyn Source) -> bool
+        {
+            let Atlas { ref mut strikes, ref mut glyphs, ref mut queue, .. } = *self;
+            let glyph_ppem = ppem(glyph);
+            let strike_key = (font_id, glyph.id(), glyph_ppem.round() as u32);
+            let maybe_key = *strikes.entry(strike_key).or_insert_with(|| {
+                source.bitmap(glyph.id(), glyph_ppem).map(|bitmap| {
+                    let key = (font_id, glyph.id(), bitmap.ppem.to_bits());
+                    glyphs.entry(key).or_insert(Cached { bitmap: bitmap, rect: None });
+                    key
+                })
+            });
+            match maybe_key {
+                Some(key) => {
+                    if !queue.contains(&key) {
+                        queue.push(key);
+                    }
+                    true
+                },
+                None => false,
+            }
+        }
+
+        /// Pack all queued glyphs into the atlas, calling `uploader` with the region of the
+        /// texture and the RGBA data for each glyph that was not already present.
+        ///
+        /// If there is not enough room, all glyphs that are not queued are evicted.
+        pub fn cache_queued<F>(&mut self, mut uploader: F) -> Result<(), rt::gpu_cache::CacheWriteErr>
+            where F: FnMut(rt::Rect<u32>, &[u8]),
+        {
+            let Atlas { width, height, ref mut rows, ref mut glyphs, ref mut queue, .. } = *self;
+
+            // Check that every queued glyph could fit within the atlas at all.
+            for key in queue.iter() {
+                if let Some(cached) = glyphs.get(key) {
+                    if cached.bitmap.width > width || cached.bitmap.height > height {
+                        queue.clear();
+                        return Err(rt::gpu_cache::CacheWriteErr::GlyphTooLarge);
+                    }
+                }
+            }
+
+            // Find a place within the atlas for each queued glyph that does not yet have one.
+            //
+            // Returns `None` if they do not all fit.
+            let pack_queue = |rows: &mut Vec<Row>, glyphs: &fnv::FnvHashMap<Key, Cached>| {
+                let mut packed = Vec::new();
+                for key in queue.iter() {
+                    if let Some(cached) = glyphs.get(key) {
+                        if cached.rect.is_some() {
+                            continue;
+                        }
+                        let (w, h) = (cached.bitmap.width, cached.bitmap.height);
+                        let rect = allocate(rows, width, height, w, h)?;
+                        packed.push((*key, rect));
+                    }
+                }
+                Some(packed)
+            };
+
+            let packed = match pack_queue(rows, glyphs) {
+                Some(packed) => packed,
+                None => {
+                    // Evict everything and try again with only the queued glyphs.
+                    rows.clear();
+                    for cached in glyphs.values_mut() {
+                        cached.rect = None;
+                    }
+                    match pack_queue(rows, glyphs) {
+                        Some(packed) => packed,
+                        None => {
+                            queue.clear();
+                            return Err(rt::gpu_cache::CacheWriteErr::NoRoomForWholeQueue);
+                        },
+                    }
+                },
+            };
+
+            // Only upload the glyphs once they are known to fit.
+            for (key, rect) in packed {
+                if let Some(cached) = glyphs.get_mut(&key) {
+                    uploader(rect, &cached.bitmap.rgba);
+                    cached.rect = Some(rect);
+                }
+            }
+
+            queue.clear();
+            Ok(())
+        }
+
+        /// Produces the texture coordinates of the given bitmap glyph within the atlas along with
+        /// the pixel-space rectangle at which it should be drawn.
+        ///
+        /// Returns `None` if the glyph is not a bitmap glyph or is not currently cached.
+        pub fn rect_for(&self,
+                        font_id: usize,
+                        glyph: &super::PositionedGlyph) -> Option<(rt::Rect<f32>, rt::Rect<f32>)>
+        {
+            let strike_key = (font_id, glyph.id(), ppem(glyph).round() as u32);
+            let cached = match self.strikes.get(&strike_key) {
+                Some(&Some(ref key)) => self.glyphs.get(key)?,
+                _ => return None,
+            };
+            cached.rect.map(|rect| {
+                let (w, h) = (self.width as f32, self.height as f32);
+                let uv_rect = rt::Rect {
+                    min: rt::point(rect.min.x as f32 / w, rect.min.y as f32 / h),
+                    max: rt::point(rect.max.x as f32 / w, rect.max.y as f32 / h),
+                };
+                let bitmap = &cached.bitmap;
+                let scale = ppem(glyph) / bitmap.ppem;
+                let position = glyph.position();
+                let min = rt::point(position.x + bitmap.offset[0] * scale,
+                                    position.y + bitmap.offset[1] * scale);
+                let max = rt::point(min.x + bitmap.width as f32 * scale,
+                                    min.y + bitmap.height as f32 * scale);
+                (uv_rect, rt::Rect { min: min, max: max })
+            })
+        }
+
+        /// Remove all glyphs from the atlas.
+        pub fn clear(&mut self) {
+            self.rows.clear();
+            self.strikes.clear();
+            self.glyphs.clear();
+            self.queue.clear();
+        }
+
+    }
+
+    /// The number of pixels per em at which the given glyph is drawn.
+    ///
+    /// RustType scales glyphs so that `scale.y` is the distance in pixels from the font's descent
+    /// to its ascent rather than the size of its em square.
+    pub fn ppem(glyph: &super::PositionedGlyph) -> f32 {
+        let scale = glyph.scale().y;
+        let font = match glyph.font() {
+            Some(font) => font,
+            None => return scale,
+        };
+        let v_metrics = font.v_metrics_unscaled();
+        let height = v_metrics.ascent - v_metrics.descent;
+        if height > 0.0 {
+            scale * font.units_per_em() as f32 / height
+        } else {
+            scale
+        }
+    }
+
+    // Find room for a `w` x `h` glyph within the shelves, adding a new shelf if necessary.
+    fn allocate(rows: &mut Vec<Row>,
+                atlas_w: u32,
+                atlas_h: u32,
+                w: u32,
+                h: u32) -> Option<rt::Rect<u32>>
+    {
+        let rect = |x, y| rt::Rect { min: rt::point(x, y), max: rt::point(x + w, y + h) };
+
+        // Use the first existing row that is tall enough and has enough room left.
+        for row in rows.iter_mut() {
+            if h <= row.height && row.width + w <= atlas_w {
+                let x = row.width;
+                row.width += w;
+                return Some(rect(x, row.top));
+            }
+        }
+
+        // Otherwise start a new row beneath the last.
+        let top = rows.last().map(|row| row.top + row.height).unwrap_or(0);
+        if top + h > atlas_h || w > atlas_w {
+            return None;
+        }
+        rows.push(Row { top: top, height: h, width: w });
+        Some(rect(0, top))
+    }
+
+}
@@ -3,7 +3,7 @@
 //!
 
 use Scalar;
-use color::{Color, BLACK, WHITE};
+use color::{Color, BLACK, LIGHT_BLUE, WHITE};
 use position::{Align, Direction, Padding, Position, Relative};
 use fnv;
 use std;
@@ -17,6 +17,10 @@ pub type StyleMap = fnv::FnvHashMap<std::any::TypeId, WidgetDefault>;
 
 
 /// A serializable collection of canvas and widget styling defaults.
+///
+/// New fields may be added to the `Theme` over time. When constructing a `Theme` from a struct
+/// literal, prefer filling the remaining fields with `..Theme::default()` so that such additions
+/// do not break your code.
 #[derive(Debug)]
 pub struct Theme {
     /// A name for the theme used for identification.
@@ -54,6 +58,15 @@ pub struct Theme {
     /// Once the `Duration` that separates two consecutive `Click`s is greater than this value, a
     /// `DoubleClick` event will no longer be generated.
     pub double_click_threshold: std::time::Duration,
+    /// The duration for which a touch must be held in place before a `LongPress` event is
+    /// generated.
+    pub long_press_duration: std::time::Duration,
+    /// The duration for which the mouse must rest over a widget before its tooltip is shown.
+    pub tooltip_delay: std::time::Duration,
+    /// The color of the ring drawn around a widget that received keyboard focus via `Tab`.
+    pub focus_ring_color: Color,
+    /// The thickness of the ring drawn around a widget that received keyboard focus via `Tab`.
+    pub focus_ring_thickness: Scalar,
 }
 
 /// The defaults for a specific widget.
@@ -105,6 +118,10 @@ impl Theme {
             widget_styling: fnv::FnvHashMap::default(),
             mouse_drag_threshold: 0.0,
             double_click_threshold: std::time::Duration::from_millis(500),
+            long_press_duration: std::time::Duration::from_millis(500),
+            tooltip_delay: std::time::Duration::from_millis(700),
+            focus_ring_color: LIGHT_BLUE,
+            focus_ring_thickness: 2.0,
         }
     }
 
@@ -1,3 +1,4 @@
+use clock::{self, Clock};
 use color::Color;
 use event;
 use graph::{self, Graph};
@@ -34,7 +35,11 @@ pub struct UiBuilder {
     ///
     /// If this field is `None` when `build` is called, these collections will be initialised with
     /// no pre-reserved size and will instead grow organically as needed.
-    pub maybe_widgets_capacity: Option<usize>
+    pub maybe_widgets_capacity: Option<usize>,
+    /// The source of time used to timestamp input, e.g. when detecting `DoubleClick`s.
+    ///
+    /// If this field is `None` when `build` is called, the `clock::System` clock will be used.
+    pub maybe_clock: Option<Box<dyn Clock>>,
 }
 
 /// `Ui` is the most important type within Conrod and is necessary for rendering and maintaining
@@ -55,6 +60,10 @@ pub struct Ui {
     global_input: input::Global,
     /// Manages all fonts that have been loaded by the user.
     pub fonts: text::font::Map,
+    /// All keyboard shortcuts that have been registered by the user.
+    pub shortcuts: input::shortcut::Registry,
+    /// The inputs used to move keyboard focus between widgets spatially.
+    pub navigation: input::navigation::Navigation,
     /// The Widget cache, storing state for all widgets.
     widget_graph: Graph,
     /// The widget::Id of the widget that was last updated/set.
@@ -86,6 +95,62 @@ pub struct Ui {
     pending_scroll_events: Vec<event::Ui>,
     /// Mouse cursor
     mouse_cursor: cursor::MouseCursor,
+    /// All focusable widgets that were updated during the last `Ui::set_widgets` stage, in the
+    /// order in which `Tab` visits them.
+    focus_chain: Vec<widget::Id>,
+    /// Whether the `Tab` text produced by a `Tab` press that moved keyboard focus is yet to be
+    /// received, in which case it is discarded rather than delivered to the focused widget.
+    is_tab_text_pending: bool,
+    /// The widget used to draw a ring around the widget capturing the keyboard.
+    focus_ring: widget::Id,
+    /// Whether or not keyboard capture was last moved via the focus chain, in which case the
+    /// `focus_ring` is drawn.
+    is_focus_ring_visible: bool,
+    /// Tooltip text registered via `UiCell::tooltip` during the current `set_widgets` stage.
+    tooltips: fnv::FnvHashMap<widget::Id, String>,
+    /// The widget used to display the tooltip of the widget under the mouse.
+    tooltip: widget::Id,
+    /// The widget whose tooltip was displayed during the last `set_widgets` stage, if any.
+    maybe_tooltip_target: Option<widget::Id>,
+    /// The last time at which the mouse moved or a button was pressed. The tooltip is only shown
+    /// once the `theme.tooltip_delay` has elapsed since this time.
+    tooltip_hover_start: std::time::Instant,
+    /// The mouse buttons that have produced a `Drag` since they were last pressed.
+    dragging_mouse_buttons: Vec<input::MouseButton>,
+    /// The drag and drop in progress, if any.
+    maybe_drag: Option<DragState>,
+    /// The drag most recently dropped onto an accepting widget along with the id of that widget,
+    /// available until the end of the following `set_widgets` stage.
+    maybe_dropped: Option<(widget::Id, input::drag_drop::Drag)>,
+    /// The widget used to display the `Preview` of the drag in progress.
+    drag_preview: widget::Id,
+    /// The widget that received the last `FileHover` while files are dragged over the window.
+    maybe_file_hover_widget: Option<widget::Id>,
+    /// The motion of kinetically scrolling widgets that are receiving scroll input or are still
+    /// moving with momentum.
+    kinetic_scrolls: fnv::FnvHashMap<widget::Id, KineticScroll>,
+    /// Widgets to be fit around their children during the `set_widgets` stage.
+    content_fits: Vec<ContentFit>,
+    /// The `content_fits` whose children may still be being set, innermost last.
+    pending_content_fits: Vec<ContentFit>,
+    /// Whether or not some widget was resized by `fit_widget_to_content` after its children were
+    /// positioned against its previous dimensions, requiring the widgets to be set again.
+    is_layout_pending: bool,
+    /// Layout constraints registered via `UiCell::constrain` during the current `set_widgets`
+    /// stage.
+    constraints: Vec<position::constraint::Constraint>,
+    /// The solved position of the constrained widget currently being set, used by `calc_xy`.
+    constraint_values: fnv::FnvHashMap<position::constraint::Variable, Scalar>,
+    /// The solver for the `constraints` of the current `set_widgets` stage, created once the first
+    /// constrained widget is set.
+    maybe_constraint_layout: Option<ConstraintLayout>,
+    /// The constraints that could not be satisfied during the last `set_widgets` stage.
+    rejected_constraints: Vec<(position::constraint::Constraint, position::constraint::Error)>,
+    /// Records every `Input` passed to `handle_event` while recording.
+    #[cfg(feature = "serde")]
+    maybe_recorder: Option<input::record::Recorder>,
+    /// The source of time used to timestamp input.
+    clock: Box<dyn Clock>,
 
     // TODO: Remove the following fields as they should now be handled by `input::Global`.
 
@@ -112,6 +177,59 @@ pub struct UiCell<'a> {
 }
 
 
+/// A drag and drop in progress.
+#[derive(Debug)]
+struct DragState {
+    drag: input::drag_drop::Drag,
+    /// The position of the mouse when the drag began.
+    origin: Point,
+    /// The widgets that accepted the drag during the last `set_widgets` stage.
+    acceptors: Vec<widget::Id>,
+}
+
+/// A widget to be fit around the bounding box of its children.
+#[derive(Copy, Clone, Debug)]
+struct ContentFit {
+    id: widget::Id,
+    /// The padding for each axis that is sized to the widget's content.
+    pads: [Option<Scalar>; 2],
+    /// The minimum and maximum length of the widget along each axis.
+    bounds: [(Option<Scalar>, Option<Scalar>); 2],
+}
+
+/// The constraint solver shared by every constrained widget set during a `set_widgets` stage.
+#[derive(Debug)]
+struct ConstraintLayout {
+    solver: position::constraint::Solver,
+    /// The number of the `Ui`'s constraints that have been added to the `solver`.
+    num_constraints: usize,
+    /// Every widget referred to by the constraints added to the `solver`.
+    ids: Vec<widget::Id>,
+    /// The widgets whose attributes are edit variables, suggesting their preferred `Rect`.
+    edited: fnv::FnvHashSet<widget::Id>,
+    /// The widgets that have been set and are now fixed in place.
+    fixed: fnv::FnvHashSet<widget::Id>,
+}
+
+/// The motion of a widget that scrolls kinetically.
+#[derive(Debug)]
+struct KineticScroll {
+    /// The scroll input received within the last `KINETIC_VELOCITY_WINDOW_MS`, along with the
+    /// time at which each was received.
+    samples: Vec<(std::time::Instant, [Scalar; 2])>,
+    /// Whether or not scroll input was received since the last step.
+    has_input: bool,
+    /// The velocity in offset per second with which the widget continues to scroll.
+    velocity: [Scalar; 2],
+    /// The time at which the motion was last stepped.
+    last_step: std::time::Instant,
+}
+
+/// The duration over which the velocity of scroll input is measured when it is released into
+/// kinetic motion.
+const KINETIC_VELOCITY_WINDOW_MS: u64 = 100;
+
+
 /// Each time conrod is required to redraw the GUI, it must draw for at least the next three frames
 /// to ensure that, in the case that graphics buffers are being swapped, we have filled each
 /// buffer. Otherwise if we don't draw into each buffer, we will probably be subject to flickering.
@@ -127,7 +245,8 @@ impl UiBuilder {
         UiBuilder {
             window_dimensions: window_dimensions,
             maybe_theme: None,
-            maybe_widgets_capacity: None
+            maybe_widgets_capacity: None,
+            maybe_clock: None,
         }
     }
 
@@ -157,6 +276,19 @@ impl UiBuilder {
         self
     }
 
+    /// The source of time used to timestamp input, e.g. when detecting `DoubleClick`s.
+    ///
+    /// Giving a `clock::Manual` makes the timing of simulated input and replayed recordings
+    /// reproducible.
+    ///
+    /// If this field is `None` when `build` is called, the `clock::System` clock will be used.
+    pub fn clock<C>(mut self, value: C) -> Self
+        where C: Clock + 'static,
+    {
+        self.maybe_clock = Some(Box::new(value));
+        self
+    }
+
     /// Build **Ui** from the given builder
     pub fn build(self) -> Ui {
         Ui::new(self)
@@ -173,6 +305,7 @@ impl Ui {
             window_dimensions,
             maybe_widgets_capacity,
             maybe_theme,
+            maybe_clock,
         } = builder;
 
         let (mut widget_graph, depth_order, updated_widgets) =
@@ -186,11 +319,17 @@ impl Ui {
                         fnv::FnvBuildHasher::default())));
 
         let window = widget_graph.add_placeholder();
+        let focus_ring = widget_graph.add_placeholder();
+        let tooltip = widget_graph.add_placeholder();
+        let drag_preview = widget_graph.add_placeholder();
+        let clock = maybe_clock.unwrap_or_else(|| Box::new(clock::System));
         let prev_updated_widgets = updated_widgets.clone();
         Ui {
             widget_graph: widget_graph,
             theme: maybe_theme.unwrap_or_else(|| Theme::default()),
             fonts: text::font::Map::new(),
+            shortcuts: input::shortcut::Registry::new(),
+            navigation: input::navigation::Navigation::new(),
             window: window,
             win_w: window_dimensions[0],
             win_h: window_dimensions[1],
@@ -205,9 +344,38 @@ impl Ui {
             global_input: input::Global::new(),
             pending_scroll_events: Vec::new(),
             mouse_cursor: cursor::MouseCursor::Arrow,
+            focus_chain: Vec::new(),
+            is_tab_text_pending: false,
+            focus_ring: focus_ring,
+            is_focus_ring_visible: false,
+            tooltips: fnv::FnvHashMap::default(),
+            tooltip: tooltip,
+            maybe_tooltip_target: None,
+            tooltip_hover_start: clock.now(),
+            dragging_mouse_buttons: Vec::new(),
+            maybe_drag: None,
+            maybe_dropped: None,
+            drag_preview: drag_preview,
+            maybe_file_hover_widget: None,
+            kinetic_scrolls: fnv::FnvHashMap::default(),
+            content_fits: Vec::new(),
+            pending_content_fits: Vec::new(),
+            is_layout_pending: false,
+            constraints: Vec::new(),
+            constraint_values: fnv::FnvHashMap::default(),
+            maybe_constraint_layout: None,
+            rejected_constraints: Vec::new(),
+            #[cfg(feature = "serde")]
+            maybe_recorder: None,
+            clock: clock,
         }
     }
 
+    /// The source of time used by the `Ui` to timestamp input.
+    pub fn clock(&self) -> &dyn Clock {
+        &*self.clock
+    }
+
     /// Returns a `input::Widget` for the given widget
     pub fn widget_input(&self, widget: widget::Id) -> input::Widget {
         // If there's no rectangle for a given widget, then we use one with zero area.
@@ -330,6 +498,7 @@ impl Ui {
     ///
     /// Note: This function expects that `ui.global_input.current.mouse.xy` is up-to-date.
     fn track_widget_under_mouse_and_update_capturing(&mut self) {
+        let prev_widget_under_mouse = self.global_input.current.widget_under_mouse;
         self.global_input.current.widget_under_mouse =
             graph::algo::pick_widgets(&self.depth_order.indices,
                                       self.global_input.current.mouse.xy)
@@ -337,10 +506,20 @@ impl Ui {
                                             &self.depth_order.indices,
                                             &self.theme);
 
+        // Notify the widgets that the mouse has left and entered.
+        let widget_under_mouse = self.global_input.current.widget_under_mouse;
+        if widget_under_mouse != prev_widget_under_mouse {
+            if let Some(idx) = prev_widget_under_mouse {
+                self.global_input.push_event(event::Ui::MouseLeave(idx).into());
+            }
+            if let Some(idx) = widget_under_mouse {
+                self.global_input.push_event(event::Ui::MouseEnter(idx).into());
+            }
+        }
+
         // If MouseButton::Left is up and `widget_under_mouse` has changed, capture new widget
         // under mouse.
         if self.global_input.current.mouse.buttons.left().is_up() {
-            let widget_under_mouse = self.global_input.current.widget_under_mouse;
 
             // Check to see if we need to uncapture a widget.
             if let Some(idx) = self.global_input.current.widget_capturing_mouse {
@@ -364,6 +543,109 @@ impl Ui {
         }
     }
 
+    /// Apply the given scroll to the top-most scrollable widget under `xy` that has not yet
+    /// reached the bound of the scroll's direction, emitting a `Scroll` event for it.
+    ///
+    /// Returns whether or not some widget was scrolled.
+    fn scroll_widgets_at(&mut self, xy: Point, x: Scalar, y: Scalar) -> bool {
+        let mut scrollable_widgets =
+            graph::algo::pick_scrollable_widgets(&self.depth_order.indices, xy);
+
+        // Iterate through the scrollable widgets from top to bottom.
+        //
+        // A scroll event will be created for the first scrollable widget
+        // that hasn't already reached the bound of the scroll event's
+        // direction.
+        while let Some(idx) =
+            scrollable_widgets.next(&self.widget_graph,
+                                    &self.depth_order.indices,
+                                    &self.theme)
+        {
+
+            let (kid_area, maybe_x_scroll, maybe_y_scroll) =
+                match self.widget_graph.widget(idx) {
+                    Some(widget) => {
+                        (widget.kid_area,
+                         widget.maybe_x_scroll_state,
+                         widget.maybe_y_scroll_state)
+                    },
+                    None => continue,
+                };
+
+            fn offset_is_at_bound<A>(scroll: &widget::scroll::State<A>,
+                                     additional_offset: Scalar) -> bool
+            {
+                use utils;
+
+                fn approx_eq(a: Scalar, b: Scalar) -> bool {
+                    (a - b).abs() < 0.000001
+                }
+
+                if additional_offset.is_sign_positive() {
+                    let max = utils::partial_max(scroll.offset_bounds.start,
+                                                 scroll.offset_bounds.end);
+                    approx_eq(scroll.offset, max)
+                } else {
+                    let min = utils::partial_min(scroll.offset_bounds.start,
+                                                 scroll.offset_bounds.end);
+                    approx_eq(scroll.offset, min)
+                }
+            }
+
+            let mut scroll_x = false;
+            let mut scroll_y = false;
+
+            // Check whether the x axis is scrollable.
+            if x != 0.0 {
+                let new_scroll =
+                    widget::scroll::State::update(self, idx, &kid_area,
+                                                  maybe_x_scroll, x);
+                if let Some(prev_scroll) = maybe_x_scroll {
+                    let (prev_is_at_bound, new_is_at_bound) =
+                        (offset_is_at_bound(&prev_scroll, x),
+                         offset_is_at_bound(&new_scroll, x));
+                    scroll_x = !prev_is_at_bound || !new_is_at_bound;
+                }
+            }
+
+            // Check whether the y axis is scrollable.
+            if y != 0.0 {
+                let new_scroll =
+                    widget::scroll::State::update(self, idx, &kid_area,
+                                                  maybe_y_scroll, y);
+                if let Some(prev_scroll) = maybe_y_scroll {
+                    let (prev_is_at_bound, new_is_at_bound) =
+                        (offset_is_at_bound(&prev_scroll, y),
+                         offset_is_at_bound(&new_scroll, y));
+                    scroll_y = !prev_is_at_bound || !new_is_at_bound;
+                }
+            }
+
+            // Create a `Scroll` event if either axis is scrollable.
+            if scroll_x || scroll_y {
+                let event = event::Ui::Scroll(Some(idx), event::Scroll {
+                    x: x,
+                    y: y,
+                    modifiers: self.global_input.current.modifiers,
+                }).into();
+                self.global_input.push_event(event);
+
+                // Track the velocity of the input for widgets that scroll kinetically.
+                let is_kinetic = maybe_x_scroll.and_then(|s| s.maybe_kinetic).is_some()
+                    || maybe_y_scroll.and_then(|s| s.maybe_kinetic).is_some();
+                if is_kinetic {
+                    self.record_kinetic_scroll(idx, [x, y]);
+                }
+
+                // Now that we've scrolled the top, scrollable widget,
+                // we're done with the loop.
+                return true;
+            }
+        }
+
+        false
+    }
+
     /// Handle raw window events and update the `Ui` state accordingly.
     ///
     /// This occurs within several stages:
@@ -397,6 +679,28 @@ impl Ui {
             }
         }
 
+        #[cfg(feature = "serde")]
+        {
+            if let Some(ref mut recorder) = self.maybe_recorder {
+                recorder.record(&event);
+            }
+        }
+
+        // Touches that have been held in place for long enough produce a `LongPress` before any
+        // new input is handled.
+        self.check_long_presses();
+
+        // Hide the tooltip and restart the hover delay whenever the mouse moves or a button is
+        // pressed.
+        match event {
+            Input::Motion(Motion::MouseCursor { .. }) |
+            Input::Motion(Motion::MouseRelative { .. }) |
+            Input::Motion(Motion::Scroll { .. }) |
+            Input::Press(_) |
+            Input::Touch(_) => self.tooltip_hover_start = self.clock.now(),
+            _ => (),
+        }
+
         // Here we handle all user input given to conrod.
         //
         // Not only do we store the `Input` event as an `Event::Raw`, we also use them to
@@ -423,6 +727,9 @@ impl Ui {
                     self.global_input.push_event(press_event);
 
                     if let MouseButton::Left = mouse_button {
+                        // Keyboard capture is no longer driven by the focus chain.
+                        self.is_focus_ring_visible = false;
+
                         // Check to see if we need to uncapture the keyboard.
                         if let Some(idx) = self.global_input.current.widget_capturing_keyboard {
                             if Some(idx) != self.global_input.current.widget_under_mouse {
@@ -446,31 +753,139 @@ impl Ui {
                     let xy = self.global_input.current.mouse.xy;
                     let widget = self.global_input.current.widget_under_mouse;
                     self.global_input.current.mouse.buttons.press(mouse_button, xy, widget);
+                    self.dragging_mouse_buttons.retain(|&btn| btn != mouse_button);
                 },
 
                 Button::Keyboard(key) => {
 
-                    // Create a keyboard `Press` event.
+                    // Check whether the key press triggers a registered shortcut.
+                    let capturing = self.global_input.current.widget_capturing_keyboard;
+                    let chord = input::shortcut::Chord {
+                        key: key,
+                        modifiers: self.global_input.current.modifiers,
+                    };
+                    let maybe_shortcut = self.shortcuts.find(chord, capturing, &self.widget_graph);
+
+                    // Otherwise, check whether the key press triggers spatial navigation. Keys are
+                    // left to widgets that accept text input, or that handle navigation keys
+                    // themselves while they or their descendants capture the keyboard.
+                    let is_key_handled_by_capturing = capturing
+                        .map(|id| self.is_navigation_key_handled_by(id))
+                        .unwrap_or(false);
+                    let maybe_navigation = match maybe_shortcut {
+                        None if !is_key_handled_by_capturing =>
+                            self.navigation.action(input::navigation::Binding::Key(key)),
+                        _ => None,
+                    };
+
+                    // A `Tab` press moves keyboard capture along the focus chain, if there is one.
+                    let is_focus_moved = key == Key::Tab && !self.focus_chain.is_empty();
+
+                    // Create a keyboard `Press` event. Key presses that trigger a shortcut,
+                    // navigation or a change of focus are not delivered to the widget capturing
+                    // the keyboard.
                     let press = event::Press {
                         button: event::Button::Keyboard(key),
                         modifiers: self.global_input.current.modifiers,
                     };
-                    let widget = self.global_input.current.widget_capturing_keyboard;
+                    let is_consumed = maybe_shortcut.is_some()
+                        || maybe_navigation.is_some()
+                        || is_focus_moved;
+                    let widget = if is_consumed { None } else { capturing };
                     let press_event = event::Ui::Press(widget, press).into();
                     self.global_input.push_event(press_event);
 
+                    // Create a `Shortcut` event.
+                    if let Some((id, scope)) = maybe_shortcut {
+                        let widget = match scope {
+                            input::shortcut::Scope::Global => None,
+                            input::shortcut::Scope::Widget(widget) => Some(widget),
+                        };
+                        let shortcut_event = event::Ui::Shortcut(widget, id).into();
+                        self.global_input.push_event(shortcut_event);
+                    }
+
+                    if let Some(action) = maybe_navigation {
+                        self.navigate(action);
+                    }
+
                     // If some modifier key was pressed, add it to the current modifiers.
                     if let Some(modifier) = filter_modifier(key) {
                         self.global_input.current.modifiers.insert(modifier);
                     }
 
+                    // If `Tab` was pressed, move keyboard capture along the focus chain. The `Tab`
+                    // text that follows the press must not reach the newly focused widget.
+                    self.is_tab_text_pending = is_focus_moved;
+                    if is_focus_moved {
+                        let backwards = self.global_input.current.modifiers
+                            .contains(ModifierKey::SHIFT);
+                        self.move_keyboard_focus(!backwards);
+                    }
+
                     // If `Esc` was pressed, check to see if we need to cancel a `Drag` or
                     // uncapture a widget.
                     if let Key::Escape = key {
-                        // TODO:
-                        // 1. Cancel `Drag` if currently under way.
-                        // 2. If mouse is captured due to pinning widget with left mouse button,
-                        //    cancel capturing.
+                        let mouse_xy = self.global_input.current.mouse.xy;
+                        let is_left_down = self.global_input.current.mouse.buttons.left().is_down();
+
+                        // Cancel any drag and drop in progress. Its source receives the left
+                        // button's `DragCancel` below if the button is dragging from the source.
+                        if let Some(state) = self.maybe_drag.take() {
+                            let source = state.drag.source();
+                            let pressed = self.global_input.current.mouse.buttons.left().if_down();
+                            let is_pressed_on_source = pressed.and_then(|(_, w)| w) == Some(source);
+                            let is_left_dragging = self.dragging_mouse_buttons
+                                .contains(&MouseButton::Left);
+                            if !is_pressed_on_source || !is_left_dragging {
+                                let drag_cancel = event::DragCancel {
+                                    button: MouseButton::Left,
+                                    origin: state.origin,
+                                    xy: mouse_xy,
+                                    modifiers: self.global_input.current.modifiers,
+                                };
+                                let event = event::Ui::DragCancel(Some(source), drag_cancel);
+                                self.global_input.push_event(event.into());
+                            }
+                        }
+
+                        // Cancel the `Drag` of every mouse button that is dragging. Releasing
+                        // all buttons from the `input::State` ensures that no further `Drag`s or
+                        // `Click`s are produced until they are pressed again.
+                        let buttons = self.global_input.current.mouse.buttons.clone();
+                        for (btn, btn_xy, widget) in buttons.pressed() {
+                            if self.dragging_mouse_buttons.contains(&btn) {
+                                let drag_cancel = event::DragCancel {
+                                    button: btn,
+                                    origin: btn_xy,
+                                    xy: mouse_xy,
+                                    modifiers: self.global_input.current.modifiers,
+                                };
+                                let event = event::Ui::DragCancel(widget, drag_cancel);
+                                self.global_input.push_event(event.into());
+                            }
+                            self.global_input.current.mouse.buttons.release(btn);
+                        }
+                        self.dragging_mouse_buttons.clear();
+
+                        // If the mouse was pinned to a widget by the left mouse button, release
+                        // it before returning capturing to whatever is under the mouse.
+                        if is_left_down {
+                            if let Some(idx) = self.global_input.current.widget_capturing_mouse {
+                                let source = input::Source::Mouse;
+                                let event = event::Ui::WidgetUncapturesInputSource(idx, source);
+                                self.global_input.push_event(event.into());
+                                self.global_input.current.widget_capturing_mouse = None;
+                            }
+                            self.track_widget_under_mouse_and_update_capturing();
+                        }
+                    }
+                },
+
+                Button::Controller(controller_button) => {
+                    let binding = input::navigation::Binding::ControllerButton(controller_button.button);
+                    if let Some(action) = self.navigation.action(binding) {
+                        self.navigate(action);
                     }
                 },
 
@@ -496,6 +911,11 @@ impl Ui {
                     let release_event = event::Ui::Release(widget, release).into();
                     self.global_input.push_event(release_event);
 
+                    // Drop the payload of any drag and drop in progress.
+                    if let MouseButton::Left = mouse_button {
+                        self.drop_drag();
+                    }
+
                     // Check for `Click` and `DoubleClick` events.
                     let down = self.global_input.current.mouse.buttons[mouse_button].if_down();
                     if let Some((_, widget)) = down {
@@ -515,7 +935,7 @@ impl Ui {
                         let click_event = event::Ui::Click(clicked_widget, click).into();
                         self.global_input.push_event(click_event);
 
-                        let now = std::time::Instant::now();
+                        let now = self.clock.now();
                         let double_click = self.global_input.last_click
                             .and_then(|(last_time, last_click)| {
 
@@ -577,6 +997,7 @@ impl Ui {
 
                     // Release the given mouse_button from the input::State.
                     self.global_input.current.mouse.buttons.release(mouse_button);
+                    self.dragging_mouse_buttons.retain(|&btn| btn != mouse_button);
                 },
 
                 Button::Keyboard(key) => {
@@ -653,6 +1074,9 @@ impl Ui {
                                     modifiers: self.global_input.current.modifiers,
                                 }).into();
                                 self.global_input.push_event(event);
+                                if !self.dragging_mouse_buttons.contains(&btn) {
+                                    self.dragging_mouse_buttons.push(btn);
+                                }
                             }
                         }
 
@@ -666,96 +1090,8 @@ impl Ui {
                     // Some scrolling occurred (e.g. mouse scroll wheel).
                     Motion::Scroll { x, y } => {
 
-                        let mut scrollable_widgets = {
-                            let depth_order = &self.depth_order.indices;
-                            let mouse_xy = self.global_input.current.mouse.xy;
-                            graph::algo::pick_scrollable_widgets(depth_order, mouse_xy)
-                        };
-
-                        // Iterate through the scrollable widgets from top to bottom.
-                        //
-                        // A scroll event will be created for the first scrollable widget
-                        // that hasn't already reached the bound of the scroll event's
-                        // direction.
-                        while let Some(idx) =
-                            scrollable_widgets.next(&self.widget_graph,
-                                                    &self.depth_order.indices,
-                                                    &self.theme)
-                        {
-
-                            let (kid_area, maybe_x_scroll, maybe_y_scroll) =
-                                match self.widget_graph.widget(idx) {
-                                    Some(widget) => {
-                                        (widget.kid_area,
-                                         widget.maybe_x_scroll_state,
-                                         widget.maybe_y_scroll_state)
-                                    },
-                                    None => continue,
-                                };
-
-                            fn offset_is_at_bound<A>(scroll: &widget::scroll::State<A>,
-                                                     additional_offset: Scalar) -> bool
-                            {
-                                use utils;
-
-                                fn approx_eq(a: Scalar, b: Scalar) -> bool {
-                                    (a - b).abs() < 0.000001
-                                }
-
-                                if additional_offset.is_sign_positive() {
-                                    let max = utils::partial_max(scroll.offset_bounds.start,
-                                                                 scroll.offset_bounds.end);
-                                    approx_eq(scroll.offset, max)
-                                } else {
-                                    let min = utils::partial_min(scroll.offset_bounds.start,
-                                                                 scroll.offset_bounds.end);
-                                    approx_eq(scroll.offset, min)
-                                }
-                            }
-
-                            let mut scroll_x = false;
-                            let mut scroll_y = false;
-
-                            // Check whether the x axis is scrollable.
-                            if x != 0.0 {
-                                let new_scroll =
-                                    widget::scroll::State::update(self, idx, &kid_area,
-                                                                  maybe_x_scroll, x);
-                                if let Some(prev_scroll) = maybe_x_scroll {
-                                    let (prev_is_at_bound, new_is_at_bound) =
-                                        (offset_is_at_bound(&prev_scroll, x),
-                                         offset_is_at_bound(&new_scroll, x));
-                                    scroll_x = !prev_is_at_bound || !new_is_at_bound;
-                                }
-                            }
-
-                            // Check whether the y axis is scrollable.
-                            if y != 0.0 {
-                                let new_scroll =
-                                    widget::scroll::State::update(self, idx, &kid_area,
-                                                                  maybe_y_scroll, y);
-                                if let Some(prev_scroll) = maybe_y_scroll {
-                                    let (prev_is_at_bound, new_is_at_bound) =
-                                        (offset_is_at_bound(&prev_scroll, y),
-                                         offset_is_at_bound(&new_scroll, y));
-                                    scroll_y = !prev_is_at_bound || !new_is_at_bound;
-                                }
-                            }
-
-                            // Create a `Scroll` event if either axis is scrollable.
-                            if scroll_x || scroll_y {
-                                let event = event::Ui::Scroll(Some(idx), event::Scroll {
-                                    x: x,
-                                    y: y,
-                                    modifiers: self.global_input.current.modifiers,
-                                }).into();
-                                self.global_input.push_event(event);
-
-                                // Now that we've scrolled the top, scrollable widget,
-                                // we're done with the loop.
-                                break;
-                            }
-                        }
+                        let mouse_xy = self.global_input.current.mouse.xy;
+                        self.scroll_widgets_at(mouse_xy, x, y);
 
                         // If no scrollable widgets could be scrolled, emit the event to
                         // the widget that currently captures the mouse.
@@ -785,12 +1121,26 @@ impl Ui {
                         self.track_widget_under_mouse_and_update_capturing();
                     },
 
+                    // A controller axis may trigger spatial navigation.
+                    Motion::ControllerAxis(args) => {
+                        if let Some(action) = self.navigation.axis_action(args) {
+                            self.navigate(action);
+                        }
+                    },
+
                     _ => (),
 
                 }
             },
 
             Input::Text(string) => {
+                // Discard the text produced by a `Tab` press that moved keyboard focus.
+                let is_tab_text_pending = self.is_tab_text_pending;
+                self.is_tab_text_pending = false;
+                if is_tab_text_pending && string == "\t" {
+                    return;
+                }
+
                 // Create a `Text` event.
                 let text = event::Text {
                     string: string,
@@ -811,7 +1161,7 @@ impl Ui {
 
                     // The start of the touch interaction state to be stored.
                     let start = input::state::touch::Start {
-                        time: std::time::Instant::now(),
+                        time: self.clock.now(),
                         xy: touch.xy,
                         widget: widget_under_touch,
                     };
@@ -830,6 +1180,28 @@ impl Ui {
                     let event = event::Ui::Touch(widget_under_touch, touch);
                     self.global_input.push_event(event.into());
 
+                    // If this is the second of two touches, begin interpreting them together as a
+                    // pinch, rotate and pan gesture over the widget containing both.
+                    if self.global_input.gestures.pair().is_none()
+                    && self.global_input.current.touch.len() == 2 {
+                        let other = self.global_input.current.touch.iter()
+                            .find(|&(&id, _)| id != touch.id)
+                            .map(|(&id, other)| (id, other.xy, other.start.widget));
+                        if let Some((other_id, other_xy, other_widget)) = other {
+                            let widget = match (other_widget, widget_under_touch) {
+                                (Some(a), Some(b)) =>
+                                    graph::algo::common_depth_ancestor(&self.widget_graph, a, b),
+                                _ => None,
+                            };
+                            let pair = input::gesture::Pair {
+                                touches: [other_id, touch.id],
+                                widget: widget,
+                                xys: [other_xy, touch.xy],
+                            };
+                            self.global_input.gestures.begin_pair(pair);
+                        }
+                    }
+
                     // Push capture event.
                     if let Some(widget) = widget_under_touch {
                         let source = input::Source::Touch(touch.id);
@@ -840,25 +1212,55 @@ impl Ui {
 
                 input::touch::Phase::Move => {
 
-                    // Update the widget under the touch and return the widget capturing the touch.
-                    let widget = match self.global_input.current.touch.get_mut(&touch.id) {
-                        Some(touch) => {
-                            touch.widget =
+                    // Update the widget under the touch and return the widget capturing the touch
+                    // along with where the touch began.
+                    let (widget, start_xy) = match self.global_input.current.touch.get_mut(&touch.id) {
+                        Some(state) => {
+                            state.widget =
                                 graph::algo::pick_widgets(&self.depth_order.indices, touch.xy)
                                     .next(&self.widget_graph,
                                           &self.depth_order.indices,
                                           &self.theme);
-                            touch.xy = touch.xy;
-                            touch.start.widget
+                            state.xy = touch.xy;
+                            (state.start.widget, state.start.xy)
                         },
-                        None => None,
+                        None => (None, touch.xy),
                     };
                     let event = event::Ui::Touch(widget, touch);
                     self.global_input.push_event(event.into());
+
+                    // Check whether the touch belongs to a two-finger gesture.
+                    let gesture = self.global_input.gestures.move_touch(touch.id, start_xy, touch.xy);
+                    if let Some((widget, delta)) = gesture {
+                        let center = delta.center;
+
+                        if delta.scale != 1.0 {
+                            let pinch = event::Pinch { center: center, scale: delta.scale };
+                            let event = event::Ui::Pinch(widget, pinch);
+                            self.global_input.push_event(event.into());
+                        }
+
+                        if delta.rotation != 0.0 {
+                            let rotate = event::Rotate { center: center, radians: delta.rotation };
+                            let event = event::Ui::Rotate(widget, rotate);
+                            self.global_input.push_event(event.into());
+                        }
+
+                        let [dx, dy] = delta.translation;
+                        if dx != 0.0 || dy != 0.0 {
+                            let pan = event::Pan { center: center, delta_xy: delta.translation };
+                            let event = event::Ui::Pan(widget, pan);
+                            self.global_input.push_event(event.into());
+
+                            // Panning with two fingers scrolls the scrollable widgets beneath.
+                            self.scroll_widgets_at(center, dx, dy);
+                        }
+                    }
                 },
 
                 input::touch::Phase::Cancel => {
                     let widget = self.global_input.current.touch.remove(&touch.id).and_then(|t| t.start.widget);
+                    self.global_input.gestures.end_touch(touch.id);
                     let event = event::Ui::Touch(widget, touch);
                     self.global_input.push_event(event.into());
 
@@ -872,6 +1274,7 @@ impl Ui {
 
                 input::touch::Phase::End => {
                     let old_touch = self.global_input.current.touch.remove(&touch.id).map(|touch| touch);
+                    self.global_input.gestures.end_touch(touch.id);
                     let widget_capturing = old_touch.as_ref().and_then(|touch| touch.start.widget);
                     let event = event::Ui::Touch(widget_capturing, touch);
                     self.global_input.push_event(event.into());
@@ -905,9 +1308,455 @@ impl Ui {
             Input::Focus(_focused) => (),
 
             Input::Redraw => self.needs_redraw(),
+
+            // A file from the OS is being dragged over the window.
+            //
+            // The file events carry no position, so the widget is picked at the last known
+            // position of the cursor. See `Input::FileHovered`.
+            Input::FileHovered(path) => {
+                let widget = self.widget_under_last_cursor_position();
+                let file_hover = event::FileHover {
+                    path: path,
+                    xy: self.global_input.current.mouse.xy,
+                };
+                self.global_input.push_event(event::Ui::FileHover(widget, file_hover).into());
+                self.maybe_file_hover_widget = widget;
+            },
+
+            // A file from the OS was dropped onto the window.
+            Input::FileDropped(path) => {
+                let widget = self.widget_under_last_cursor_position();
+                let file_drop = event::FileDrop {
+                    path: path,
+                    xy: self.global_input.current.mouse.xy,
+                    modifiers: self.global_input.current.modifiers,
+                };
+                self.global_input.push_event(event::Ui::FileDrop(widget, file_drop).into());
+                self.maybe_file_hover_widget = None;
+            },
+
+            // The hovering files left the window without being dropped.
+            Input::FileHoverCancelled => {
+                let widget = self.maybe_file_hover_widget.take();
+                self.global_input.push_event(event::Ui::FileHoverCancel(widget).into());
+            },
+        }
+    }
+
+
+    /// The widget at the last position of the mouse cursor reported via `Input::Motion`, picked
+    /// from the widgets as they were at the end of the last `set_widgets` stage.
+    fn widget_under_last_cursor_position(&self) -> Option<widget::Id> {
+        graph::algo::pick_widgets(&self.depth_order.indices, self.global_input.current.mouse.xy)
+            .next(&self.widget_graph, &self.depth_order.indices, &self.theme)
+    }
+
+    /// Begin recording every `Input` passed to `handle_event` with the given `recorder`.
+    ///
+    /// Returns the previous recorder if the `Ui` was already recording.
+    #[cfg(feature = "serde")]
+    pub fn start_recording(&mut self, recorder: input::record::Recorder)
+        -> Option<input::record::Recorder>
+    {
+        std::mem::replace(&mut self.maybe_recorder, Some(recorder))
+    }
+
+    /// Stop recording, returning the recorder so that the recording may be `finish`ed.
+    #[cfg(feature = "serde")]
+    pub fn stop_recording(&mut self) -> Option<input::record::Recorder> {
+        self.maybe_recorder.take()
+    }
+
+    /// Whether or not the `Ui` is currently recording input.
+    #[cfg(feature = "serde")]
+    pub fn is_recording(&self) -> bool {
+        self.maybe_recorder.is_some()
+    }
+
+    /// Record scroll input applied to a kinetically scrolling widget so that its velocity may be
+    /// tracked.
+    fn record_kinetic_scroll(&mut self, idx: widget::Id, delta: [Scalar; 2]) {
+        let now = self.clock.now();
+        let window = std::time::Duration::from_millis(KINETIC_VELOCITY_WINDOW_MS);
+        let kinetic = self.kinetic_scrolls.entry(idx).or_insert_with(|| KineticScroll {
+            samples: Vec::new(),
+            has_input: false,
+            velocity: [0.0, 0.0],
+            last_step: now,
+        });
+        kinetic.samples.retain(|&(time, _)| now.duration_since(time) <= window);
+        kinetic.samples.push((now, delta));
+        kinetic.has_input = true;
+        kinetic.velocity = [0.0, 0.0];
+    }
+
+    /// Step the motion of all kinetically scrolling widgets, emitting a `Scroll` event for each
+    /// widget that is still moving.
+    ///
+    /// Widgets only begin moving with momentum once a step passes without any scroll input.
+    fn step_kinetic_scrolls(&mut self) {
+        if self.kinetic_scrolls.is_empty() {
+            return;
+        }
+
+        let now = self.clock.now();
+        let ids: Vec<widget::Id> = self.kinetic_scrolls.keys().cloned().collect();
+        for idx in ids {
+            let (maybe_x_scroll, maybe_y_scroll) = match self.widget_graph.widget(idx) {
+                Some(widget) => (widget.maybe_x_scroll_state, widget.maybe_y_scroll_state),
+                None => {
+                    self.kinetic_scrolls.remove(&idx);
+                    continue;
+                },
+            };
+
+            let (delta, is_at_rest) = match self.kinetic_scrolls.get_mut(&idx) {
+                None => continue,
+                Some(kinetic) => {
+                    // Wait for the scroll input to stop before continuing with its momentum.
+                    if kinetic.has_input {
+                        kinetic.has_input = false;
+                        kinetic.last_step = now;
+                        continue;
+                    }
+
+                    // Release the most recent input into kinetic motion.
+                    if !kinetic.samples.is_empty() {
+                        kinetic.velocity = kinetic_velocity(&kinetic.samples, now);
+                        kinetic.samples.clear();
+                    }
+
+                    let dt = duration_secs(now.duration_since(kinetic.last_step));
+                    kinetic.last_step = now;
+                    let (dx, vx) = maybe_x_scroll
+                        .map(|scroll| scroll.step_kinetic(kinetic.velocity[0], dt))
+                        .unwrap_or((0.0, 0.0));
+                    let (dy, vy) = maybe_y_scroll
+                        .map(|scroll| scroll.step_kinetic(kinetic.velocity[1], dt))
+                        .unwrap_or((0.0, 0.0));
+                    kinetic.velocity = [vx, vy];
+                    let is_at_rest = dx == 0.0 && dy == 0.0 && vx == 0.0 && vy == 0.0;
+                    ([dx, dy], is_at_rest)
+                },
+            };
+
+            if is_at_rest {
+                self.kinetic_scrolls.remove(&idx);
+            } else {
+                self.scroll_widget(idx, delta);
+            }
+        }
+
+        if !self.kinetic_scrolls.is_empty() {
+            self.needs_redraw();
+        }
+    }
+
+    /// Whether or not some part of the `Ui` is animating, e.g. a widget is scrolling kinetically,
+    /// a touch is being held that may become a `LongPress`, the mouse is resting over a widget
+    /// whose tooltip is yet to appear or a widget sized to its content was resized after its
+    /// children were positioned relative to it.
+    ///
+    /// While animating, the `Ui` should continue to be updated via `set_widgets` even if no input
+    /// is received.
+    pub fn is_animating(&self) -> bool {
+        self.is_layout_pending
+            || !self.kinetic_scrolls.is_empty()
+            || self.is_long_press_pending()
+            || self.is_tooltip_pending()
+    }
+
+    /// Whether or not some touch may still become a `LongPress` once it has been held for the
+    /// theme's `long_press_duration`.
+    fn is_long_press_pending(&self) -> bool {
+        let gestures = &self.global_input.gestures;
+        self.global_input.current.touch.keys().any(|&id| gestures.is_long_press_candidate(id))
+    }
+
+    /// Emit a `LongPress` for each touch that has been held in place for at least the theme's
+    /// `long_press_duration`.
+    fn check_long_presses(&mut self) {
+        let now = self.clock.now();
+        let long_press_duration = self.theme.long_press_duration;
+        let long_presses: Vec<_> = {
+            let gestures = &self.global_input.gestures;
+            self.global_input.current.touch.iter()
+                .filter(|&(&id, touch)| {
+                    gestures.is_long_press_candidate(id)
+                        && now.duration_since(touch.start.time) >= long_press_duration
+                })
+                .map(|(&id, touch)| (id, touch.start.widget, touch.xy))
+                .collect()
+        };
+        for (id, widget, xy) in long_presses {
+            self.global_input.gestures.long_pressed(id);
+            let long_press = event::LongPress { id: id, xy: xy };
+            let event = event::Ui::LongPress(widget, long_press);
+            self.global_input.push_event(event.into());
+        }
+    }
+
+    /// The drag and drop in progress, if any.
+    pub fn drag(&self) -> Option<&input::drag_drop::Drag> {
+        self.maybe_drag.as_ref().map(|state| &state.drag)
+    }
+
+    /// The drag and drop in progress if the mouse is over the widget with the given index or any
+    /// of its descendants.
+    ///
+    /// Widgets that accept the returned drag's payload should call `UiCell::accept_drop`.
+    pub fn drag_over(&self, id: widget::Id) -> Option<&input::drag_drop::Drag> {
+        self.maybe_drag.as_ref()
+            .and_then(|state| {
+                let mut maybe_id = self.global_input.current.widget_under_mouse;
+                while let Some(under) = maybe_id {
+                    if under == id {
+                        return Some(&state.drag);
+                    }
+                    maybe_id = self.widget_graph.depth_parent(under);
+                }
+                None
+            })
+    }
+
+    /// The widget that would receive the drag in progress if it were dropped now.
+    ///
+    /// This is the innermost widget under the mouse that accepted the drag during the last
+    /// `set_widgets` stage.
+    pub fn drop_target(&self) -> Option<widget::Id> {
+        let state = match self.maybe_drag {
+            Some(ref state) => state,
+            None => return None,
+        };
+        let mut maybe_id = self.global_input.current.widget_under_mouse;
+        while let Some(id) = maybe_id {
+            if state.acceptors.contains(&id) {
+                return Some(id);
+            }
+            maybe_id = self.widget_graph.depth_parent(id);
+        }
+        None
+    }
+
+    /// The drag that was dropped since the last `set_widgets` stage, if any.
+    ///
+    /// Use `UiCell::take_dropped_payload` to take ownership of its payload.
+    pub fn dropped(&self) -> Option<&input::drag_drop::Drag> {
+        self.maybe_dropped.as_ref().map(|&(_, ref drag)| drag)
+    }
+
+    /// Deliver the drag in progress to the `drop_target`, or cancel it if there is none.
+    fn drop_drag(&mut self) {
+        let maybe_target = self.drop_target();
+        let state = match self.maybe_drag.take() {
+            Some(state) => state,
+            None => return,
+        };
+        let xy = self.global_input.current.mouse.xy;
+        let modifiers = self.global_input.current.modifiers;
+        let source = state.drag.source();
+        match maybe_target {
+            Some(target) => {
+                let drop = event::DropEvent {
+                    source: source,
+                    target: target,
+                    xy: xy,
+                    modifiers: modifiers,
+                };
+                self.global_input.push_event(event::Ui::Drop(target, drop).into());
+                self.maybe_dropped = Some((target, state.drag));
+            },
+            None => {
+                let drag_cancel = event::DragCancel {
+                    button: input::MouseButton::Left,
+                    origin: state.origin,
+                    xy: xy,
+                    modifiers: modifiers,
+                };
+                let event = event::Ui::DragCancel(Some(source), drag_cancel);
+                self.global_input.push_event(event.into());
+            },
         }
     }
 
+    /// Fit each widget registered via `fit_to_content` around the bounding box of its children.
+    ///
+    /// Most widgets are already fit as soon as their children have been set (see
+    /// `fit_finished_widgets_to_content`). Fitting them all again once every widget has been set
+    /// accounts for any children that were set after some unrelated widget.
+    ///
+    /// Widgets are fit in the reverse order in which they were set so that nested widgets are fit
+    /// before their parents.
+    fn fit_widgets_to_content(&mut self) {
+        self.pending_content_fits.clear();
+        let fits = std::mem::replace(&mut self.content_fits, Vec::new());
+        for &fit in fits.iter().rev() {
+            self.fit_widget_to_content(fit);
+        }
+    }
+
+    /// Fit the widget around the bounding box of its children.
+    ///
+    /// The fitted length is clamped to the widget's minimum and maximum lengths, keeping its left
+    /// and top edges in place.
+    ///
+    /// The widget's graphic children (e.g. its backdrop) are moved along with the nearest edge of
+    /// the widget, or stretched if they span the widget entirely.
+    fn fit_widget_to_content(&mut self, fit: ContentFit) {
+        use daggy::Walker;
+
+        let ContentFit { id, pads, bounds } = fit;
+
+        let (rect, kid_area) = match self.widget_graph.widget(id) {
+            Some(container) => (container.rect, container.kid_area.rect),
+            None => return,
+        };
+        let maybe_bounds = graph::algo::kids_bounding_box(&self.widget_graph,
+                                                          &self.updated_widgets,
+                                                          id);
+
+        // The content of an empty widget collapses to the start of its kid area.
+        let content = maybe_bounds.unwrap_or_else(|| {
+            Rect { x: Range::new(kid_area.left(), kid_area.left()),
+                   y: Range::new(kid_area.bottom(), kid_area.bottom()) }
+        });
+        let fit_range = |range: Range, kid: Range, content: Range, maybe_pad: Option<Scalar>| {
+            maybe_pad.map(|pad| {
+                let start_margin = kid.start - range.start;
+                let end_margin = range.end - kid.end;
+                let kid = Range::new(content.start - pad, content.end + pad);
+                (Range::new(kid.start - start_margin, kid.end + end_margin), kid)
+            }).unwrap_or((range, kid))
+        };
+        let (mut x, mut kid_x) = fit_range(rect.x, kid_area.x, content.x, pads[0]);
+        let (mut y, mut kid_y) = fit_range(rect.y, kid_area.y, content.y, pads[1]);
+        let clamp_delta = |range: Range, (min, max): (Option<Scalar>, Option<Scalar>)| {
+            position::clamp_length(range.len(), min, max) - range.len()
+        };
+        if pads[0].is_some() {
+            let delta = clamp_delta(x, bounds[0]);
+            x.end += delta;
+            kid_x.end += delta;
+        }
+        if pads[1].is_some() {
+            let delta = clamp_delta(y, bounds[1]);
+            y.start -= delta;
+            kid_y.start -= delta;
+        }
+        let new_rect = Rect { x: x, y: y };
+        if new_rect == rect {
+            return;
+        }
+
+        // Children positioned relative to the widget (e.g. `mid_top_of`) were placed against its
+        // previous dimensions, so the widgets must be set again to be placed against the new ones.
+        let is_resized = (new_rect.w() - rect.w()).abs() > std::f64::EPSILON
+            || (new_rect.h() - rect.h()).abs() > std::f64::EPSILON;
+        if is_resized {
+            self.is_layout_pending = true;
+            self.needs_redraw();
+        }
+
+        // Collect the graphic children along with all of their descendants.
+        let mut graphics = Vec::new();
+        let mut stack: Vec<widget::Id> = self.widget_graph.graphic_children(id)
+            .iter(&self.widget_graph).nodes().collect();
+        while let Some(graphic) = stack.pop() {
+            graphics.push(graphic);
+            stack.extend(self.widget_graph.depth_children(graphic)
+                .iter(&self.widget_graph).nodes());
+        }
+
+        let move_range = |range: Range, old: Range, new: Range| -> Range {
+            if (range.len() - old.len()).abs() < std::f64::EPSILON
+            && (range.start - old.start).abs() < std::f64::EPSILON {
+                return new;
+            }
+            match old.closest_edge(range.middle()) {
+                position::Edge::Start => range.shift(new.start - old.start),
+                position::Edge::End => range.shift(new.end - old.end),
+            }
+        };
+        for graphic in graphics {
+            if let Some(container) = self.widget_graph.widget_mut(graphic) {
+                let graphic_rect = container.rect;
+                container.rect = Rect {
+                    x: move_range(graphic_rect.x, rect.x, new_rect.x),
+                    y: move_range(graphic_rect.y, rect.y, new_rect.y),
+                };
+                let kid_rect = container.kid_area.rect;
+                container.kid_area.rect = Rect {
+                    x: move_range(kid_rect.x, rect.x, new_rect.x),
+                    y: move_range(kid_rect.y, rect.y, new_rect.y),
+                };
+            }
+        }
+
+        if let Some(container) = self.widget_graph.widget_mut(id) {
+            container.rect = new_rect;
+            container.kid_area.rect = Rect { x: kid_x, y: kid_y };
+        }
+    }
+
+    /// The constraints registered via `UiCell::constrain` that were ignored during the last
+    /// `set_widgets` stage, along with the reason why.
+    ///
+    /// A required constraint is rejected when it conflicts with the required constraints that
+    /// were added before it, or with the `Rect` of a widget that had already been set.
+    pub fn rejected_constraints(&self)
+        -> &[(position::constraint::Constraint, position::constraint::Error)]
+    {
+        &self.rejected_constraints
+    }
+
+    /// The widget whose tooltip was displayed during the last `set_widgets` stage, if any.
+    pub fn tooltip_target(&self) -> Option<widget::Id> {
+        self.maybe_tooltip_target
+    }
+
+    /// The widget whose registered tooltip should currently be displayed, if any.
+    ///
+    /// This is the innermost widget under the mouse with a registered tooltip, as long as the
+    /// mouse has rested for the `theme.tooltip_delay` with no buttons pressed.
+    fn find_tooltip_target(&self) -> Option<widget::Id> {
+        let now = self.clock.now();
+        if now < self.tooltip_hover_start
+        || now.duration_since(self.tooltip_hover_start) < self.theme.tooltip_delay {
+            return None;
+        }
+        self.hovered_tooltip_widget()
+    }
+
+    /// Whether or not the mouse is over a widget whose tooltip is waiting for the
+    /// `theme.tooltip_delay` to elapse before it is displayed.
+    fn is_tooltip_pending(&self) -> bool {
+        let maybe_hovered = self.hovered_tooltip_widget();
+        maybe_hovered.is_some() && maybe_hovered != self.maybe_tooltip_target
+    }
+
+    /// The innermost widget under the mouse with a registered tooltip, regardless of how long the
+    /// mouse has rested over it.
+    fn hovered_tooltip_widget(&self) -> Option<widget::Id> {
+        if self.tooltips.is_empty() {
+            return None;
+        }
+        let current = &self.global_input.current;
+        if current.mouse.buttons.pressed().next().is_some() {
+            return None;
+        }
+        let mut maybe_id = current.widget_under_mouse;
+        while let Some(id) = maybe_id {
+            // Keep showing the same tooltip if the mouse is over the tooltip itself.
+            if id == self.tooltip {
+                return self.maybe_tooltip_target;
+            }
+            if self.tooltips.contains_key(&id) {
+                return Some(id);
+            }
+            maybe_id = self.widget_graph.depth_parent(id);
+        }
+        None
+    }
 
     /// Get an immutable reference to global input. Handles aggregation of events and providing them to Widgets
     ///
@@ -931,6 +1780,119 @@ impl Ui {
         self.global_input.current.widget_capturing_keyboard = Some(idx);
     }
 
+    /// The keyboard focus chain.
+    ///
+    /// This contains all focusable widgets that were updated during the last call to
+    /// `Ui::set_widgets`, in the order in which they are visited by `Tab`.
+    pub fn focus_chain(&self) -> &[widget::Id] {
+        &self.focus_chain
+    }
+
+    /// Move keyboard capture to the next widget within the focus chain, or the previous widget
+    /// if `forwards` is `false`.
+    ///
+    /// If no widget within the chain is capturing the keyboard, capture moves to the first (or
+    /// last) widget.
+    fn move_keyboard_focus(&mut self, forwards: bool) {
+        let len = self.focus_chain.len();
+        if len == 0 {
+            return;
+        }
+
+        let maybe_capturing = self.global_input.current.widget_capturing_keyboard;
+        let maybe_pos = maybe_capturing
+            .and_then(|id| self.focus_chain.iter().position(|&chain_id| chain_id == id));
+        let next_pos = match (maybe_pos, forwards) {
+            (Some(pos), true) => (pos + 1) % len,
+            (Some(pos), false) => (pos + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+        let next = self.focus_chain[next_pos];
+        self.focus_widget(next);
+    }
+
+    /// Perform the given spatial navigation `action`.
+    ///
+    /// Moving focus from a widget that is not capturing the keyboard moves it to the first widget
+    /// within the focus chain.
+    fn navigate(&mut self, action: input::navigation::Action) {
+        use input::navigation::{self, Action};
+
+        let maybe_focused = self.global_input.current.widget_capturing_keyboard
+            .and_then(|id| self.rect_of(id).map(|rect| (id, rect)));
+
+        match action {
+            Action::Move(direction) => {
+                let maybe_next = match maybe_focused {
+                    Some((focused, rect)) => {
+                        let candidates = self.focus_chain.iter()
+                            .filter(|&&id| id != focused)
+                            .filter_map(|&id| self.rect_of(id).map(|rect| (id, rect)));
+                        navigation::nearest(rect, direction, candidates)
+                    },
+                    None => self.focus_chain.first().cloned(),
+                };
+                if let Some(next) = maybe_next {
+                    self.focus_widget(next);
+                }
+            },
+
+            // Activate the focused widget by clicking its centre.
+            Action::Activate => {
+                if let Some((focused, rect)) = maybe_focused {
+                    let click = event::Click {
+                        button: input::MouseButton::Left,
+                        xy: rect.xy(),
+                        modifiers: self.global_input.current.modifiers,
+                    };
+                    let event = event::Ui::Click(Some(focused), click);
+                    self.global_input.push_event(event.into());
+                    self.needs_redraw();
+                }
+            },
+        }
+    }
+
+    /// Whether the given widget capturing the keyboard should receive keys bound to spatial
+    /// navigation rather than having them move focus.
+    fn is_navigation_key_handled_by(&self, capturing: widget::Id) -> bool {
+        let is_text_input = self.widget_graph.widget(capturing)
+            .map(|container| container.is_text_input)
+            .unwrap_or(false);
+        if is_text_input {
+            return true;
+        }
+        let mut maybe_id = Some(capturing);
+        while let Some(id) = maybe_id {
+            if let Some(container) = self.widget_graph.widget(id) {
+                if container.handles_navigation_keys {
+                    return true;
+                }
+            }
+            maybe_id = self.widget_graph.depth_parent(id);
+        }
+        false
+    }
+
+    /// Give keyboard capture to the given widget and show the focus ring around it.
+    fn focus_widget(&mut self, id: widget::Id) {
+        let source = input::Source::Keyboard;
+        let maybe_capturing = self.global_input.current.widget_capturing_keyboard;
+        if maybe_capturing != Some(id) {
+            if let Some(idx) = maybe_capturing {
+                let event = event::Ui::WidgetUncapturesInputSource(idx, source);
+                self.global_input.push_event(event.into());
+            }
+            let event = event::Ui::WidgetCapturesInputSource(id, source);
+            self.global_input.push_event(event.into());
+            self.global_input.current.widget_capturing_keyboard = Some(id);
+        }
+
+        self.is_focus_ring_visible = true;
+        self.needs_redraw();
+    }
+
     /// Get the centred xy coords for some given `Dimension`s, `Position` and alignment.
     ///
     /// If getting the xy for a specific widget, its `widget::Id` should be specified so that we
@@ -1039,9 +2001,20 @@ impl Ui {
         let xy = [x, y];
 
         // Add the widget's parents' total combined scroll offset to the given xy.
-        maybe_id
+        let xy = maybe_id
             .map(|idx| vec2_add(xy, graph::algo::scroll_offset(&self.widget_graph, idx)))
-            .unwrap_or(xy)
+            .unwrap_or(xy);
+
+        // Positions solved from constraints are already absolute and take precedence.
+        match maybe_id {
+            Some(idx) => {
+                use position::constraint::Variable;
+                let x = self.constraint_values.get(&Variable::X(idx)).cloned().unwrap_or(xy[0]);
+                let y = self.constraint_values.get(&Variable::Y(idx)).cloned().unwrap_or(xy[1]);
+                [x, y]
+            },
+            None => xy,
+        }
     }
 
 
@@ -1059,6 +2032,26 @@ impl Ui {
             updated_widgets.clear();
         }
 
+        // Check for touches that have been held long enough to become a `LongPress` since the last
+        // input was handled.
+        self.check_long_presses();
+
+        // Continue the motion of any kinetically scrolling widgets.
+        self.step_kinetic_scrolls();
+
+        // Tooltips, constraints and drop acceptance must be registered anew each stage.
+        self.tooltips.clear();
+        self.constraints.clear();
+        self.constraint_values.clear();
+        self.maybe_constraint_layout = None;
+        self.rejected_constraints.clear();
+        self.content_fits.clear();
+        self.pending_content_fits.clear();
+        self.is_layout_pending = false;
+        if let Some(ref mut state) = self.maybe_drag {
+            state.acceptors.clear();
+        }
+
         let mut ui_cell = UiCell { ui: self };
 
         // Instantiate the root `Window` `Widget`.
@@ -1245,10 +2238,218 @@ impl<'a> UiCell<'a> {
     pub fn set_mouse_cursor(&mut self, cursor: cursor::MouseCursor) {
         self.ui.mouse_cursor = cursor;
     }
+
+    /// Register the tooltip text for the widget with the given index.
+    ///
+    /// Once the mouse has rested over the widget (or any of its descendants without a tooltip of
+    /// their own) for the `theme.tooltip_delay`, the text is displayed within a `widget::Tooltip`
+    /// near the cursor, above all other widgets. The tooltip is hidden as soon as the mouse moves
+    /// or a button is pressed.
+    ///
+    /// Tooltips must be registered during every `set_widgets` stage in which they should be
+    /// available. Note that the tooltip only appears during the first `set_widgets` stage after the
+    /// delay has elapsed, so the application should continue updating the `Ui` while idle.
+    pub fn tooltip(&mut self, id: widget::Id, text: &str) {
+        self.ui.tooltips.insert(id, text.to_owned());
+    }
+
+    /// Constrain the layout of widgets within the current `set_widgets` stage.
+    ///
+    /// Constraints are linear equalities and inequalities between the edges and dimensions of any
+    /// number of widgets. See the `position::constraint` module for building them.
+    ///
+    /// Before each constrained widget is set, its `Rect` is solved from all registered constraints,
+    /// treating widgets that have already been set during this stage as fixed. Solved attributes
+    /// take precedence over the widget's own positioning and dimensions. Attributes left
+    /// underdetermined by the constraints keep their previous values (or, for dimensions, the
+    /// widget's own dimensions) where possible.
+    ///
+    /// Constraints must be registered during every `set_widgets` stage in which they should apply
+    /// and before the widgets that they constrain are set. Required constraints that conflict with
+    /// one another are ignored and reported by `Ui::rejected_constraints`.
+    pub fn constrain(&mut self, constraint: position::constraint::Constraint) {
+        self.ui.constraints.push(constraint);
+    }
+
+    /// Set the widget capturing the keyboard.
+    ///
+    /// This is useful for widgets that receive keyboard input without first being clicked, e.g.
+    /// popup menus.
+    pub fn keyboard_capture(&mut self, id: widget::Id) {
+        self.ui.keyboard_capture(id);
+    }
+
+    /// Release the keyboard from whichever widget is capturing it.
+    pub fn keyboard_uncapture(&mut self) {
+        if let Some(idx) = self.ui.global_input.current.widget_capturing_keyboard.take() {
+            let event = event::Ui::WidgetUncapturesInputSource(idx, input::Source::Keyboard);
+            self.ui.global_input.push_event(event.into());
+        }
+    }
+
+    /// Begin dragging the payload of the given `drag` from its source widget.
+    ///
+    /// This should be called while the left mouse button is held, usually in response to the
+    /// source widget's first `event::Drag`. Returns `false` and ignores the `drag` if the left
+    /// mouse button is up or another drag is already in progress.
+    ///
+    /// See the `input::drag_drop` module for details.
+    pub fn start_drag(&mut self, drag: input::drag_drop::Drag) -> bool {
+        if self.ui.maybe_drag.is_some() {
+            return false;
+        }
+        let origin = match self.ui.global_input.current.mouse.buttons.left().xy_if_down() {
+            Some(xy) => xy,
+            None => return false,
+        };
+        self.ui.maybe_drag = Some(DragState {
+            drag: drag,
+            origin: origin,
+            acceptors: Vec::new(),
+        });
+        true
+    }
+
+    /// Indicate that the widget with the given index accepts the payload of the drag in progress.
+    ///
+    /// This should be called during each `set_widgets` stage in which `Ui::drag_over` returns a
+    /// drag whose payload the widget accepts. If the drag is released over the widget (or any of
+    /// its descendants that do not accept it themselves), the widget receives an
+    /// `event::DropEvent`.
+    pub fn accept_drop(&mut self, id: widget::Id) {
+        if let Some(ref mut state) = self.ui.maybe_drag {
+            if !state.acceptors.contains(&id) {
+                state.acceptors.push(id);
+            }
+        }
+    }
+
+    /// Cancel the drag and drop in progress, returning its `Drag` if there was one.
+    ///
+    /// Unlike cancelling via the `Escape` key, no `DragCancel` event is produced.
+    pub fn cancel_drag(&mut self) -> Option<input::drag_drop::Drag> {
+        self.ui.maybe_drag.take().map(|state| state.drag)
+    }
+
+    /// Take the payload of the drag that was dropped onto the widget with the given `id` since the
+    /// last `set_widgets` stage if it is of type `T`.
+    ///
+    /// The target should call this upon receiving an `event::DropEvent`. Returns `None` for any
+    /// widget other than the `target` of the drop. The payload is only available until the end of
+    /// the current `set_widgets` stage.
+    pub fn take_dropped_payload<T>(&mut self, id: widget::Id) -> Option<T>
+        where T: std::any::Any,
+    {
+        match self.ui.maybe_dropped.take() {
+            Some((target, drag)) if target == id => match drag.into_payload::<T>() {
+                Ok(payload) => Some(payload),
+                Err(drag) => {
+                    self.ui.maybe_dropped = Some((target, drag));
+                    None
+                },
+            },
+            maybe_dropped => {
+                self.ui.maybe_dropped = maybe_dropped;
+                None
+            },
+        }
+    }
+
+    /// Display the given text within a `widget::Tooltip` near the mouse cursor, above all other
+    /// widgets.
+    fn set_tooltip_at_mouse(&mut self, id: widget::Id, text: &str) {
+        use {Positionable, Sizeable, Widget};
+        let tooltip = widget::Tooltip::new(text);
+        let dim = match tooltip.text_dimensions(self.ui) {
+            Some(dim) => dim,
+            None => return,
+        };
+        let mouse_xy = self.ui.global_input.current.mouse.xy;
+        let xy = tooltip_xy(mouse_xy, dim, [self.ui.win_w, self.ui.win_h]);
+        let window = self.ui.window;
+        tooltip
+            .wh(dim)
+            .xy(xy)
+            .parent(window)
+            .floating(true)
+            .set(id, self);
+
+        // Raise the tooltip above all other floating widgets.
+        let now = self.ui.clock.now();
+        if let Some(container) = self.ui.widget_graph.widget_mut(id) {
+            container.maybe_floating = Some(widget::Floating { time_last_clicked: now });
+        }
+    }
 }
 
 impl<'a> Drop for UiCell<'a> {
     fn drop(&mut self) {
+        // Now that all children have been set, fit widgets sized to their content around them.
+        self.ui.fit_widgets_to_content();
+
+        // Rebuild the focus chain from the focusable widgets that were updated. Widgets with a
+        // tab index come first, followed by the rest in the order in which they were set.
+        {
+            let Ui { ref widget_graph, ref updated_widgets, ref mut focus_chain, .. } = *self.ui;
+            focus_chain.clear();
+            for &id in updated_widgets {
+                if let Some(container) = widget_graph.widget(id) {
+                    if container.is_focusable {
+                        focus_chain.push(id);
+                    }
+                }
+            }
+            focus_chain.sort_by_key(|&id| {
+                let container = widget_graph.widget(id).expect("no container for focusable widget");
+                let tab_index = container.maybe_tab_index;
+                (tab_index.is_none(), tab_index, container.instantiation_order_idx)
+            });
+        }
+
+        // If keyboard capture was moved via the focus chain, draw a ring around the widget
+        // capturing the keyboard.
+        if self.ui.is_focus_ring_visible {
+            let maybe_focused = self.ui.global_input.current.widget_capturing_keyboard
+                .and_then(|id| if self.ui.updated_widgets.contains(&id) { Some(id) } else { None });
+            let maybe_rect = maybe_focused.and_then(|id| self.ui.rect_of(id).map(|r| (id, r)));
+            if let Some((focused, rect)) = maybe_rect {
+                use {Positionable, Widget};
+                let parent = self.ui.widget_graph.depth_parent(focused).unwrap_or(self.ui.window);
+                let thickness = self.ui.theme.focus_ring_thickness;
+                let line_style = widget::line::Style::new()
+                    .thickness(thickness)
+                    .color(self.ui.theme.focus_ring_color);
+                let dim = [rect.w() + thickness * 2.0, rect.h() + thickness * 2.0];
+                let focus_ring = self.ui.focus_ring;
+                widget::Rectangle::outline_styled(dim, line_style)
+                    .xy_relative_to(focused, [0.0, 0.0])
+                    .graphics_for(focused)
+                    .parent(parent)
+                    .set(focus_ring, self);
+            }
+        }
+
+        // Show the tooltip of the widget under the mouse once the mouse has rested for long enough.
+        let maybe_tooltip_target = self.ui.find_tooltip_target();
+        self.ui.maybe_tooltip_target = maybe_tooltip_target;
+        if let Some(target) = maybe_tooltip_target {
+            let text = self.ui.tooltips[&target].clone();
+            let tooltip = self.ui.tooltip;
+            self.set_tooltip_at_mouse(tooltip, &text);
+        }
+
+        // Show the preview of the drag and drop in progress.
+        let maybe_label = self.ui.maybe_drag.as_ref().and_then(|state| {
+            match *state.drag.get_preview() {
+                input::drag_drop::Preview::Label(ref text) => Some(text.clone()),
+                input::drag_drop::Preview::None => None,
+            }
+        });
+        if let Some(text) = maybe_label {
+            let drag_preview = self.ui.drag_preview;
+            self.set_tooltip_at_mouse(drag_preview, &text);
+        }
+
         // We'll need to re-draw if we have gained or lost widgets.
         let changed = self.ui.updated_widgets != self.ui.prev_updated_widgets;
         if changed {
@@ -1268,6 +2469,9 @@ impl<'a> Drop for UiCell<'a> {
             depth_order.update(widget_graph, window, updated_widgets);
         }
 
+        // Any dropped payload that was not taken is discarded.
+        self.ui.maybe_dropped = None;
+
         // Reset the global input state. Note that this is the **only** time this should be called.
         self.ui.global_input.clear_events_and_update_start_state();
 
@@ -1297,6 +2501,24 @@ impl<'a> AsRef<Ui> for UiCell<'a> {
     }
 }
 
+/// The position of a tooltip with the given dimensions for the mouse at `mouse_xy`.
+///
+/// The tooltip is placed below and to the right of the cursor, flipping to the other side of the
+/// cursor along either axis where it would otherwise leave the window.
+fn tooltip_xy(mouse_xy: Point, dim: Dimensions, win_dim: Dimensions) -> Point {
+    const CURSOR_OFFSET: Scalar = 16.0;
+    let axis = |mouse: Scalar, len: Scalar, win_len: Scalar, forwards: bool| {
+        let half_win = win_len / 2.0;
+        let after = mouse + CURSOR_OFFSET + len / 2.0;
+        let before = mouse - CURSOR_OFFSET - len / 2.0;
+        let (preferred, other) = if forwards { (after, before) } else { (before, after) };
+        let fits = |pos: Scalar| pos - len / 2.0 >= -half_win && pos + len / 2.0 <= half_win;
+        let pos = if fits(preferred) || !fits(other) { preferred } else { other };
+        utils::clamp(pos, -half_win + len / 2.0, half_win - len / 2.0)
+    };
+    [axis(mouse_xy[0], dim[0], win_dim[0], true), axis(mouse_xy[1], dim[1], win_dim[1], false)]
+}
+
 /// A function for retrieving the `&mut Ui<B>` from a `UiCell<B>`.
 ///
 /// This function is only for internal use to allow for some `Ui` type acrobatics in order to
@@ -1311,6 +2533,235 @@ pub fn widget_graph_mut(ui: &mut Ui) -> &mut Graph {
 }
 
 
+/// Register the widget to be fit around the bounding box of its children once they have been set.
+///
+/// Each axis that is `Some` is fit to the children, padded at both ends by the given scalar, and
+/// clamped to the given minimum and maximum length for that axis.
+///
+/// This function is only for internal use by `widget::set_widget`.
+pub fn fit_to_content(ui: &mut Ui,
+                      id: widget::Id,
+                      pads: [Option<Scalar>; 2],
+                      bounds: [(Option<Scalar>, Option<Scalar>); 2])
+{
+    let fit = ContentFit { id: id, pads: pads, bounds: bounds };
+    ui.content_fits.push(fit);
+    ui.pending_content_fits.push(fit);
+}
+
+/// Fit each widget registered via `fit_to_content` whose children have all been set.
+///
+/// A widget's children are considered to have been set once some widget that is not one of its
+/// descendants is set, i.e. a widget whose parent is given by `maybe_parent_id`. Fitting the
+/// widget at this point ensures that widgets positioned relative to it (e.g. `down_from`) see
+/// its fitted `Rect`.
+///
+/// This function is only for internal use by `widget::set_widget`.
+pub fn fit_finished_widgets_to_content(ui: &mut Ui, maybe_parent_id: Option<widget::Id>) {
+    while let Some(&fit) = ui.pending_content_fits.last() {
+        let id = fit.id;
+        let graph = &ui.widget_graph;
+        let is_ancestor = maybe_parent_id
+            .map(|parent| parent == id || graph.does_recursive_depth_edge_exist(id, parent))
+            .unwrap_or(false);
+        if is_ancestor {
+            break;
+        }
+        ui.pending_content_fits.pop();
+        ui.fit_widget_to_content(fit);
+    }
+}
+
+impl ConstraintLayout {
+
+    fn new() -> Self {
+        ConstraintLayout {
+            solver: position::constraint::Solver::new(),
+            num_constraints: 0,
+            ids: Vec::new(),
+            edited: fnv::FnvHashSet::default(),
+            fixed: fnv::FnvHashSet::default(),
+        }
+    }
+
+    /// Require the widget with the given `id` to occupy the given `rect`.
+    fn fix(&mut self, id: widget::Id, rect: Rect) -> Result<(), position::constraint::Error> {
+        use position::constraint::Expression;
+        self.fixed.insert(id);
+        for &(var, value) in &rect_variables(id, rect) {
+            self.solver.add_constraint(&Expression::from(var).eq(value))?;
+        }
+        Ok(())
+    }
+
+    /// Require the width and height of the widget with the given `id` to lie within `bounds`.
+    fn bound(&mut self,
+             id: widget::Id,
+             bounds: [(Option<Scalar>, Option<Scalar>); 2])
+        -> Result<(), position::constraint::Error>
+    {
+        use position::constraint::{Expression, Variable};
+        let vars = [Variable::W(id), Variable::H(id)];
+        for (&var, &(maybe_min, maybe_max)) in vars.iter().zip(bounds.iter()) {
+            if let Some(min) = maybe_min {
+                self.solver.add_constraint(&Expression::from(var).ge(min))?;
+            }
+            if let Some(max) = maybe_max {
+                self.solver.add_constraint(&Expression::from(var).le(max))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Weakly suggest that the widget with the given `id` occupies the given `rect`.
+    fn prefer(&mut self, id: widget::Id, rect: Rect) {
+        use position::constraint::WEAK;
+        let is_edited = !self.edited.insert(id);
+        for &(var, value) in &rect_variables(id, rect) {
+            if !is_edited {
+                let _ = self.solver.add_edit_variable(var, WEAK);
+            }
+            let _ = self.solver.suggest_value(var, value);
+        }
+    }
+
+}
+
+/// Each of the constraint variables describing the given widget's `Rect` paired with its value.
+fn rect_variables(id: widget::Id, rect: Rect) -> [(position::constraint::Variable, Scalar); 4] {
+    use position::constraint::Variable;
+    [
+        (Variable::X(id), rect.x()),
+        (Variable::Y(id), rect.y()),
+        (Variable::W(id), rect.w()),
+        (Variable::H(id), rect.h()),
+    ]
+}
+
+/// Solve the registered constraints for the widget with the given `id` about to be set.
+///
+/// Returns the widget's dimensions, overridden by any solved width and height. The solved
+/// position is stored for use by `Ui::calc_xy`.
+///
+/// The widget's minimum and maximum width and height are given by `bounds`. The solved width and
+/// height are required to lie within them.
+///
+/// A single solver is used for the whole `set_widgets` stage. Each time a constrained widget is
+/// set, any newly registered constraints are added to it, the widgets that have been set since
+/// are fixed in place and the widget's preferred `Rect` is suggested via its edit variables. The
+/// solver is only rebuilt if a widget was set somewhere the solver did not expect it, or if the
+/// widget's bounds conflict with the constraints, in which case the conflicting constraints are
+/// rejected instead.
+///
+/// Rejected constraints are recorded for `Ui::rejected_constraints`.
+///
+/// This function is only for internal use by `widget::set_widget`.
+pub fn solve_constraints(ui: &mut Ui,
+                         id: widget::Id,
+                         x_position: Position,
+                         y_position: Position,
+                         dim: Dimensions,
+                         bounds: [(Option<Scalar>, Option<Scalar>); 2],
+                         place_on_kid_area: bool) -> Dimensions
+{
+    use position::constraint::Variable;
+
+    ui.constraint_values.clear();
+    let is_constrained = ui.constraints.iter()
+        .any(|c| c.expression.terms.iter().any(|&(v, _)| v.widget_id() == id));
+    if !is_constrained {
+        return dim;
+    }
+
+    // Where the widget would be placed if it were not constrained.
+    let xy = ui.calc_xy(Some(id), x_position, y_position, dim, place_on_kid_area);
+    let natural_rect = Rect::from_xy_dim(xy, dim);
+
+    let Ui {
+        ref constraints,
+        ref mut maybe_constraint_layout,
+        ref mut rejected_constraints,
+        ref mut constraint_values,
+        ref updated_widgets,
+        ref widget_graph,
+        ..
+    } = *ui;
+    let rect_of = |id: widget::Id| widget_graph.widget(id).map(|container| container.rect);
+    let is_set = |other: widget::Id| other != id && updated_widgets.contains(&other);
+    let layout = maybe_constraint_layout.get_or_insert_with(ConstraintLayout::new);
+
+    // Update the solver with the constraints and widgets that are new since it was last used.
+    let mut is_consistent = true;
+    {
+        let new_ids = constraints[layout.num_constraints..].iter()
+            .flat_map(|c| c.expression.terms.iter().map(|&(var, _)| var.widget_id()));
+        for other in new_ids {
+            if !layout.ids.contains(&other) {
+                layout.ids.push(other);
+            }
+        }
+        let newly_set: Vec<_> = layout.ids.iter().cloned()
+            .filter(|&other| is_set(other) && !layout.fixed.contains(&other))
+            .collect();
+        for other in newly_set {
+            if let Some(rect) = rect_of(other) {
+                is_consistent &= layout.fix(other, rect).is_ok();
+            }
+        }
+        for constraint in &constraints[layout.num_constraints..] {
+            if let Err(err) = layout.solver.add_constraint(constraint) {
+                rejected_constraints.push((constraint.clone(), err));
+            }
+        }
+        layout.num_constraints = constraints.len();
+        is_consistent &= layout.bound(id, bounds).is_ok();
+    }
+
+    // Otherwise, rebuild the solver so that the set widgets and the widget's bounds take
+    // precedence over the registered constraints.
+    if !is_consistent {
+        let ids = std::mem::replace(layout, ConstraintLayout::new()).ids;
+        rejected_constraints.clear();
+        for &other in ids.iter().filter(|&&other| is_set(other)) {
+            if let Some(rect) = rect_of(other) {
+                let _ = layout.fix(other, rect);
+            }
+        }
+        let _ = layout.bound(id, bounds);
+        for constraint in constraints {
+            if let Err(err) = layout.solver.add_constraint(constraint) {
+                rejected_constraints.push((constraint.clone(), err));
+            }
+        }
+        layout.num_constraints = constraints.len();
+        layout.ids = ids;
+    }
+
+    // The other widgets would prefer to stay where they were last placed, while this widget
+    // would prefer to be placed where it would be if it were not constrained.
+    let unplaced: Vec<_> = layout.ids.iter().cloned()
+        .filter(|&other| other != id && !layout.fixed.contains(&other))
+        .filter(|other| !layout.edited.contains(other))
+        .collect();
+    for other in unplaced {
+        if let Some(rect) = rect_of(other) {
+            layout.prefer(other, rect);
+        }
+    }
+    layout.prefer(id, natural_rect);
+
+    for &var in &[Variable::X(id), Variable::Y(id)] {
+        if let Some(value) = layout.solver.value(var) {
+            constraint_values.insert(var, value);
+        }
+    }
+    let w = layout.solver.value(Variable::W(id)).unwrap_or(dim[0]).max(0.0);
+    let h = layout.solver.value(Variable::H(id)).unwrap_or(dim[1]).max(0.0);
+    [position::clamp_length(w, bounds[0].0, bounds[0].1),
+     position::clamp_length(h, bounds[1].0, bounds[1].1)]
+}
+
+
 /// Infer a widget's `Depth` parent by examining it's *x* and *y* `Position`s.
 ///
 /// When a different parent may be inferred from either `Position`, the *x* `Position` is favoured.
@@ -1346,6 +2797,34 @@ pub fn infer_parent_unchecked(ui: &Ui, x_pos: Position, y_pos: Position) -> widg
 }
 
 
+/// The average velocity in offset per second of the given scroll input samples.
+///
+/// Returns zero if the latest sample was received more than `KINETIC_VELOCITY_WINDOW_MS` before
+/// `now`, i.e. the input came to a halt before it was released.
+fn kinetic_velocity(samples: &[(std::time::Instant, [Scalar; 2])],
+                    now: std::time::Instant) -> [Scalar; 2]
+{
+    let window = std::time::Duration::from_millis(KINETIC_VELOCITY_WINDOW_MS);
+    let (first, last) = match (samples.first(), samples.last()) {
+        (Some(&(first, _)), Some(&(last, _))) => (first, last),
+        _ => return [0.0, 0.0],
+    };
+    let secs = duration_secs(last.duration_since(first));
+    if now.duration_since(last) > window || secs <= 0.0 {
+        return [0.0, 0.0];
+    }
+    // The first sample's delta occurred before the measured duration began.
+    let total = samples[1..].iter().fold([0.0, 0.0], |total, &(_, delta)| {
+        utils::vec2_add(total, delta)
+    });
+    [total[0] / secs, total[1] / secs]
+}
+
+/// Convert the given `Duration` to seconds.
+fn duration_secs(duration: std::time::Duration) -> Scalar {
+    duration.as_secs() as Scalar + duration.subsec_nanos() as Scalar * 1e-9
+}
+
 /// Cache some `PreUpdateCache` widget data into the widget graph.
 /// Set the widget that is being cached as the new `prev_widget`.
 /// Set the widget's parent as the new `current_parent`.
@@ -1353,6 +2832,21 @@ pub fn pre_update_cache(ui: &mut Ui, widget: widget::PreUpdateCache) {
     ui.maybe_prev_widget_id = Some(widget.id);
     ui.maybe_current_parent_id = widget.maybe_parent_id;
     let widget_id = widget.id;
+
+    // Ensure that kinetically scrolling widgets bounce back from any overscroll, whatever its
+    // source.
+    let is_overscrolled = widget.maybe_x_scroll_state.map(|s| s.overscroll != 0.0).unwrap_or(false)
+        || widget.maybe_y_scroll_state.map(|s| s.overscroll != 0.0).unwrap_or(false);
+    if is_overscrolled && !ui.kinetic_scrolls.contains_key(&widget_id) {
+        let now = ui.clock.now();
+        ui.kinetic_scrolls.insert(widget_id, KineticScroll {
+            samples: Vec::new(),
+            has_input: false,
+            velocity: [0.0, 0.0],
+            last_step: now,
+        });
+    }
+
     ui.widget_graph.pre_update_cache(ui.window, widget, ui.updated_widgets.len());
 
     // Add the widget's `widget::Id` to the set of updated widgets.
@@ -1,8 +1,8 @@
 //! The `Button` widget and related items.
 
-use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Sizeable, UiCell, Widget};
+use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Sizeable, Ui, UiCell, Widget};
 use image;
-use position::{self, Align, Rect, Scalar};
+use position::{self, Align, Dimensions, Rect, Scalar};
 use text;
 use widget;
 
@@ -294,6 +294,14 @@ impl<'a> Widget for Button<'a, Flat> {
         self.style.clone()
     }
 
+    fn content_wh(&self, ui: &Ui) -> Option<Dimensions> {
+        Some(label_wh(self.maybe_label, &self.style, ui))
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -333,6 +341,14 @@ impl<'a> Widget for Button<'a, Image> {
         self.style.clone()
     }
 
+    fn content_wh(&self, ui: &Ui) -> Option<Dimensions> {
+        Some(label_wh(self.maybe_label, &self.style, ui))
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
     /// Update the state of the Button.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -410,6 +426,18 @@ fn bordered_rectangle(button_id: widget::Id, rectangle_id: widget::Id,
         .set(rectangle_id, ui);
 }
 
+/// The dimensions of the button's label, or zero if there is no label.
+fn label_wh(maybe_label: Option<&str>, style: &Style, ui: &Ui) -> Dimensions {
+    let font_size = style.label_font_size(&ui.theme);
+    let maybe_font = style.label_font_id(&ui.theme)
+        .or(ui.fonts.ids().next())
+        .and_then(|id| ui.fonts.get(id));
+    match (maybe_label, maybe_font) {
+        (Some(label), Some(font)) => text::dimensions(label, font, font_size, 0.0),
+        _ => [0.0, 0.0],
+    }
+}
+
 fn label(button_id: widget::Id, label_id: widget::Id,
          label: &str, style: &Style, ui: &mut UiCell)
 {
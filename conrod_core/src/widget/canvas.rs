@@ -323,31 +323,13 @@ impl<'a> Widget for Canvas<'a> {
         // If we were given some child canvas splits, we should instantiate them.
         if let Some((direction, splits)) = maybe_splits {
 
-            let (total_abs, total_weight) =
-                splits.iter().fold((0.0, 0.0), |(abs, weight), &(_, split)| {
-                    match split.style.length(ui.theme()) {
-                        Length::Absolute(a) => (abs + a, weight),
-                        Length::Weight(w) => (abs, weight + w),
-                    }
-                });
-
             // No need to calculate kid_area again, we'll just get it from the graph.
             let kid_area = ui.kid_area_of(id).expect("No KidArea found");
             let kid_area_range = match direction {
                 Direction::X(_) => kid_area.x,
                 Direction::Y(_) => kid_area.y,
             };
-
-            let total_length = kid_area_range.len();
-            let non_abs_length = (total_length - total_abs).max(0.0);
-            let weight_normaliser = 1.0 / total_weight;
-
-            let length = |split: &Self, ui: &UiCell| -> Scalar {
-                match split.style.length(ui.theme()) {
-                    Length::Absolute(length) => length,
-                    Length::Weight(weight) => weight * weight_normaliser * non_abs_length,
-                }
-            };
+            let lengths = split_lengths(splits, direction, kid_area_range.len(), &ui);
 
             let set_split = |split_id: widget::Id, split: Canvas<'a>, ui: &mut UiCell| {
                 split.parent(id).set(split_id, ui);
@@ -358,7 +340,7 @@ impl<'a> Widget for Canvas<'a> {
 
                 Direction::X(direction) => match direction {
                     Forwards => for (i, &(split_id, split)) in splits.iter().enumerate() {
-                        let w = length(&split, &ui);
+                        let w = lengths[i];
                         let split = match i {
                             0 => split.h(kid_area.h()).mid_left_of(id),
                             _ => split.right(0.0),
@@ -366,7 +348,7 @@ impl<'a> Widget for Canvas<'a> {
                         set_split(split_id, split, &mut ui);
                     },
                     Backwards => for (i, &(split_id, split)) in splits.iter().enumerate() {
-                        let w = length(&split, &ui);
+                        let w = lengths[i];
                         let split = match i {
                             0 => split.h(kid_area.h()).mid_right_of(id),
                             _ => split.left(0.0),
@@ -377,7 +359,7 @@ impl<'a> Widget for Canvas<'a> {
 
                 Direction::Y(direction) => match direction {
                     Forwards => for (i, &(split_id, split)) in splits.iter().enumerate() {
-                        let h = length(&split, &ui);
+                        let h = lengths[i];
                         let split = match i {
                             0 => split.w(kid_area.w()).mid_bottom_of(id),
                             _ => split.up(0.0),
@@ -385,7 +367,7 @@ impl<'a> Widget for Canvas<'a> {
                         set_split(split_id, split, &mut ui);
                     },
                     Backwards => for (i, &(split_id, split)) in splits.iter().enumerate() {
-                        let h = length(&split, &ui);
+                        let h = lengths[i];
                         let split = match i {
                             0 => split.w(kid_area.w()).mid_top_of(id),
                             _ => split.down(0.0),
@@ -449,3 +431,63 @@ impl<'a> ::label::Labelable<'a> for Canvas<'a> {
     }
 }
 
+
+/// The length of each split along the direction of the flow.
+///
+/// Absolute lengths are used as given while the remaining length is shared between the weighted
+/// splits. The lengths of all splits are clamped to their minimum and maximum lengths, with any
+/// length gained or lost by clamping a weighted split shared between the others.
+fn split_lengths(splits: ListOfSplits, direction: Direction, total_length: Scalar, ui: &Ui)
+    -> Vec<Scalar>
+{
+    let clamp = |split: &Canvas, length: Scalar| match direction {
+        Direction::X(_) => split.clamp_w(length, ui),
+        Direction::Y(_) => split.clamp_h(length, ui),
+    };
+
+    let mut lengths = vec![0.0; splits.len()];
+    let mut is_resolved = vec![false; splits.len()];
+    let mut remaining = total_length;
+    for (i, &(_, ref split)) in splits.iter().enumerate() {
+        if let Length::Absolute(length) = split.style.length(&ui.theme) {
+            lengths[i] = clamp(split, length);
+            is_resolved[i] = true;
+            remaining -= lengths[i];
+        }
+    }
+
+    // Share the remaining length by weight until no more splits need clamping.
+    loop {
+        let weight = |split: &Canvas| match split.style.length(&ui.theme) {
+            Length::Weight(weight) => weight,
+            Length::Absolute(_) => 0.0,
+        };
+        let total_weight: Scalar = splits.iter().enumerate()
+            .filter(|&(i, _)| !is_resolved[i])
+            .map(|(_, &(_, ref split))| weight(split))
+            .sum();
+        if total_weight <= 0.0 {
+            break;
+        }
+        let available = remaining.max(0.0);
+        let mut was_clamped = false;
+        for (i, &(_, ref split)) in splits.iter().enumerate() {
+            if is_resolved[i] {
+                continue;
+            }
+            let length = weight(split) / total_weight * available;
+            let clamped = clamp(split, length);
+            lengths[i] = clamped;
+            if clamped != length {
+                is_resolved[i] = true;
+                remaining -= clamped;
+                was_clamped = true;
+            }
+        }
+        if !was_clamped {
+            break;
+        }
+    }
+
+    lengths
+}
@@ -0,0 +1,1006 @@
+//! A docking system for arranging panels into tabbed groups, splits and floating windows.
+//!
+//! The arrangement of the panels is described by a `Layout`, which is owned by the user so that it
+//! may be serialized, saved and later restored. The `Dock` widget displays a `Layout` and reports
+//! the user's interactions with it as `Action`s, which may be applied to the `Layout` via
+//! `Layout::apply`.
+
+use {
+    Borderable,
+    Color,
+    Colorable,
+    Dimensions,
+    FontSize,
+    Labelable,
+    Point,
+    Positionable,
+    Rect,
+    Scalar,
+    Sizeable,
+    UiCell,
+    Widget,
+};
+use color;
+use input;
+use input::drag_drop::Drag;
+use std;
+use std::collections::HashMap;
+use utils::{self, vec2_add, vec2_sub};
+use widget;
+
+
+/// Displays a `Layout` of panels, each identified by a unique name.
+///
+/// Docked panels fill the area of the `Dock`, split into rows and columns of tab groups. Each
+/// group shows a tab for every one of its panels along with the content of its selected panel.
+/// Floating windows are displayed above the docked panels and may be moved via their title bar,
+/// remaining within the area of the `Dock`.
+///
+/// Dragging a tab shows docking guides over the group beneath the mouse. Dropping the tab onto the
+/// central guide or a group's tab bar adds the panel to that group, while dropping it onto one of
+/// the guides surrounding the centre splits the group and docks the panel to that side. Dropping
+/// it anywhere else over a group moves the panel into a new floating window.
+///
+/// The `Dock` does not modify the `Layout` itself. Instead, it produces a `Panels` iterator
+/// yielding an `Element` for the selected panel of each group, via which any widget may be set
+/// within the panel's area, along with the `Action`s that should be applied to the `Layout`.
+#[derive(WidgetCommon_)]
+pub struct Dock<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    layout: &'a Layout,
+    style: Style,
+}
+
+/// The arrangement of the panels displayed by a `Dock`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Layout {
+    /// The panels docked within the area of the `Dock`, if any.
+    pub root: Option<Node>,
+    /// The floating windows, from back to front.
+    pub windows: Vec<Window>,
+}
+
+/// A node within the tree of docked panels.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Node {
+    /// A group of panels displayed as tabs.
+    Group(Group),
+    /// Two nodes side by side, the `left` taking the given `ratio` of the width.
+    Row {
+        /// The share of the width given to the `left` node.
+        ratio: Scalar,
+        /// The node on the left.
+        left: Box<Node>,
+        /// The node on the right.
+        right: Box<Node>,
+    },
+    /// Two nodes one above the other, the `top` taking the given `ratio` of the height.
+    Column {
+        /// The share of the height given to the `top` node.
+        ratio: Scalar,
+        /// The node on top.
+        top: Box<Node>,
+        /// The node below.
+        bottom: Box<Node>,
+    },
+}
+
+/// A group of panels displayed as tabs, of which only the selected panel's content is visible.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Group {
+    /// The names of the panels in the order in which their tabs are displayed.
+    pub panels: Vec<String>,
+    /// The index of the selected panel.
+    pub selected: usize,
+}
+
+/// A group of panels floating above the docked panels.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Window {
+    /// The position of the centre of the window relative to the centre of the `Dock`.
+    pub xy: Point,
+    /// The dimensions of the window including its title bar.
+    pub dim: Dimensions,
+    /// The panels within the window.
+    pub group: Group,
+}
+
+/// The area of a group onto which a panel may be docked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Zone {
+    /// Among the tabs of the group.
+    Center,
+    /// Beside the group on the left.
+    Left,
+    /// Beside the group on the right.
+    Right,
+    /// Above the group.
+    Top,
+    /// Below the group.
+    Bottom,
+}
+
+/// Where a panel should be docked.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Target {
+    /// Within the given `Zone` of the entire docked area.
+    ///
+    /// `Zone::Center` adds the panel to the first docked group, or creates it if there is none.
+    Dock(Zone),
+    /// Within the given `Zone` of the group containing the named panel.
+    Group(String, Zone),
+}
+
+/// A change to a `Layout` requested by the user via a `Dock`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    /// Select the named panel within its group.
+    Select(String),
+    /// Move the named panel to the given `Target`.
+    Dock {
+        /// The name of the panel.
+        panel: String,
+        /// Where the panel should be docked.
+        target: Target,
+    },
+    /// Move the named panel into a new floating window.
+    Float {
+        /// The name of the panel.
+        panel: String,
+        /// The position of the window relative to the centre of the `Dock`.
+        xy: Point,
+        /// The dimensions of the window.
+        dim: Dimensions,
+    },
+    /// Move the floating window at the given index.
+    MoveWindow {
+        /// The index of the window within `Layout::windows`.
+        index: usize,
+        /// The new position of the window relative to the centre of the `Dock`.
+        xy: Point,
+    },
+}
+
+/// Unique styling for the `Dock`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The color of each group's area and tabs.
+    #[conrod(default = "theme.background_color")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding each group and tab.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the border surrounding each group and tab.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the tab and title bar labels.
+    #[conrod(default = "theme.label_color")]
+    pub label_color: Option<Color>,
+    /// The font size of the tab and title bar labels.
+    #[conrod(default = "theme.font_size_small")]
+    pub label_font_size: Option<FontSize>,
+    /// The height of each group's tab bar.
+    ///
+    /// If `None`, the tab bar fits the height of the label font.
+    #[conrod(default = "None")]
+    pub bar_thickness: Option<Option<Scalar>>,
+    /// The color of the docking guides shown while dragging a panel.
+    #[conrod(default = "color::LIGHT_BLUE")]
+    pub target_color: Option<Color>,
+    /// The dimensions of the floating windows created by dragging a panel out of its group.
+    #[conrod(default = "[240.0, 180.0]")]
+    pub window_dim: Option<Dimensions>,
+}
+
+widget_ids! {
+    struct Ids {
+        empty,
+        preview,
+        guides[],
+    }
+}
+
+/// The state of the `Dock`.
+pub struct State {
+    ids: Ids,
+    /// A `widget::Id` for the area of each group, docked groups first.
+    area_ids: widget::id::List,
+    /// A `widget::Id` for each floating window.
+    window_ids: widget::id::List,
+    /// A `widget::Id` for the title bar of each floating window.
+    title_ids: widget::id::List,
+    /// The `widget::Id`s associated with each panel, by name.
+    panel_ids: HashMap<String, PanelIds>,
+    /// The index of the floating window whose title bar is being dragged along with the position
+    /// of the window when the drag began.
+    maybe_window_drag: Option<(usize, Point)>,
+}
+
+/// The `widget::Id`s associated with a single panel.
+#[derive(Copy, Clone, Debug)]
+struct PanelIds {
+    tab: widget::Id,
+    content: widget::Id,
+}
+
+/// The payload of a panel being dragged by its tab.
+struct Payload {
+    dock: widget::Id,
+    panel: String,
+}
+
+/// The event produced by the `Dock`, yielding an `Element` for the selected panel of each group.
+#[derive(Clone, Debug)]
+pub struct Panels<'a> {
+    dock_id: widget::Id,
+    actions: Vec<Action>,
+    elements: std::vec::IntoIter<Element<'a>>,
+}
+
+/// Data necessary for instantiating a widget within the area of a single panel.
+#[derive(Copy, Clone, Debug)]
+pub struct Element<'a> {
+    /// The name of the panel.
+    pub panel: &'a str,
+    /// The id generated for the widget.
+    pub widget_id: widget::Id,
+    /// The width of the element.
+    pub w: Scalar,
+    /// The height of the element.
+    pub h: Scalar,
+    /// The *x* position of the element relative to the centre of its group.
+    pub rel_x: Scalar,
+    /// The *y* position of the element relative to the centre of its group.
+    pub rel_y: Scalar,
+    /// The id of the area of the panel's group, used for positioning.
+    area_id: widget::Id,
+}
+
+/// The padding between the edge of a tab and its label.
+const TAB_BAR_LABEL_PADDING: Scalar = 4.0;
+
+/// The side length of each docking guide.
+const GUIDE_SIZE: Scalar = 28.0;
+
+/// The distance between the centres of adjacent docking guides.
+const GUIDE_STEP: Scalar = GUIDE_SIZE + 6.0;
+
+
+impl Layout {
+
+    /// A layout without any panels.
+    pub fn new() -> Self {
+        Layout {
+            root: None,
+            windows: Vec::new(),
+        }
+    }
+
+    /// A layout with the given tree of docked panels.
+    pub fn docked(root: Node) -> Self {
+        Layout {
+            root: Some(root),
+            windows: Vec::new(),
+        }
+    }
+
+    /// Whether or not the named panel is within the layout.
+    pub fn contains(&self, panel: &str) -> bool {
+        self.root.as_ref().map(|root| root.contains(panel)).unwrap_or(false)
+            || self.windows.iter().any(|window| window.group.contains(panel))
+    }
+
+    /// The group containing the named panel, whether docked or floating.
+    pub fn group_of(&self, panel: &str) -> Option<&Group> {
+        self.root.as_ref()
+            .and_then(|root| root.group_of(panel))
+            .or_else(|| {
+                self.windows.iter()
+                    .map(|window| &window.group)
+                    .find(|group| group.contains(panel))
+            })
+    }
+
+    fn group_of_mut(&mut self, panel: &str) -> Option<&mut Group> {
+        let Layout { ref mut root, ref mut windows } = *self;
+        match root.as_mut().and_then(|root| root.group_of_mut(panel)) {
+            Some(group) => Some(group),
+            None => windows.iter_mut()
+                .map(|window| &mut window.group)
+                .find(|group| group.contains(panel)),
+        }
+    }
+
+    /// Select the named panel within its group.
+    pub fn select(&mut self, panel: &str) {
+        if let Some(group) = self.group_of_mut(panel) {
+            if let Some(index) = group.panels.iter().position(|p| p == panel) {
+                group.selected = index;
+            }
+        }
+    }
+
+    /// Remove the named panel from the layout, returning whether or not it was found.
+    ///
+    /// Groups left without any panels are removed, along with any windows containing them, and
+    /// the remaining node of their split takes the split's place.
+    pub fn remove(&mut self, panel: &str) -> bool {
+        if !self.contains(panel) {
+            return false;
+        }
+        self.root = self.root.take().and_then(|root| root.without(panel));
+        for window in &mut self.windows {
+            window.group.remove(panel);
+        }
+        self.windows.retain(|window| !window.group.panels.is_empty());
+        true
+    }
+
+    /// Dock the named panel at the given `Target`, removing it from wherever it was before.
+    ///
+    /// Panels may only be docked beside groups within the docked area. Docking a panel beside the
+    /// group of a floating window adds it to the window's group instead. Nothing happens if the
+    /// target group does not exist, or if it contains only the panel being docked.
+    pub fn dock(&mut self, panel: &str, target: &Target) {
+        match *target {
+
+            Target::Dock(zone) => {
+                self.remove(panel);
+                let group = Node::Group(Group::new(&[panel]));
+                self.root = match self.root.take() {
+                    None => Some(group),
+                    Some(mut root) => match zone {
+                        Zone::Center => {
+                            root.first_group_mut().push(panel);
+                            Some(root)
+                        },
+                        side => Some(beside(root, group, side)),
+                    },
+                };
+            },
+
+            Target::Group(ref other, zone) => {
+                if other == panel || !self.contains(other) {
+                    self.select(panel);
+                    return;
+                }
+                self.remove(panel);
+                let is_docked = self.root.as_ref().map(|root| root.contains(other)).unwrap_or(false);
+                if zone == Zone::Center || !is_docked {
+                    if let Some(group) = self.group_of_mut(other) {
+                        group.push(panel);
+                    }
+                } else if let Some(root) = self.root.take() {
+                    self.root = Some(root.split(other, panel, zone));
+                }
+            },
+
+        }
+    }
+
+    /// Move the named panel into a new floating window, removing it from wherever it was before.
+    pub fn float(&mut self, panel: &str, xy: Point, dim: Dimensions) {
+        self.remove(panel);
+        self.windows.push(Window {
+            xy: xy,
+            dim: dim,
+            group: Group::new(&[panel]),
+        });
+    }
+
+    /// Move the floating window at the given index.
+    pub fn move_window(&mut self, index: usize, xy: Point) {
+        if let Some(window) = self.windows.get_mut(index) {
+            window.xy = xy;
+        }
+    }
+
+    /// Apply the given `Action` produced by a `Dock` displaying the layout.
+    pub fn apply(&mut self, action: &Action) {
+        match *action {
+            Action::Select(ref panel) => self.select(panel),
+            Action::Dock { ref panel, ref target } => self.dock(panel, target),
+            Action::Float { ref panel, xy, dim } => self.float(panel, xy, dim),
+            Action::MoveWindow { index, xy } => self.move_window(index, xy),
+        }
+    }
+
+}
+
+
+impl Default for Layout {
+    fn default() -> Self {
+        Layout::new()
+    }
+}
+
+
+impl Node {
+
+    /// A group of the given panels with the first selected.
+    pub fn group(panels: &[&str]) -> Self {
+        Node::Group(Group::new(panels))
+    }
+
+    /// Two nodes side by side, the `left` taking the given `ratio` of the width.
+    pub fn row(ratio: Scalar, left: Node, right: Node) -> Self {
+        Node::Row { ratio: ratio, left: Box::new(left), right: Box::new(right) }
+    }
+
+    /// Two nodes one above the other, the `top` taking the given `ratio` of the height.
+    pub fn column(ratio: Scalar, top: Node, bottom: Node) -> Self {
+        Node::Column { ratio: ratio, top: Box::new(top), bottom: Box::new(bottom) }
+    }
+
+    /// Whether or not the named panel is within the node.
+    pub fn contains(&self, panel: &str) -> bool {
+        self.group_of(panel).is_some()
+    }
+
+    /// The group within the node containing the named panel.
+    pub fn group_of(&self, panel: &str) -> Option<&Group> {
+        match *self {
+            Node::Group(ref group) => if group.contains(panel) { Some(group) } else { None },
+            Node::Row { ref left, ref right, .. } =>
+                left.group_of(panel).or_else(|| right.group_of(panel)),
+            Node::Column { ref top, ref bottom, .. } =>
+                top.group_of(panel).or_else(|| bottom.group_of(panel)),
+        }
+    }
+
+    fn group_of_mut(&mut self, panel: &str) -> Option<&mut Group> {
+        match *self {
+            Node::Group(ref mut group) => if group.contains(panel) { Some(group) } else { None },
+            Node::Row { ref mut left, ref mut right, .. } |
+            Node::Column { top: ref mut left, bottom: ref mut right, .. } =>
+                match left.group_of_mut(panel) {
+                    Some(group) => Some(group),
+                    None => right.group_of_mut(panel),
+                },
+        }
+    }
+
+    /// The first group within the node, from left to right and top to bottom.
+    fn first_group_mut(&mut self) -> &mut Group {
+        match *self {
+            Node::Group(ref mut group) => group,
+            Node::Row { ref mut left, .. } => left.first_group_mut(),
+            Node::Column { ref mut top, .. } => top.first_group_mut(),
+        }
+    }
+
+    /// The node without the named panel, or `None` if no panels remain.
+    fn without(self, panel: &str) -> Option<Node> {
+        match self {
+            Node::Group(mut group) => {
+                group.remove(panel);
+                if group.panels.is_empty() { None } else { Some(Node::Group(group)) }
+            },
+            Node::Row { ratio, left, right } => match (left.without(panel), right.without(panel)) {
+                (Some(left), Some(right)) => Some(Node::row(ratio, left, right)),
+                (Some(node), None) | (None, Some(node)) => Some(node),
+                (None, None) => None,
+            },
+            Node::Column { ratio, top, bottom } => match (top.without(panel), bottom.without(panel)) {
+                (Some(top), Some(bottom)) => Some(Node::column(ratio, top, bottom)),
+                (Some(node), None) | (None, Some(node)) => Some(node),
+                (None, None) => None,
+            },
+        }
+    }
+
+    /// The node with a new group containing `panel` beside the group containing `other`.
+    fn split(self, other: &str, panel: &str, zone: Zone) -> Node {
+        match self {
+            Node::Group(group) => {
+                let is_target = group.contains(other);
+                let node = Node::Group(group);
+                if is_target { beside(node, Node::group(&[panel]), zone) } else { node }
+            },
+            Node::Row { ratio, left, right } =>
+                Node::row(ratio, left.split(other, panel, zone), right.split(other, panel, zone)),
+            Node::Column { ratio, top, bottom } =>
+                Node::column(ratio, top.split(other, panel, zone), bottom.split(other, panel, zone)),
+        }
+    }
+
+}
+
+
+impl Group {
+
+    /// A group of the given panels with the first selected.
+    pub fn new(panels: &[&str]) -> Self {
+        Group {
+            panels: panels.iter().map(|&panel| panel.to_owned()).collect(),
+            selected: 0,
+        }
+    }
+
+    /// Whether or not the named panel is within the group.
+    pub fn contains(&self, panel: &str) -> bool {
+        self.panels.iter().any(|p| p == panel)
+    }
+
+    /// The name of the selected panel, if any.
+    pub fn selected_panel(&self) -> Option<&str> {
+        self.panels.get(self.selected).map(|panel| &panel[..])
+    }
+
+    /// Add the named panel as the last tab of the group and select it.
+    fn push(&mut self, panel: &str) {
+        self.panels.push(panel.to_owned());
+        self.selected = self.panels.len() - 1;
+    }
+
+    /// Remove the named panel, keeping the same panel selected where possible.
+    fn remove(&mut self, panel: &str) {
+        if let Some(index) = self.panels.iter().position(|p| p == panel) {
+            self.panels.remove(index);
+            if index < self.selected || self.selected >= self.panels.len() {
+                self.selected = self.selected.saturating_sub(1);
+            }
+        }
+    }
+
+}
+
+
+/// Place the `new` node within the given `Zone` beside the given `node`, sharing its area equally.
+fn beside(node: Node, new: Node, zone: Zone) -> Node {
+    match zone {
+        Zone::Left => Node::row(0.5, new, node),
+        Zone::Right => Node::row(0.5, node, new),
+        Zone::Top => Node::column(0.5, new, node),
+        Zone::Bottom => Node::column(0.5, node, new),
+        Zone::Center => node,
+    }
+}
+
+
+impl<'a> Dock<'a> {
+
+    /// Begin building a `Dock` displaying the given `Layout`.
+    pub fn new(layout: &'a Layout) -> Self {
+        Dock {
+            common: widget::CommonBuilder::default(),
+            layout: layout,
+            style: Style::default(),
+        }
+    }
+
+    /// The height of each group's tab bar.
+    pub fn bar_thickness(mut self, thickness: Scalar) -> Self {
+        self.style.bar_thickness = Some(Some(thickness));
+        self
+    }
+
+    builder_methods!{
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub target_color { style.target_color = Some(Color) }
+        pub window_dim { style.window_dim = Some(Dimensions) }
+    }
+
+}
+
+
+impl<'a> Widget for Dock<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Panels<'a>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            area_ids: widget::id::List::new(),
+            window_ids: widget::id::List::new(),
+            title_ids: widget::id::List::new(),
+            panel_ids: HashMap::new(),
+            maybe_window_drag: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style
+    }
+
+    /// Update the state of the Dock.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Dock { layout, .. } = self;
+
+        let color = style.color(&ui.theme);
+        let border = style.border(&ui.theme);
+        let border_color = style.border_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let font_size = style.label_font_size(&ui.theme);
+        let target_color = style.target_color(&ui.theme);
+        let window_dim = style.window_dim(&ui.theme);
+        let bar_h = style.bar_thickness(&ui.theme)
+            .unwrap_or(font_size as Scalar + TAB_BAR_LABEL_PADDING * 2.0);
+        let title_h = widget::title_bar::calc_height(font_size);
+
+        // The area of every group along with the index of its window if it is floating.
+        let mut groups = Vec::new();
+        if let Some(ref root) = layout.root {
+            docked_groups(root, rect, &mut groups);
+        }
+        // Floating windows are kept within the area of the `Dock`.
+        let window_xys: Vec<Point> = layout.windows.iter()
+            .map(|window| clamp_window_xy(window.xy, window.dim, rect.dim()))
+            .collect();
+        for (i, window) in layout.windows.iter().enumerate() {
+            let window_rect = Rect::from_xy_dim(vec2_add(rect.xy(), window_xys[i]), window.dim);
+            groups.push((window_rect.pad_top(title_h), &window.group, Some(i)));
+        }
+
+        // Ensure there are ids for every group, window and panel.
+        let num_windows = layout.windows.len();
+        let has_all_ids = state.area_ids.len() >= groups.len()
+            && state.window_ids.len() >= num_windows
+            && state.ids.guides.len() == 5
+            && groups.iter().all(|&(_, group, _)| {
+                group.panels.iter().all(|panel| state.panel_ids.contains_key(panel))
+            });
+        if !has_all_ids {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.area_ids.resize(groups.len(), id_gen);
+                state.window_ids.resize(num_windows, id_gen);
+                state.title_ids.resize(num_windows, id_gen);
+                state.ids.guides.resize(5, id_gen);
+                for &(_, group, _) in &groups {
+                    for panel in &group.panels {
+                        if !state.panel_ids.contains_key(panel) {
+                            let ids = PanelIds { tab: id_gen.next(), content: id_gen.next() };
+                            state.panel_ids.insert(panel.clone(), ids);
+                        }
+                    }
+                }
+            });
+        }
+
+        // The payload of the panel being dragged from this dock, if any.
+        let dragged_panel = |drag: Option<&Drag>| -> Option<String> {
+            drag.and_then(|drag| drag.payload::<Payload>())
+                .filter(|payload| payload.dock == id)
+                .map(|payload| payload.panel.clone())
+        };
+
+        let mut actions = Vec::new();
+
+        // An empty dock accepts any panel as its first group.
+        if layout.root.is_none() {
+            widget::Canvas::new()
+                .wh(rect.dim())
+                .xy(rect.xy())
+                .color(color)
+                .border(border)
+                .border_color(border_color)
+                .parent(id)
+                .set(state.ids.empty, ui);
+            if dragged_panel(ui.drag_over(state.ids.empty)).is_some() {
+                ui.accept_drop(state.ids.empty);
+                widget::Rectangle::fill(rect.dim())
+                    .xy(rect.xy())
+                    .color(target_color.alpha(0.25))
+                    .floating(true)
+                    .graphics_for(state.ids.empty)
+                    .parent(state.ids.empty)
+                    .set(state.ids.preview, ui);
+            }
+            let num_drops = ui.widget_input(state.ids.empty).drops()
+                .filter(|drop| drop.target == state.ids.empty)
+                .count();
+            for _ in 0..num_drops {
+                if let Some(payload) = ui.take_dropped_payload::<Payload>(state.ids.empty) {
+                    let target = Target::Dock(Zone::Center);
+                    actions.push(Action::Dock { panel: payload.panel, target: target });
+                }
+            }
+        }
+
+        // The frame of each floating window, moved by dragging its title bar and returned to where
+        // it was when the drag began if the drag is cancelled.
+        for (i, window) in layout.windows.iter().enumerate() {
+            let window_id = state.window_ids[i];
+            let title_id = state.title_ids[i];
+            widget::Canvas::new()
+                .wh(window.dim)
+                .xy_relative_to(id, window_xys[i])
+                .color(color)
+                .border(border)
+                .border_color(border_color)
+                .floating(true)
+                .parent(id)
+                .set(window_id, ui);
+            widget::TitleBar::new(window.group.selected_panel().unwrap_or(""), window_id)
+                .h(title_h)
+                .color(color)
+                .border(border)
+                .border_color(border_color)
+                .label_color(label_color)
+                .label_font_size(font_size)
+                .parent(window_id)
+                .set(title_id, ui);
+            let title_input = ui.widget_input(title_id);
+            let delta = title_input.drags().left()
+                .fold([0.0, 0.0], |total, drag| vec2_add(total, drag.delta_xy));
+            let is_cancelled = title_input.drag_cancels()
+                .any(|cancel| cancel.button == input::MouseButton::Left);
+            let maybe_start_xy = state.maybe_window_drag
+                .and_then(|(index, xy)| if index == i { Some(xy) } else { None });
+            if is_cancelled {
+                if let Some(xy) = maybe_start_xy {
+                    actions.push(Action::MoveWindow { index: i, xy: xy });
+                    state.update(|state| state.maybe_window_drag = None);
+                }
+            } else if delta != [0.0, 0.0] {
+                if maybe_start_xy.is_none() {
+                    state.update(|state| state.maybe_window_drag = Some((i, window.xy)));
+                }
+                let xy = clamp_window_xy(vec2_add(window_xys[i], delta), window.dim, rect.dim());
+                actions.push(Action::MoveWindow { index: i, xy: xy });
+            }
+        }
+        let is_left_up = ui.global_input().current.mouse.buttons.left().is_up();
+        if state.maybe_window_drag.is_some() && is_left_up {
+            state.update(|state| state.maybe_window_drag = None);
+        }
+
+        let mut elements = Vec::with_capacity(groups.len());
+        for (g, &(area, group, maybe_window)) in groups.iter().enumerate() {
+            let area_id = state.area_ids[g];
+            let parent_id = maybe_window.map(|i| state.window_ids[i]).unwrap_or(id);
+            widget::Canvas::new()
+                .wh(area.dim())
+                .xy(area.xy())
+                .color(color)
+                .border(border)
+                .border_color(border_color)
+                .parent(parent_id)
+                .set(area_id, ui);
+
+            // A tab for each panel, selected by clicking and moved by dragging.
+            let tab_w = area.w() / group.panels.len() as Scalar;
+            for (i, panel) in group.panels.iter().enumerate() {
+                let tab_id = state.panel_ids[panel].tab;
+                let tab_x = area.left() + tab_w * (i as Scalar + 0.5);
+                let tab_color = if i == group.selected { color.highlighted() } else { color };
+                let times_clicked = widget::Button::new()
+                    .w_h(tab_w, bar_h)
+                    .x_y(tab_x, area.top() - bar_h / 2.0)
+                    .color(tab_color)
+                    .border(border)
+                    .border_color(border_color)
+                    .label(panel)
+                    .label_color(label_color)
+                    .label_font_size(font_size)
+                    .parent(area_id)
+                    .set(tab_id, ui);
+                if times_clicked.was_clicked() && i != group.selected {
+                    actions.push(Action::Select(panel.clone()));
+                }
+                if ui.widget_input(tab_id).drags().left().next().is_some() && ui.drag().is_none() {
+                    let payload = Payload { dock: id, panel: panel.clone() };
+                    ui.start_drag(Drag::new(tab_id, payload).label(panel));
+                }
+            }
+
+            // Show the docking guides while a panel from another group (or one of several within
+            // this group) is dragged over the group.
+            let can_split = maybe_window.is_none();
+            let maybe_dragged = dragged_panel(ui.drag_over(area_id))
+                .filter(|dragged| group.panels.iter().any(|panel| panel != dragged));
+            if maybe_dragged.is_some() {
+                ui.accept_drop(area_id);
+                let mouse_xy = ui.global_input().current.mouse.xy;
+                let maybe_zone = zone_at(area, bar_h, mouse_xy, can_split);
+                if let Some(zone) = maybe_zone {
+                    let preview = preview_rect(area, zone);
+                    widget::Rectangle::fill(preview.dim())
+                        .xy(preview.xy())
+                        .color(target_color.alpha(0.25))
+                        .floating(true)
+                        .graphics_for(area_id)
+                        .parent(area_id)
+                        .set(state.ids.preview, ui);
+                }
+                let guides = guides(area.pad_top(bar_h), can_split);
+                for (&guide_id, &(zone, guide)) in state.ids.guides.iter().zip(guides.iter()) {
+                    let alpha = if maybe_zone == Some(zone) { 1.0 } else { 0.6 };
+                    widget::Rectangle::fill(guide.dim())
+                        .xy(guide.xy())
+                        .color(target_color.alpha(alpha))
+                        .floating(true)
+                        .graphics_for(area_id)
+                        .parent(area_id)
+                        .set(guide_id, ui);
+                }
+            }
+
+            // Dock or float any panel dropped onto the group.
+            let drop_xys: Vec<Point> = ui.widget_input(area_id).drops()
+                .filter(|drop| drop.target == area_id)
+                .map(|drop| vec2_add(drop.xy, area.xy()))
+                .collect();
+            for xy in drop_xys {
+                let panel = match ui.take_dropped_payload::<Payload>(area_id) {
+                    Some(payload) => payload.panel,
+                    None => continue,
+                };
+                let maybe_other = group.panels.iter().find(|&other| *other != panel);
+                let action = match (zone_at(area, bar_h, xy, can_split), maybe_other) {
+                    (Some(zone), Some(other)) => {
+                        let target = Target::Group(other.clone(), zone);
+                        Action::Dock { panel: panel, target: target }
+                    },
+                    (Some(_), None) => continue,
+                    (None, _) => Action::Float {
+                        panel: panel,
+                        xy: clamp_window_xy(vec2_sub(xy, rect.xy()), window_dim, rect.dim()),
+                        dim: window_dim,
+                    },
+                };
+                actions.push(action);
+            }
+
+            // The selected panel's content fills the area below the tab bar.
+            if let Some(panel) = group.selected_panel() {
+                elements.push(Element {
+                    panel: panel,
+                    widget_id: state.panel_ids[panel].content,
+                    w: area.w(),
+                    h: area.h() - bar_h,
+                    rel_x: 0.0,
+                    rel_y: -bar_h / 2.0,
+                    area_id: area_id,
+                });
+            }
+        }
+
+        Panels {
+            dock_id: id,
+            actions: actions,
+            elements: elements.into_iter(),
+        }
+    }
+
+}
+
+
+/// Collect the area of each group within the given node of docked panels.
+fn docked_groups<'a>(node: &'a Node,
+                     rect: Rect,
+                     groups: &mut Vec<(Rect, &'a Group, Option<usize>)>)
+{
+    match *node {
+        Node::Group(ref group) => groups.push((rect, group, None)),
+        Node::Row { ratio, ref left, ref right } => {
+            let left_w = rect.w() * ratio;
+            docked_groups(left, rect.pad_right(rect.w() - left_w), groups);
+            docked_groups(right, rect.pad_left(left_w), groups);
+        },
+        Node::Column { ratio, ref top, ref bottom } => {
+            let top_h = rect.h() * ratio;
+            docked_groups(top, rect.pad_bottom(rect.h() - top_h), groups);
+            docked_groups(bottom, rect.pad_top(top_h), groups);
+        },
+    }
+}
+
+/// Clamp the position of a floating window (relative to the centre of the `Dock`) so that the
+/// window lies within the dock's area.
+///
+/// Windows larger than the `Dock` along either axis are centred along that axis.
+fn clamp_window_xy(xy: Point, dim: Dimensions, dock_dim: Dimensions) -> Point {
+    let clamp = |n: Scalar, w: Scalar, dock_w: Scalar| {
+        let max = utils::partial_max((dock_w - w) / 2.0, 0.0);
+        utils::clamp(n, -max, max)
+    };
+    [clamp(xy[0], dim[0], dock_dim[0]), clamp(xy[1], dim[1], dock_dim[1])]
+}
+
+/// The docking guides centred within the given area below a group's tab bar.
+///
+/// Only the central guide is given for groups that may not be split.
+fn guides(body: Rect, can_split: bool) -> Vec<(Zone, Rect)> {
+    let (x, y) = (body.x(), body.y());
+    let guide = |zone, x, y| (zone, Rect::from_xy_dim([x, y], [GUIDE_SIZE, GUIDE_SIZE]));
+    let mut guides = vec![guide(Zone::Center, x, y)];
+    if can_split {
+        guides.push(guide(Zone::Left, x - GUIDE_STEP, y));
+        guides.push(guide(Zone::Right, x + GUIDE_STEP, y));
+        guides.push(guide(Zone::Top, x, y + GUIDE_STEP));
+        guides.push(guide(Zone::Bottom, x, y - GUIDE_STEP));
+    }
+    guides
+}
+
+/// The `Zone` of the group with the given area at the given point, if any.
+///
+/// The group's tab bar is always part of the central zone.
+fn zone_at(area: Rect, bar_h: Scalar, xy: Point, can_split: bool) -> Option<Zone> {
+    if area.pad_bottom(area.h() - bar_h).is_over(xy) {
+        return Some(Zone::Center);
+    }
+    guides(area.pad_top(bar_h), can_split).into_iter()
+        .find(|&(_, guide)| guide.is_over(xy))
+        .map(|(zone, _)| zone)
+}
+
+/// The part of the group's area that a panel docked within the given `Zone` would occupy.
+fn preview_rect(area: Rect, zone: Zone) -> Rect {
+    match zone {
+        Zone::Center => area,
+        Zone::Left => area.pad_right(area.w() / 2.0),
+        Zone::Right => area.pad_left(area.w() / 2.0),
+        Zone::Top => area.pad_bottom(area.h() / 2.0),
+        Zone::Bottom => area.pad_top(area.h() / 2.0),
+    }
+}
+
+
+impl<'a> Iterator for Panels<'a> {
+    type Item = Element<'a>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+}
+
+impl<'a> Panels<'a> {
+
+    /// The id of the `Dock` that produced the `Panels`.
+    pub fn dock_id(&self) -> widget::Id {
+        self.dock_id
+    }
+
+    /// The changes to the `Layout` requested by the user during this update.
+    ///
+    /// These should be applied to the `Layout` via `Layout::apply`.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+}
+
+
+impl<'a> Element<'a> {
+
+    /// Sets the given widget as the content of the panel.
+    ///
+    /// Sets the:
+    /// - position of the widget.
+    /// - dimensions of the widget.
+    /// - parent of the widget.
+    /// - and finally sets the widget within the `Ui`.
+    pub fn set<W>(self, widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        let Element { widget_id, area_id, w, h, rel_x, rel_y, .. } = self;
+        widget
+            .w_h(w, h)
+            .x_y_relative_to(area_id, rel_x, rel_y)
+            .parent(area_id)
+            .set(widget_id, ui)
+    }
+
+}
+
+
+impl<'a> Colorable for Dock<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for Dock<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
@@ -1,6 +1,6 @@
 //! The `DropDownList` and related items.
 
-use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Sizeable};
+use {Color, Colorable, Dimensions, FontSize, Borderable, Labelable, Positionable, Sizeable, Ui};
 use position::{self, Align, Scalar};
 use text;
 use utils;
@@ -211,6 +211,24 @@ impl<'a, T> Widget for DropDownList<'a, T>
         self.style.clone()
     }
 
+    /// The content of the list is its widest item or label on a single line.
+    fn content_wh(&self, ui: &Ui) -> Option<Dimensions> {
+        let font_size = self.style.label_font_size(&ui.theme);
+        let font = match self.style.label_font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id))
+        {
+            Some(font) => font,
+            None => return Some([0.0, 0.0]),
+        };
+        let w = self.items.iter()
+            .map(|item| item.as_ref())
+            .chain(self.maybe_label)
+            .map(|label| text::line::width(label, font, font_size))
+            .fold(0.0, utils::partial_max);
+        Some([w, text::height(1, font_size, 0.0)])
+    }
+
     /// Update the state of the DropDownList.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
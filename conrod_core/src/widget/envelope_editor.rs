@@ -1,7 +1,7 @@
 //! The `EnvelopeEditor` widget and related items.
 
 use {Color, Colorable, Borderable, FontSize, Labelable, Positionable, Sizeable, Widget};
-use num::Float;
+use num::{Float, NumCast, ToPrimitive};
 use position::{Direction, Edge, Point, Rect, Scalar};
 use std;
 use text;
@@ -75,9 +75,20 @@ widget_ids! {
 /// Represents the state of the EnvelopeEditor widget.
 pub struct State {
     pressed_point: Option<usize>,
+    drag_origin: Option<DragOrigin>,
     ids: Ids,
 }
 
+/// The state of the pressed point prior to being dragged, used to restore it if the drag is
+/// cancelled.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum DragOrigin {
+    /// The point was inserted by the press that began the drag.
+    Inserted,
+    /// The point already existed at the given *x* and *y* values.
+    Existing(f64, f64),
+}
+
 
 /// `EnvPoint` must be implemented for any type that is used as a 2D point within the
 /// EnvelopeEditor.
@@ -236,6 +247,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
     fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
         State {
             pressed_point: None,
+            drag_origin: None,
             ids: Ids::new(id_gen),
         }
     }
@@ -310,6 +322,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
 
         // Track the currently pressed point if any.
         let mut pressed_point = state.pressed_point;
+        let mut drag_origin = state.drag_origin;
 
         // Handle all events that have occurred to the EnvelopeEditor since the last update.
         //
@@ -317,6 +330,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
         // - New points via left `Click`.
         // - Remove points via right `Click`.
         // - Dragging points via left `Drag`.
+        // - Restoring the dragged point via left `DragCancel`.
         let mut events = Vec::new();
         'events: for widget_event in ui.widget_input(id).events() {
             use event;
@@ -356,6 +370,11 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                             // If the press was over a point, begin dragging it and we're done.
                             if distance <= point_radius.powf(2.0) {
                                 pressed_point = Some(i);
+                                let origin = (px.to_f64(), py.to_f64());
+                                drag_origin = match origin {
+                                    (Some(x), Some(y)) => Some(DragOrigin::Existing(x, y)),
+                                    _ => None,
+                                };
                                 continue 'events;
                             }
 
@@ -378,6 +397,7 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                         let new_point = EnvelopePoint::new(new_x, new_y);
 
                         // Insert the point and push an `AddPoint` event.
+                        drag_origin = Some(DragOrigin::Inserted);
                         match (maybe_left, maybe_right) {
                             (Some(_), None) | (None, None) => {
                                 let idx = env.len();
@@ -419,7 +439,25 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
                 event::Widget::Release(release) => {
                     if let event::Button::Mouse(MouseButton::Left, _) = release.button {
                         pressed_point = None;
+                        drag_origin = None;
+                    }
+                },
+
+                // A left `DragCancel` returns the `pressed_point` to where it was before the
+                // drag, removing it if it was inserted by the drag.
+                event::Widget::DragCancel(cancel) if cancel.button == input::MouseButton::Left => {
+                    if let (Some(idx), Some(origin)) = (pressed_point, drag_origin) {
+                        match origin {
+                            DragOrigin::Inserted => events.push(Event::RemovePoint { i: idx }),
+                            DragOrigin::Existing(x, y) => {
+                                if let (Some(x), Some(y)) = (NumCast::from(x), NumCast::from(y)) {
+                                    events.push(Event::MovePoint { i: idx, x: x, y: y });
+                                }
+                            },
+                        }
                     }
+                    pressed_point = None;
+                    drag_origin = None;
                 },
 
                 // A left `Drag` moves the `pressed_point` if there is one.
@@ -444,8 +482,11 @@ impl<'a, E> Widget for EnvelopeEditor<'a, E>
             }
         }
 
-        if state.pressed_point != pressed_point {
-            state.update(|state| state.pressed_point = pressed_point);
+        if state.pressed_point != pressed_point || state.drag_origin != drag_origin {
+            state.update(|state| {
+                state.pressed_point = pressed_point;
+                state.drag_origin = drag_origin;
+            });
         }
 
         // Ensure that the local version of the `env` is up to date for drawing.
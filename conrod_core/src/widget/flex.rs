@@ -0,0 +1,498 @@
+//! A layout widget that arranges its children in lines according to their flex properties.
+
+use {Scalar, Ui, UiCell, Widget};
+use position::{Dimension, Rect};
+use std;
+use utils;
+use widget;
+
+
+/// Arranges a sequence of child widgets along a main axis, similarly to a CSS flexbox.
+///
+/// Each child is described by an `Item`, whose `basis` is its preferred length along the main
+/// axis. Any free space along the main axis is distributed between the items in proportion to
+/// their `grow` factors, while any overflow is taken from the items in proportion to their
+/// `shrink` factors weighted by their basis. When `wrap` is enabled, items that do not fit within
+/// the current line begin a new line instead.
+///
+/// The `Flex` itself draws nothing. It produces an `Items` iterator yielding an `Element` for
+/// each `Item`, via which any widget may be set within its computed rectangle.
+#[derive(Clone, WidgetCommon_)]
+pub struct Flex<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    items: &'a [Item],
+    style: Style,
+}
+
+/// The flex properties of a single child of a `Flex`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Item {
+    /// The preferred length of the item along the main axis before growing or shrinking.
+    pub basis: Dimension,
+    /// The proportion of the line's free space given to the item.
+    pub grow: Scalar,
+    /// The proportion of the line's overflow taken from the item, weighted by its basis.
+    pub shrink: Scalar,
+    /// The length of the item along the cross axis.
+    ///
+    /// If `None`, the item is stretched to the cross length of its line.
+    pub maybe_cross: Option<Dimension>,
+    /// Overrides the `Flex`'s `align_items` for this item.
+    pub maybe_align: Option<CrossAlign>,
+}
+
+/// The axis along which the items of a `Flex` are laid out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// From left to right, with lines wrapping from top to bottom.
+    Row,
+    /// From top to bottom, with lines wrapping from left to right.
+    Column,
+}
+
+/// The way in which free space along the main axis is distributed around the items of a line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Justify {
+    /// Items are packed towards the start of the line.
+    Start,
+    /// Items are packed around the middle of the line.
+    Middle,
+    /// Items are packed towards the end of the line.
+    End,
+    /// The free space is divided evenly between each pair of adjacent items.
+    SpaceBetween,
+    /// The free space is divided evenly around each item, so that the space at either end is
+    /// half the space between items.
+    SpaceAround,
+    /// The free space is divided evenly between each pair of adjacent items and either end.
+    SpaceEvenly,
+}
+
+/// The way in which an item is aligned along the cross axis of its line.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CrossAlign {
+    /// Aligned to the start of the line (the top of a row or the left of a column).
+    Start,
+    /// Aligned to the middle of the line.
+    Middle,
+    /// Aligned to the end of the line.
+    End,
+    /// Stretched to the cross length of the line, unless the item has a `maybe_cross` length.
+    Stretch,
+}
+
+/// Unique styling for the `Flex`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The axis along which the items are laid out.
+    #[conrod(default = "Direction::Row")]
+    pub direction: Option<Direction>,
+    /// Whether or not items that overflow the main axis begin a new line.
+    #[conrod(default = "false")]
+    pub wrap: Option<bool>,
+    /// The space between adjacent items along the main axis.
+    #[conrod(default = "0.0")]
+    pub gap: Option<Scalar>,
+    /// The space between adjacent lines along the cross axis.
+    #[conrod(default = "0.0")]
+    pub line_gap: Option<Scalar>,
+    /// The distribution of free space along the main axis.
+    #[conrod(default = "Justify::Start")]
+    pub justify: Option<Justify>,
+    /// The alignment of items along the cross axis.
+    #[conrod(default = "CrossAlign::Stretch")]
+    pub align_items: Option<CrossAlign>,
+    /// The distance between the edges of the `Flex` and its items.
+    #[conrod(default = "0.0")]
+    pub pad: Option<Scalar>,
+}
+
+/// The state of the `Flex`.
+pub struct State {
+    /// A `widget::Id` for each `Item`.
+    ids: widget::id::List,
+}
+
+/// The event produced by the `Flex`, yielding an `Element` for each `Item`.
+#[derive(Clone, Debug)]
+pub struct Items {
+    flex_id: widget::Id,
+    elements: std::vec::IntoIter<Element>,
+}
+
+/// Data necessary for instantiating a widget for a single `Flex` `Item`.
+#[derive(Copy, Clone, Debug)]
+pub struct Element {
+    /// The id generated for the widget.
+    pub widget_id: widget::Id,
+    /// The index of the `Item` within the slice given to the `Flex`.
+    pub index: usize,
+    /// The width of the element.
+    pub w: Scalar,
+    /// The height of the element.
+    pub h: Scalar,
+    /// The *x* position of the element relative to the centre of the `Flex`.
+    pub rel_x: Scalar,
+    /// The *y* position of the element relative to the centre of the `Flex`.
+    pub rel_y: Scalar,
+    /// The id of the `Flex`, used for positioning.
+    flex_id: widget::Id,
+}
+
+/// An `Item` whose lengths have been resolved.
+#[derive(Copy, Clone, Debug)]
+struct Resolved {
+    basis: Scalar,
+    grow: Scalar,
+    shrink: Scalar,
+    maybe_cross: Option<Scalar>,
+    align: CrossAlign,
+}
+
+/// The span of an item along both axes, relative to the start of each axis.
+#[derive(Copy, Clone, Debug, Default)]
+struct Span {
+    main_start: Scalar,
+    main_len: Scalar,
+    cross_start: Scalar,
+    cross_len: Scalar,
+}
+
+
+impl Item {
+
+    /// An item with no basis that neither grows nor shrinks.
+    pub fn new() -> Self {
+        Item {
+            basis: Dimension::Absolute(0.0),
+            grow: 0.0,
+            shrink: 1.0,
+            maybe_cross: None,
+            maybe_align: None,
+        }
+    }
+
+    /// Specify the preferred length of the item along the main axis.
+    pub fn basis(self, basis: Scalar) -> Self {
+        self.basis_dimension(Dimension::Absolute(basis))
+    }
+
+    /// Specify the preferred length of the item along the main axis as a `Dimension`, e.g. to
+    /// match the length of some other widget.
+    pub fn basis_dimension(mut self, basis: Dimension) -> Self {
+        self.basis = basis;
+        self
+    }
+
+    /// Specify the proportion of the line's free space given to the item.
+    pub fn grow(mut self, grow: Scalar) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Specify the proportion of the line's overflow taken from the item.
+    pub fn shrink(mut self, shrink: Scalar) -> Self {
+        self.shrink = shrink;
+        self
+    }
+
+    /// Specify the length of the item along the cross axis.
+    pub fn cross(self, cross: Scalar) -> Self {
+        self.cross_dimension(Dimension::Absolute(cross))
+    }
+
+    /// Specify the length of the item along the cross axis as a `Dimension`.
+    pub fn cross_dimension(mut self, cross: Dimension) -> Self {
+        self.maybe_cross = Some(cross);
+        self
+    }
+
+    /// Specify the alignment of the item along the cross axis of its line.
+    pub fn align(mut self, align: CrossAlign) -> Self {
+        self.maybe_align = Some(align);
+        self
+    }
+
+}
+
+
+impl<'a> Flex<'a> {
+
+    /// Begin building a `Flex` laying out the given items.
+    pub fn new(items: &'a [Item]) -> Self {
+        Flex {
+            common: widget::CommonBuilder::default(),
+            items: items,
+            style: Style::default(),
+        }
+    }
+
+    /// Lay the items out from left to right.
+    pub fn row(self) -> Self {
+        self.direction(Direction::Row)
+    }
+
+    /// Lay the items out from top to bottom.
+    pub fn column(self) -> Self {
+        self.direction(Direction::Column)
+    }
+
+    builder_methods!{
+        pub direction { style.direction = Some(Direction) }
+        pub wrap { style.wrap = Some(bool) }
+        pub gap { style.gap = Some(Scalar) }
+        pub line_gap { style.line_gap = Some(Scalar) }
+        pub justify { style.justify = Some(Justify) }
+        pub align_items { style.align_items = Some(CrossAlign) }
+        pub pad { style.pad = Some(Scalar) }
+    }
+
+}
+
+
+impl<'a> Widget for Flex<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Items;
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State { ids: widget::id::List::new() }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Flex.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Flex { items, .. } = self;
+
+        // Ensure there is an id for every item.
+        if state.ids.len() < items.len() {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.ids.resize(items.len(), id_gen));
+        }
+
+        let direction = style.direction(&ui.theme);
+        let align_items = style.align_items(&ui.theme);
+        let inner = rect.pad(style.pad(&ui.theme));
+        let (main_len, cross_len) = match direction {
+            Direction::Row => (inner.w(), inner.h()),
+            Direction::Column => (inner.h(), inner.w()),
+        };
+
+        // Resolve the lengths of each item along both axes.
+        let resolved: Vec<Resolved> = items.iter().map(|item| {
+            let (main_is_x, cross_is_x) = match direction {
+                Direction::Row => (true, false),
+                Direction::Column => (false, true),
+            };
+            Resolved {
+                basis: resolve(item.basis, main_is_x, ui).unwrap_or(0.0),
+                grow: item.grow,
+                shrink: item.shrink,
+                maybe_cross: item.maybe_cross.and_then(|cross| resolve(cross, cross_is_x, ui)),
+                align: item.maybe_align.unwrap_or(align_items),
+            }
+        }).collect();
+
+        let spans = layout(&resolved,
+                           main_len,
+                           cross_len,
+                           style.wrap(&ui.theme),
+                           style.gap(&ui.theme),
+                           style.line_gap(&ui.theme),
+                           style.justify(&ui.theme));
+
+        // Convert each span to a rectangle relative to the centre of the `Flex`.
+        let (left, top) = (inner.left() - rect.x(), inner.top() - rect.y());
+        let elements: Vec<Element> = spans.iter().zip(state.ids.iter()).enumerate()
+            .map(|(i, (span, &widget_id))| {
+                let (x, w, y, h) = match direction {
+                    Direction::Row =>
+                        (span.main_start, span.main_len, span.cross_start, span.cross_len),
+                    Direction::Column =>
+                        (span.cross_start, span.cross_len, span.main_start, span.main_len),
+                };
+                Element {
+                    widget_id: widget_id,
+                    index: i,
+                    w: w,
+                    h: h,
+                    rel_x: left + x + w / 2.0,
+                    rel_y: top - y - h / 2.0,
+                    flex_id: id,
+                }
+            })
+            .collect();
+
+        Items {
+            flex_id: id,
+            elements: elements.into_iter(),
+        }
+    }
+
+}
+
+
+/// Resolve the given `Dimension` along the *x* axis if `is_x`, or the *y* axis otherwise.
+fn resolve(dimension: Dimension, is_x: bool, ui: &Ui) -> Option<Scalar> {
+    let len = |rect: Rect| if is_x { rect.w() } else { rect.h() };
+    match dimension {
+        Dimension::Absolute(length) => Some(length),
+        Dimension::Of(idx, maybe_pad) =>
+            ui.rect_of(idx).map(|rect| len(rect) - maybe_pad.unwrap_or(0.0) * 2.0),
+        Dimension::KidAreaOf(idx, maybe_pad) =>
+            ui.kid_area_of(idx).map(|rect| len(rect) - maybe_pad.unwrap_or(0.0) * 2.0),
+        Dimension::ToContent(_) => None,
+    }
+}
+
+/// Compute the span of each item from the start of the main and cross axes.
+fn layout(items: &[Resolved],
+          main_len: Scalar,
+          cross_len: Scalar,
+          wrap: bool,
+          gap: Scalar,
+          line_gap: Scalar,
+          justify: Justify) -> Vec<Span>
+{
+    // Break the items into lines, each a range of indices into `items`.
+    let mut lines = Vec::new();
+    let mut line_start = 0;
+    let mut line_len = 0.0;
+    for (i, item) in items.iter().enumerate() {
+        let len_with_item = if i == line_start { item.basis } else { line_len + gap + item.basis };
+        if wrap && i > line_start && len_with_item > main_len {
+            lines.push(line_start..i);
+            line_start = i;
+            line_len = item.basis;
+        } else {
+            line_len = len_with_item;
+        }
+    }
+    if line_start < items.len() {
+        lines.push(line_start..items.len());
+    }
+
+    // Lines are as long as their longest item along the cross axis, with any remaining space
+    // shared between them. A single line always spans the entire cross axis.
+    let mut line_cross_lens: Vec<Scalar> = lines.iter().map(|line| {
+        items[line.clone()].iter()
+            .filter_map(|item| item.maybe_cross)
+            .fold(0.0, utils::partial_max)
+    }).collect();
+    if !wrap {
+        for len in &mut line_cross_lens {
+            *len = cross_len;
+        }
+    } else if !lines.is_empty() {
+        let gaps = line_gap * (lines.len() - 1) as Scalar;
+        let used = line_cross_lens.iter().fold(gaps, |total, &len| total + len);
+        let extra = (cross_len - used) / lines.len() as Scalar;
+        if extra > 0.0 {
+            for len in &mut line_cross_lens {
+                *len += extra;
+            }
+        }
+    }
+
+    let mut spans = vec![Span::default(); items.len()];
+    let mut cross_start = 0.0;
+    for (line, &line_cross_len) in lines.iter().zip(line_cross_lens.iter()) {
+        let line_items = &items[line.clone()];
+        let num = line_items.len() as Scalar;
+        let gaps = gap * (num - 1.0);
+        let total_basis = line_items.iter().fold(0.0, |total, item| total + item.basis);
+        let free = main_len - total_basis - gaps;
+
+        // Grow or shrink the items to fill the line.
+        let total_grow = line_items.iter().fold(0.0, |total, item| total + item.grow);
+        let total_shrink = line_items.iter()
+            .fold(0.0, |total, item| total + item.shrink * item.basis);
+        let lens: Vec<Scalar> = line_items.iter().map(|item| {
+            if free > 0.0 && total_grow > 0.0 {
+                item.basis + free * item.grow / total_grow
+            } else if free < 0.0 && total_shrink > 0.0 {
+                utils::partial_max(0.0, item.basis + free * item.shrink * item.basis / total_shrink)
+            } else {
+                item.basis
+            }
+        }).collect();
+
+        // Distribute any remaining free space according to the justification.
+        let remaining = utils::partial_max(0.0, main_len - gaps - lens.iter().fold(0.0, |t, l| t + l));
+        let (mut main_start, between) = match justify {
+            Justify::Start => (0.0, 0.0),
+            Justify::Middle => (remaining / 2.0, 0.0),
+            Justify::End => (remaining, 0.0),
+            Justify::SpaceBetween if num > 1.0 => (0.0, remaining / (num - 1.0)),
+            Justify::SpaceBetween => (0.0, 0.0),
+            Justify::SpaceAround => (remaining / num / 2.0, remaining / num),
+            Justify::SpaceEvenly => (remaining / (num + 1.0), remaining / (num + 1.0)),
+        };
+
+        for ((i, item), &len) in line.clone().zip(line_items.iter()).zip(lens.iter()) {
+            let cross = item.maybe_cross.unwrap_or(line_cross_len);
+            let offset = match item.align {
+                CrossAlign::Start | CrossAlign::Stretch => 0.0,
+                CrossAlign::Middle => (line_cross_len - cross) / 2.0,
+                CrossAlign::End => line_cross_len - cross,
+            };
+            spans[i] = Span {
+                main_start: main_start,
+                main_len: len,
+                cross_start: cross_start + offset,
+                cross_len: cross,
+            };
+            main_start += len + gap + between;
+        }
+
+        cross_start += line_cross_len + line_gap;
+    }
+
+    spans
+}
+
+
+impl Iterator for Items {
+    type Item = Element;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+}
+
+impl Items {
+
+    /// The id of the `Flex` that produced the `Items`.
+    pub fn flex_id(&self) -> widget::Id {
+        self.flex_id
+    }
+
+}
+
+
+impl Element {
+
+    /// Sets the given widget as the widget to use for the item.
+    ///
+    /// Sets the:
+    /// - position of the widget.
+    /// - dimensions of the widget.
+    /// - parent of the widget.
+    /// - and finally sets the widget within the `Ui`.
+    pub fn set<W>(self, widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        use {Positionable, Sizeable};
+        let Element { widget_id, flex_id, w, h, rel_x, rel_y, .. } = self;
+        widget
+            .w_h(w, h)
+            .x_y_relative_to(flex_id, rel_x, rel_y)
+            .parent(flex_id)
+            .set(widget_id, ui)
+    }
+
+}
@@ -0,0 +1,176 @@
+//! A container that places its children from left to right, wrapping them onto new rows.
+
+use {Scalar, Ui, UiCell, Widget};
+use position::Dimension;
+use utils;
+use widget;
+
+
+/// Places arbitrary child widgets from left to right, beginning a new row whenever the next child
+/// would overflow the width of the `Flow`.
+///
+/// The `Flow` itself draws nothing. It produces a `Children` handle via which each child widget
+/// is set in turn. Each child keeps its own dimensions and is aligned to the top of its row.
+///
+/// By default, the height of the `Flow` is fit to its children (see `Dimension::ToContent`). It is
+/// fit once all of its children have been set, before any widget that follows it is positioned.
+#[derive(Clone, WidgetCommon_)]
+pub struct Flow {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    style: Style,
+}
+
+/// Unique styling for the `Flow`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The space between adjacent children within a row.
+    #[conrod(default = "0.0")]
+    pub x_spacing: Option<Scalar>,
+    /// The space between adjacent rows.
+    #[conrod(default = "0.0")]
+    pub y_spacing: Option<Scalar>,
+    /// The distance between the edges of the `Flow` and its children.
+    #[conrod(default = "0.0")]
+    pub pad: Option<Scalar>,
+}
+
+/// The event produced by the `Flow`, via which each of its children may be set.
+#[derive(Copy, Clone, Debug)]
+pub struct Children {
+    flow_id: widget::Id,
+    /// The left and top edges of the `Flow`.
+    left: Scalar,
+    top: Scalar,
+    width: Scalar,
+    pad: Scalar,
+    x_spacing: Scalar,
+    y_spacing: Scalar,
+    /// The offset of the next child from the left edge of the kid area.
+    x: Scalar,
+    /// The offset of the current row from the top edge of the kid area.
+    y: Scalar,
+    /// The height of the tallest child within the current row.
+    row_h: Scalar,
+    /// Whether or not the current row contains any children.
+    is_row_empty: bool,
+}
+
+
+impl Flow {
+
+    /// Begin building a `Flow`.
+    pub fn new() -> Self {
+        Flow {
+            common: widget::CommonBuilder::default(),
+            style: Style::default(),
+        }
+    }
+
+    /// Specify the space between adjacent children within a row and between adjacent rows.
+    pub fn spacing(self, x: Scalar, y: Scalar) -> Self {
+        self.x_spacing(x).y_spacing(y)
+    }
+
+    builder_methods!{
+        pub x_spacing { style.x_spacing = Some(Scalar) }
+        pub y_spacing { style.y_spacing = Some(Scalar) }
+        pub pad { style.pad = Some(Scalar) }
+    }
+
+}
+
+
+impl Widget for Flow {
+    type State = ();
+    type Style = Style;
+    type Event = Children;
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        ()
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// The `Flow` is fit to its children, padded by its `pad`.
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        Dimension::ToContent(Some(self.style.pad(&ui.theme)))
+    }
+
+    /// Update the state of the Flow.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, rect, style, ui, .. } = args;
+        let pad = style.pad(&ui.theme);
+        Children {
+            flow_id: id,
+            left: rect.left(),
+            top: rect.top(),
+            width: rect.w(),
+            pad: pad,
+            x_spacing: style.x_spacing(&ui.theme),
+            y_spacing: style.y_spacing(&ui.theme),
+            x: 0.0,
+            y: 0.0,
+            row_h: 0.0,
+            is_row_empty: true,
+        }
+    }
+
+}
+
+
+impl Children {
+
+    /// The id of the `Flow` that produced the `Children`.
+    pub fn flow_id(&self) -> widget::Id {
+        self.flow_id
+    }
+
+    /// The height of the `Flow` given the children that have been set so far.
+    pub fn h(&self) -> Scalar {
+        self.y + self.row_h + self.pad * 2.0
+    }
+
+    /// Sets the given widget as the next child of the `Flow`.
+    ///
+    /// The widget is placed to the right of the previous child, or at the start of a new row if
+    /// it would otherwise overflow the width of the `Flow`.
+    pub fn set<W>(&mut self, widget: W, widget_id: widget::Id, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        use {Positionable, Sizeable};
+
+        let w = widget.get_w(ui).unwrap_or(0.0);
+        let h = widget.get_h(ui).unwrap_or(0.0);
+        let kid_w = self.width - self.pad * 2.0;
+        if !self.is_row_empty && self.x + self.x_spacing + w > kid_w {
+            self.y += self.row_h + self.y_spacing;
+            self.x = 0.0;
+            self.row_h = 0.0;
+            self.is_row_empty = true;
+        }
+        if !self.is_row_empty {
+            self.x += self.x_spacing;
+        }
+
+        // Position the child relative to the centre of the `Flow`.
+        let flow_xy = ui.xy_of(self.flow_id).unwrap_or([0.0, 0.0]);
+        let x = self.left + self.pad + self.x + w / 2.0;
+        let y = self.top - self.pad - self.y - h / 2.0;
+        let event = widget
+            .x_y_relative_to(self.flow_id, x - flow_xy[0], y - flow_xy[1])
+            .parent(self.flow_id)
+            .set(widget_id, ui);
+
+        // Advance by the child's resulting dimensions, which may differ from those requested.
+        let wh = ui.wh_of(widget_id).unwrap_or([w, h]);
+        self.x += wh[0];
+        self.row_h = utils::partial_max(self.row_h, wh[1]);
+        self.is_row_empty = false;
+
+        event
+    }
+
+}
@@ -1,6 +1,7 @@
 //! A widget for viewing and controlling graph structures.
 
 use {color, widget, Color, Colorable, Point, Positionable, Scalar, Widget, Ui, UiCell};
+use input;
 use std::any::{Any, TypeId};
 use std::cell::Cell;
 use std::collections::{HashMap, VecDeque};
@@ -878,6 +879,15 @@ where
                         .left()
                         .fold((0.0, 0.0), |(x, y), d| (x + d.delta_xy[0], y + d.delta_xy[1]));
 
+                    // A cancelled drag returns the node to where the drag began.
+                    let (dragged_x, dragged_y) = ui.widget_input(widget_id)
+                        .drag_cancels()
+                        .filter(|cancel| cancel.button == input::MouseButton::Left)
+                        .fold((dragged_x, dragged_y), |(x, y), cancel| {
+                            (x - (cancel.xy[0] - cancel.origin[0]),
+                             y - (cancel.xy[1] - cancel.origin[1]))
+                        });
+
                     // If dragging would not move the widget, we're done.
                     if dragged_x == 0.0 && dragged_y == 0.0 {
                         point
@@ -259,6 +259,9 @@ impl<D, S> List<D, S>
     /// Specify a fixed item size, where size is a `Scalar` in the direction that the `List` is
     /// flowing. When a `List` is constructed with this method, all items will have a fixed, equal
     /// length.
+    ///
+    /// Items that clamp their length via their minimum or maximum dimensions are laid out by the
+    /// length they occupy instead.
     pub fn item_size(self, length: Scalar) -> List<D, Fixed> {
         let List { common, style, num_items, .. } = self;
         List {
@@ -527,7 +530,16 @@ impl ItemSize for Fixed {
                 "the given item height was {:?} however it must be > 0",
                 item_size.length);
 
-        let total_item_length = num_items as Scalar * item_size.length;
+        // An item may clamp its own length via its minimum and maximum dimensions, so the length
+        // that the first item occupied during the last update is used to lay out the list.
+        let item_length = state.ids.items.first()
+            .filter(|&&item_id| ui.prev_updated_widgets().contains(&item_id))
+            .and_then(|&item_id| ui.rect_of(item_id))
+            .map(|rect| D::ranges(rect).0.len())
+            .filter(|&length| length > 0.0)
+            .unwrap_or(item_size.length);
+
+        let total_item_length = num_items as Scalar * item_length;
         let (list_range, list_perpendicular_range) = D::ranges(rect);
         let list_length = list_range.len();
         let list_breadth = list_perpendicular_range.len();
@@ -573,8 +585,8 @@ impl ItemSize for Fixed {
                 let (scroll_trigger_range, _) = D::ranges(scroll_trigger_rect);
 
                 let hidden_range_length = (scroll_trigger_range.start - list_range.start).abs();
-                let num_start_hidden_items = hidden_range_length / item_size.length;
-                let num_visible_items = list_length / item_size.length;
+                let num_start_hidden_items = hidden_range_length / item_length;
+                let num_visible_items = list_length / item_length;
                 let first_visible_item_idx = num_start_hidden_items.floor() as usize;
                 let end_visible_item_idx = std::cmp::min(
                     (num_start_hidden_items + num_visible_items).ceil() as usize,
@@ -582,7 +594,7 @@ impl ItemSize for Fixed {
                 );
 
                 let range = first_visible_item_idx..end_visible_item_idx;
-                let margin = first_visible_item_idx as Scalar * item_size.length;
+                let margin = first_visible_item_idx as Scalar * item_length;
                 (range, margin)
             },
         };
@@ -93,6 +93,7 @@ pub struct Events<M, D, S>
     num_items: usize,
     mode: M,
     pending_events: PendingEvents<M::Selection, D, S>,
+    is_list_input_handled: bool,
 }
 
 /// The kind of events that the `ListSelect` may `react` to.
@@ -364,10 +365,18 @@ impl<M, D, S> Widget for ListSelect<M, D, S>
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        self.num_items > 0
+    }
+
+    fn handles_navigation_keys(&self) -> bool {
+        true
+    }
+
     /// Update the state of the ListSelect.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, ui, .. } = args;
-        let ListSelect { num_items, item_size, item_instantiation, mode, .. } = self;
+        let ListSelect { common, num_items, item_size, item_instantiation, mode, .. } = self;
 
         // Make sure that `last_selected_entry` refers to an actual selected value in the list.
         // If not push first selected item, if any.
@@ -387,6 +396,11 @@ impl<M, D, S> Widget for ListSelect<M, D, S>
             None => list,
         };
 
+        // The inner `List` does the scrolling, so it must also do any kinetic scrolling.
+        if let Some(kinetic) = common.maybe_kinetic_scroll {
+            list = list.kinetic_scroll(kinetic);
+        }
+
         list.item_instantiation = item_instantiation;
         list.style = style.clone();
         let (items, scrollbar) = list.middle_of(id).wh_of(id).set(state.ids.list, ui);
@@ -397,6 +411,7 @@ impl<M, D, S> Widget for ListSelect<M, D, S>
             num_items: num_items,
             mode: mode,
             pending_events: PendingEvents::new(),
+            is_list_input_handled: false,
         };
 
         (events, scrollbar)
@@ -419,17 +434,13 @@ impl<M, D, S> Events<M, D, S>
             ref mode,
             ref mut items,
             ref mut pending_events,
+            ref mut is_list_input_handled,
         } = *self;
 
         if let Some(event) = pending_events.pop_front() {
             return Some(event);
         }
 
-        let item = match items.next(ui) {
-            Some(item) => item,
-            None => return None,
-        };
-
         // Borrow the `ListSelect::State` from the `Ui`'s widget graph.
         let state = || {
             ui.widget_graph()
@@ -450,6 +461,63 @@ impl<M, D, S> Events<M, D, S>
             }
         };
 
+        // Handle input delivered to the `ListSelect` itself while it has keyboard focus, i.e. via
+        // spatial navigation. Activating the list (with a `Click`, `Return` or `Space`) chooses
+        // the last selected item, or the first if there is none.
+        if !*is_list_input_handled {
+            *is_list_input_handled = true;
+            ensure_last_selected_validity(state());
+            for widget_event in ui.widget_input(id).events() {
+                let activation = match widget_event {
+                    event::Widget::Click(click) => {
+                        pending_events.push_back(Event::Click(click));
+                        Some(click)
+                    },
+
+                    event::Widget::Press(press) => {
+                        pending_events.push_back(Event::Press(press));
+                        match press.key() {
+                            Some(key_press) => match key_press.key {
+                                input::Key::Return | input::Key::Space => Some(event::Click {
+                                    button: input::MouseButton::Left,
+                                    xy: [0.0, 0.0],
+                                    modifiers: key_press.modifiers,
+                                }),
+                                _ => {
+                                    let state = state();
+                                    let i = state.last_selected_entry.get().unwrap_or(0);
+                                    mode.key_selection(key_press, i, num_items, state,
+                                                       &is_selected, pending_events);
+                                    None
+                                },
+                            },
+                            None => None,
+                        }
+                    },
+
+                    _ => None,
+                };
+
+                if let Some(click) = activation {
+                    if num_items > 0 {
+                        let state = state();
+                        let i = state.last_selected_entry.get().unwrap_or(0);
+                        mode.click_selection(click, i, num_items, state,
+                                             &is_selected, pending_events);
+                    }
+                }
+            }
+
+            if let Some(event) = pending_events.pop_front() {
+                return Some(event);
+            }
+        }
+
+        let item = match items.next(ui) {
+            Some(item) => item,
+            None => return None,
+        };
+
         let i = item.i;
 
         // Check for any events that may have occurred to this widget.
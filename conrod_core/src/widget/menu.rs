@@ -0,0 +1,719 @@
+//! A popup `Menu` of items with nested submenus, for use as a context menu or drop down menu.
+
+use {Color, Colorable, FontSize, Borderable, Positionable, Ui};
+use event;
+use input;
+use position::{Dimension, Point, Rect, Scalar};
+use text;
+use utils;
+use widget::{self, Widget};
+
+
+/// The index of an item within each nested level of a `Menu`, from the outermost level to the
+/// level containing the item.
+pub type Path = Vec<usize>;
+
+/// A popup menu of items that opens at a given position, usually the mouse cursor.
+///
+/// The `Menu` opens when the given target widget (or one of its descendants) is right-clicked
+/// (see `context_for`) or when `open_at` is given. Once open, it floats above other widgets
+/// and captures the keyboard until it is closed, either by choosing an item, clicking outside of
+/// the menu or pressing `Escape`.
+///
+/// Items may be navigated with the mouse or with the arrow keys, and chosen by clicking or
+/// pressing `Return`. Hovering over a submenu item (or pressing `Right`) opens the submenu beside
+/// it.
+///
+/// Returns the `Path` of the chosen item, if any.
+#[derive(WidgetCommon_)]
+pub struct Menu<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    items: &'a [Item<'a>],
+    maybe_target: Option<widget::Id>,
+    maybe_open_at: Option<Point>,
+    style: Style,
+}
+
+/// A single item within a `Menu`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Item<'a> {
+    /// The kind of item.
+    pub kind: Kind<'a>,
+    /// The text displayed for the item.
+    pub label: &'a str,
+    /// Text describing the item's keyboard shortcut, displayed beside its label.
+    pub maybe_accelerator: Option<&'a str>,
+    /// Whether or not the item may be chosen.
+    pub enabled: bool,
+}
+
+/// The different kinds of `Item`s.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Kind<'a> {
+    /// An item that may be chosen.
+    Action,
+    /// An item that may be chosen and displays a check mark when `true`.
+    ///
+    /// The `Menu` does not toggle the state for you. Instead, toggle the state whenever the
+    /// item's `Path` is returned.
+    Check(bool),
+    /// An item that opens a nested menu of the given items.
+    Submenu(&'a [Item<'a>]),
+    /// A line separating groups of items.
+    Separator,
+}
+
+/// Unique styling for the `Menu`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// Color of the `Menu`'s background.
+    #[conrod(default = "theme.shape_color")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding the `Menu`.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the `Menu`'s border and separators.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the item labels.
+    #[conrod(default = "theme.label_color")]
+    pub label_color: Option<Color>,
+    /// The font size of the item labels.
+    #[conrod(default = "theme.font_size_small")]
+    pub label_font_size: Option<FontSize>,
+    /// The ID of the font used to display the item labels.
+    #[conrod(default = "theme.font_id")]
+    pub label_font_id: Option<Option<text::font::Id>>,
+    /// The height of each item other than separators.
+    #[conrod(default = "24.0")]
+    pub item_height: Option<Scalar>,
+}
+
+widget_ids! {
+    struct Ids {
+        panels[],
+        highlights[],
+        labels[],
+        accelerators[],
+        marks[],
+        separators[],
+    }
+}
+
+/// The state of the `Menu`.
+pub struct State {
+    ids: Ids,
+    maybe_open: Option<Open>,
+}
+
+/// The state of an open `Menu`.
+#[derive(Clone, Debug, PartialEq)]
+struct Open {
+    /// The position of the top left corner of the outermost level.
+    xy: Point,
+    /// The highlighted item within each open level.
+    highlights: Vec<Option<usize>>,
+    /// The widget that was capturing the keyboard before the `Menu` was opened.
+    maybe_prev_keyboard_capture: Option<widget::Id>,
+}
+
+/// The layout of a single open level of a `Menu`.
+struct Level<'a> {
+    items: &'a [Item<'a>],
+    rect: Rect,
+    item_rects: Vec<Rect>,
+}
+
+impl<'a> Item<'a> {
+
+    /// An item that may be chosen.
+    pub fn new(label: &'a str) -> Self {
+        Item {
+            kind: Kind::Action,
+            label: label,
+            maybe_accelerator: None,
+            enabled: true,
+        }
+    }
+
+    /// An item that may be chosen and displays a check mark when `checked` is `true`.
+    pub fn check(label: &'a str, checked: bool) -> Self {
+        Item { kind: Kind::Check(checked), ..Item::new(label) }
+    }
+
+    /// An item that opens a nested menu of the given items.
+    pub fn submenu(label: &'a str, items: &'a [Item<'a>]) -> Self {
+        Item { kind: Kind::Submenu(items), ..Item::new(label) }
+    }
+
+    /// A line separating groups of items.
+    pub fn separator() -> Self {
+        Item { kind: Kind::Separator, ..Item::new("") }
+    }
+
+    /// Display the given text describing the item's keyboard shortcut beside its label.
+    ///
+    /// Note that this is only a label. Use the `Ui`'s `shortcuts` to register the shortcut itself.
+    pub fn accelerator(mut self, accelerator: &'a str) -> Self {
+        self.maybe_accelerator = Some(accelerator);
+        self
+    }
+
+    /// Whether or not the item may be chosen. Disabled items are displayed faded.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Whether or not the item may be highlighted and chosen.
+    pub fn is_selectable(&self) -> bool {
+        self.enabled && self.kind != Kind::Separator
+    }
+
+    /// The nested items if this is an enabled submenu.
+    fn submenu_items(&self) -> Option<&'a [Item<'a>]> {
+        match self.kind {
+            Kind::Submenu(items) if self.enabled => Some(items),
+            _ => None,
+        }
+    }
+
+}
+
+impl<'a> Menu<'a> {
+
+    /// Construct a new, initially closed `Menu` of the given items.
+    pub fn new(items: &'a [Item<'a>]) -> Self {
+        Menu {
+            common: widget::CommonBuilder::default(),
+            items: items,
+            maybe_target: None,
+            maybe_open_at: None,
+            style: Style::default(),
+        }
+    }
+
+    /// Open the `Menu` at the mouse cursor whenever the given widget or one of its descendants is
+    /// clicked with the right mouse button.
+    pub fn context_for(mut self, target: widget::Id) -> Self {
+        self.maybe_target = Some(target);
+        self
+    }
+
+    /// Open the `Menu` with its top left corner at the given position if it is not already open.
+    ///
+    /// This should only be given upon the event that opens the menu (e.g. a `Button` being
+    /// clicked), as it would otherwise re-open the `Menu` as soon as it is closed. The input
+    /// received before the `Menu` opens, including that event, is ignored by the `Menu`.
+    pub fn open_at(mut self, xy: Point) -> Self {
+        self.maybe_open_at = Some(xy);
+        self
+    }
+
+    /// Specify the font used for displaying the item labels.
+    pub fn label_font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.label_font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub label_color { style.label_color = Some(Color) }
+        pub label_font_size { style.label_font_size = Some(FontSize) }
+        pub item_height { style.item_height = Some(Scalar) }
+    }
+
+}
+
+impl<'a> Widget for Menu<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Option<Path>;
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+            maybe_open: None,
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn handles_navigation_keys(&self) -> bool {
+        true
+    }
+
+    fn default_x_dimension(&self, _ui: &Ui) -> Dimension {
+        Dimension::Absolute(0.0)
+    }
+
+    fn default_y_dimension(&self, _ui: &Ui) -> Dimension {
+        Dimension::Absolute(0.0)
+    }
+
+    /// Update the state of the `Menu`.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, style, ui, .. } = args;
+        let Menu { items, maybe_target, maybe_open_at, .. } = self;
+
+        let was_open = state.maybe_open.is_some();
+        let mut maybe_open = state.maybe_open.clone();
+        let mut maybe_chosen = None;
+
+        // Measurements used to lay out each level.
+        let font_size = style.label_font_size(&ui.theme);
+        let font_id = style.label_font_id(&ui.theme).or(ui.fonts.ids().next());
+        let item_h = style.item_height(&ui.theme);
+        let border = style.border(&ui.theme);
+        let metrics = Metrics {
+            item_h: item_h,
+            separator_h: (item_h / 3.0).round(),
+            border: border,
+            font_size: font_size,
+            maybe_font_id: font_id,
+            win_dim: ui.window_dim(),
+        };
+
+        // The input received since the last update (e.g. the click upon which `open_at` was given)
+        // occurred before the menu opened via `open_at`, so it must not close the menu.
+        let mut is_opened_at = false;
+        if let Some(xy) = maybe_open_at {
+            if maybe_open.is_none() {
+                maybe_open = Some(Open::at(xy, ui));
+                is_opened_at = true;
+            }
+        }
+
+        // Handle mouse events in the order in which they occurred.
+        for event in ui.global_input().events().ui().filter(|_| !is_opened_at) {
+            match *event {
+
+                // Right-clicking the target (re)opens the menu at the cursor.
+                event::Ui::Click(Some(clicked), click) if click.button == input::MouseButton::Right => {
+                    let is_target = maybe_target.map(|target| {
+                        target == clicked
+                            || ui.widget_graph().does_recursive_depth_edge_exist(target, clicked)
+                    }).unwrap_or(false);
+                    if is_target {
+                        let prev = maybe_open.as_ref().and_then(|open| open.maybe_prev_keyboard_capture);
+                        let mut open = Open::at(click.xy, ui);
+                        open.maybe_prev_keyboard_capture = prev.or(open.maybe_prev_keyboard_capture);
+                        maybe_open = Some(open);
+                    }
+                },
+
+                // Highlight the item under the cursor.
+                event::Ui::Motion(_, event::Motion { motion: input::Motion::MouseCursor { x, y }, .. }) => {
+                    if let Some(ref mut open) = maybe_open {
+                        let levels = open.levels(items, ui, &metrics);
+                        open.hover([x, y], &levels);
+                    }
+                },
+
+                // Pressing any mouse button outside of the menu closes it.
+                event::Ui::Press(_, event::Press { button: event::Button::Mouse(_, xy), .. }) => {
+                    let is_outside = maybe_open.as_ref().map(|open| {
+                        let levels = open.levels(items, ui, &metrics);
+                        !levels.iter().any(|level| level.rect.is_over(xy))
+                    }).unwrap_or(false);
+                    if is_outside {
+                        maybe_open = None;
+                    }
+                },
+
+                // Releasing the left mouse button over an item activates it.
+                event::Ui::Release(_, event::Release {
+                    button: event::Button::Mouse(input::MouseButton::Left, xy), ..
+                }) => {
+                    let mut is_closed = false;
+                    if let Some(ref mut open) = maybe_open {
+                        let levels = open.levels(items, ui, &metrics);
+                        if let Some((level, i)) = item_at(xy, &levels) {
+                            open.hover(xy, &levels);
+                            maybe_chosen = open.activate(level, i, &levels);
+                            is_closed = maybe_chosen.is_some();
+                        }
+                    }
+                    if is_closed {
+                        maybe_open = None;
+                    }
+                },
+
+                _ => (),
+            }
+        }
+
+        // Handle key presses while the menu is capturing the keyboard.
+        for press in ui.widget_input(id).presses().key() {
+            let mut is_closed = false;
+            if let Some(ref mut open) = maybe_open {
+                let levels = open.levels(items, ui, &metrics);
+                let deepest = levels.len() - 1;
+                match press.key {
+                    input::Key::Escape => is_closed = true,
+                    input::Key::Up | input::Key::Down => {
+                        let forwards = press.key == input::Key::Down;
+                        let current = open.highlights[deepest];
+                        open.highlights[deepest] =
+                            next_selectable(levels[deepest].items, current, forwards);
+                    },
+                    input::Key::Right => if let Some(i) = open.highlights[deepest] {
+                        if levels[deepest].items[i].submenu_items().is_some() {
+                            open.activate(deepest, i, &levels);
+                        }
+                    },
+                    input::Key::Left => if deepest > 0 {
+                        open.highlights.pop();
+                    },
+                    input::Key::Return | input::Key::Space => {
+                        if let Some(i) = open.highlights[deepest] {
+                            maybe_chosen = open.activate(deepest, i, &levels);
+                            is_closed = maybe_chosen.is_some();
+                        }
+                    },
+                    _ => (),
+                }
+            }
+            if is_closed {
+                maybe_open = None;
+            }
+        }
+
+        // Capture the keyboard while open, returning it to the previous widget once closed.
+        let is_capturing = ui.global_input().current.widget_capturing_keyboard == Some(id);
+        match (was_open, &maybe_open) {
+            (_, &Some(_)) => if !is_capturing {
+                ui.keyboard_capture(id);
+            },
+            (true, &None) => {
+                let prev = state.maybe_open.as_ref().and_then(|open| open.maybe_prev_keyboard_capture);
+                match (is_capturing, prev) {
+                    (true, Some(prev)) => ui.keyboard_capture(prev),
+                    (true, None) => ui.keyboard_uncapture(),
+                    _ => (),
+                }
+            },
+            _ => (),
+        }
+
+        if state.maybe_open != maybe_open {
+            state.update(|state| state.maybe_open = maybe_open.clone());
+        }
+
+        let open = match maybe_open {
+            Some(open) => open,
+            None => return maybe_chosen,
+        };
+
+        // Instantiate the open levels.
+        let levels = open.levels(items, ui, &metrics);
+        let num_items = levels.iter().map(|level| level.items.len()).sum();
+        if state.ids.panels.len() < levels.len() || state.ids.labels.len() < num_items {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                let num_levels = utils::partial_max(state.ids.panels.len(), levels.len());
+                state.ids.panels.resize(num_levels, id_gen);
+                state.ids.highlights.resize(num_items, id_gen);
+                state.ids.labels.resize(num_items, id_gen);
+                state.ids.accelerators.resize(num_items, id_gen);
+                state.ids.marks.resize(num_items, id_gen);
+                state.ids.separators.resize(num_items, id_gen);
+            });
+        }
+
+        let color = style.color(&ui.theme);
+        let border_color = style.border_color(&ui.theme);
+        let label_color = style.label_color(&ui.theme);
+        let padding = item_h / 2.0;
+        let mut slot = 0;
+        for (level_idx, level) in levels.iter().enumerate() {
+            let panel = state.ids.panels[level_idx];
+            widget::BorderedRectangle::new(level.rect.dim())
+                .xy(level.rect.xy())
+                .color(color)
+                .border(border)
+                .border_color(border_color)
+                .floating(true)
+                .parent(id)
+                .set(panel, ui);
+
+            for (i, (item, item_rect)) in level.items.iter().zip(level.item_rects.iter()).enumerate() {
+                let slot_idx = slot;
+                slot += 1;
+
+                if let Kind::Separator = item.kind {
+                    let y = item_rect.y();
+                    let start = [item_rect.left() + padding / 2.0, y];
+                    let end = [item_rect.right() - padding / 2.0, y];
+                    widget::Line::abs(start, end)
+                        .color(border_color)
+                        .thickness(border.max(1.0))
+                        .graphics_for(panel)
+                        .parent(panel)
+                        .set(state.ids.separators[slot_idx], ui);
+                    continue;
+                }
+
+                if open.highlights[level_idx] == Some(i) {
+                    widget::Rectangle::fill(item_rect.dim())
+                        .xy(item_rect.xy())
+                        .color(color.highlighted())
+                        .graphics_for(panel)
+                        .parent(panel)
+                        .set(state.ids.highlights[slot_idx], ui);
+                }
+
+                let item_label_color = if item.enabled { label_color } else { label_color.alpha(0.4) };
+                let label_x = item_rect.left() + item_h + text_width(item.label, ui, &metrics) / 2.0;
+                widget::Text::new(item.label)
+                    .and_then(font_id, widget::Text::font_id)
+                    .x_y(label_x, item_rect.y())
+                    .no_line_wrap()
+                    .color(item_label_color)
+                    .font_size(font_size)
+                    .graphics_for(panel)
+                    .parent(panel)
+                    .set(state.ids.labels[slot_idx], ui);
+
+                if let Some(accelerator) = item.maybe_accelerator {
+                    let x = item_rect.right() - item_h - text_width(accelerator, ui, &metrics) / 2.0;
+                    widget::Text::new(accelerator)
+                        .and_then(font_id, widget::Text::font_id)
+                        .x_y(x, item_rect.y())
+                        .no_line_wrap()
+                        .color(item_label_color)
+                        .font_size(font_size)
+                        .graphics_for(panel)
+                        .parent(panel)
+                        .set(state.ids.accelerators[slot_idx], ui);
+                }
+
+                // A check mark to the left of checked items or an arrow to the right of submenus.
+                let quarter = item_h / 4.0;
+                match item.kind {
+                    Kind::Check(true) => {
+                        let [x, y] = [item_rect.left() + item_h / 2.0, item_rect.y()];
+                        let points = [
+                            [x - quarter, y],
+                            [x - quarter / 3.0, y - quarter * 2.0 / 3.0],
+                            [x + quarter, y + quarter * 2.0 / 3.0],
+                        ];
+                        widget::PointPath::abs(points.iter().cloned())
+                            .color(item_label_color)
+                            .thickness(2.0)
+                            .graphics_for(panel)
+                            .parent(panel)
+                            .set(state.ids.marks[slot_idx], ui);
+                    },
+                    Kind::Submenu(_) => {
+                        let [x, y] = [item_rect.right() - item_h / 2.0, item_rect.y()];
+                        let points = [
+                            [x - quarter / 2.0, y + quarter],
+                            [x + quarter / 2.0, y],
+                            [x - quarter / 2.0, y - quarter],
+                        ];
+                        widget::Polygon::abs_fill(points.iter().cloned())
+                            .color(item_label_color)
+                            .graphics_for(panel)
+                            .parent(panel)
+                            .set(state.ids.marks[slot_idx], ui);
+                    },
+                    _ => (),
+                }
+            }
+        }
+
+        maybe_chosen
+    }
+}
+
+/// Measurements shared by every level of a `Menu`.
+struct Metrics {
+    item_h: Scalar,
+    separator_h: Scalar,
+    border: Scalar,
+    font_size: FontSize,
+    maybe_font_id: Option<text::font::Id>,
+    win_dim: [Scalar; 2],
+}
+
+impl Open {
+
+    /// An open menu with its top left corner at the given position and no highlighted item.
+    fn at(xy: Point, ui: &Ui) -> Self {
+        Open {
+            xy: xy,
+            highlights: vec![None],
+            maybe_prev_keyboard_capture: ui.global_input().current.widget_capturing_keyboard,
+        }
+    }
+
+    /// The layout of each open level, from the outermost to the innermost.
+    fn levels<'a>(&self, items: &'a [Item<'a>], ui: &Ui, metrics: &Metrics) -> Vec<Level<'a>> {
+        let mut levels: Vec<Level<'a>> = Vec::with_capacity(self.highlights.len());
+        let mut level_items = items;
+        for depth in 0..self.highlights.len() {
+            let dim = [panel_width(level_items, ui, metrics), panel_height(level_items, metrics)];
+            let top_left = match levels.last() {
+                None => root_top_left(self.xy, dim, metrics.win_dim),
+                Some(parent) => {
+                    let i = self.highlights[depth - 1].expect("open submenu must be highlighted");
+                    submenu_top_left(parent.rect, parent.item_rects[i], dim, metrics)
+                },
+            };
+            let (rect, item_rects) = layout(level_items, top_left, dim[0], metrics);
+            levels.push(Level { items: level_items, rect: rect, item_rects: item_rects });
+
+            // Continue into the submenu if there is one open.
+            let open_submenu = self.highlights.get(depth + 1)
+                .and(self.highlights[depth])
+                .and_then(|i| level_items[i].submenu_items());
+            match open_submenu {
+                Some(sub_items) => level_items = sub_items,
+                None => break,
+            }
+        }
+        levels
+    }
+
+    /// Highlight the item under `xy`, opening it if it is a submenu.
+    fn hover(&mut self, xy: Point, levels: &[Level]) {
+        if let Some((level, i)) = item_at(xy, levels) {
+            let item = &levels[level].items[i];
+            self.highlights.truncate(level + 1);
+            if item.is_selectable() {
+                self.highlights[level] = Some(i);
+                if item.submenu_items().is_some() {
+                    self.highlights.push(None);
+                }
+            } else {
+                self.highlights[level] = None;
+            }
+        }
+    }
+
+    /// Activate the `i`th item of the given level.
+    ///
+    /// Opens the item if it is a submenu, otherwise returns its `Path` if it may be chosen.
+    fn activate(&mut self, level: usize, i: usize, levels: &[Level]) -> Option<Path> {
+        let item = &levels[level].items[i];
+        if !item.is_selectable() {
+            return None;
+        }
+        self.highlights.truncate(level + 1);
+        self.highlights[level] = Some(i);
+        match item.submenu_items() {
+            Some(sub_items) => {
+                self.highlights.push(next_selectable(sub_items, None, true));
+                None
+            },
+            None => Some(self.highlights.iter().map(|i| i.expect("highlighted")).collect()),
+        }
+    }
+
+}
+
+/// The width of the given text when displayed as an item label.
+fn text_width(text: &str, ui: &Ui, metrics: &Metrics) -> Scalar {
+    metrics.maybe_font_id
+        .and_then(|id| ui.fonts.get(id))
+        .map(|font| text::line::width(text, font, metrics.font_size))
+        .unwrap_or(0.0)
+}
+
+/// The width of a level displaying the given items.
+fn panel_width(items: &[Item], ui: &Ui, metrics: &Metrics) -> Scalar {
+    // Leave room for a check mark to the left and a submenu arrow to the right of each label.
+    let widest = items.iter().fold(0.0, |widest, item| {
+        let label_w = text_width(item.label, ui, metrics);
+        let accelerator_w = item.maybe_accelerator.map(|a| metrics.item_h + text_width(a, ui, metrics));
+        utils::partial_max(widest, label_w + accelerator_w.unwrap_or(0.0))
+    });
+    widest + metrics.item_h * 2.0 + metrics.border * 2.0
+}
+
+/// The height of a level displaying the given items.
+fn panel_height(items: &[Item], metrics: &Metrics) -> Scalar {
+    let items_h = items.iter().fold(0.0, |h, item| match item.kind {
+        Kind::Separator => h + metrics.separator_h,
+        _ => h + metrics.item_h,
+    });
+    items_h + metrics.border * 2.0
+}
+
+/// The rectangle of a level and each of its items.
+fn layout(items: &[Item], top_left: Point, w: Scalar, metrics: &Metrics) -> (Rect, Vec<Rect>) {
+    let h = panel_height(items, metrics);
+    let rect = Rect::from_corners(top_left, [top_left[0] + w, top_left[1] - h]);
+    let left = rect.left() + metrics.border;
+    let right = rect.right() - metrics.border;
+    let mut top = rect.top() - metrics.border;
+    let item_rects = items.iter().map(|item| {
+        let item_h = match item.kind {
+            Kind::Separator => metrics.separator_h,
+            _ => metrics.item_h,
+        };
+        let item_rect = Rect::from_corners([left, top], [right, top - item_h]);
+        top -= item_h;
+        item_rect
+    }).collect();
+    (rect, item_rects)
+}
+
+/// The top left corner of the outermost level when opened at `xy`.
+///
+/// The level opens to the left of and above `xy` where it would otherwise leave the window.
+fn root_top_left(xy: Point, dim: [Scalar; 2], win_dim: [Scalar; 2]) -> Point {
+    let (half_win_w, half_win_h) = (win_dim[0] / 2.0, win_dim[1] / 2.0);
+    let x = if xy[0] + dim[0] > half_win_w { xy[0] - dim[0] } else { xy[0] };
+    let y = if xy[1] - dim[1] < -half_win_h { xy[1] + dim[1] } else { xy[1] };
+    [utils::clamp(x, -half_win_w, half_win_w - dim[0]), utils::clamp(y, -half_win_h + dim[1], half_win_h)]
+}
+
+/// The top left corner of a submenu opened from the given item of the given parent level.
+///
+/// The submenu opens to the right of its parent unless it would leave the window.
+fn submenu_top_left(parent: Rect, item: Rect, dim: [Scalar; 2], metrics: &Metrics) -> Point {
+    let (half_win_w, half_win_h) = (metrics.win_dim[0] / 2.0, metrics.win_dim[1] / 2.0);
+    let x = if parent.right() + dim[0] > half_win_w { parent.left() - dim[0] } else { parent.right() };
+    let y = item.top() + metrics.border;
+    [utils::clamp(x, -half_win_w, half_win_w - dim[0]), utils::clamp(y, -half_win_h + dim[1], half_win_h)]
+}
+
+/// The innermost level and index of the item under `xy`, if any.
+fn item_at(xy: Point, levels: &[Level]) -> Option<(usize, usize)> {
+    levels.iter().enumerate().rev()
+        .find(|&(_, level)| level.rect.is_over(xy))
+        .and_then(|(level_idx, level)| {
+            level.item_rects.iter()
+                .position(|rect| rect.is_over(xy))
+                .map(|i| (level_idx, i))
+        })
+}
+
+/// The next selectable item after (or before) `current`, wrapping around the ends of `items`.
+fn next_selectable(items: &[Item], current: Option<usize>, forwards: bool) -> Option<usize> {
+    let len = items.len();
+    (1..len + 1)
+        .map(|step| match (current, forwards) {
+            (None, true) => step - 1,
+            (None, false) => len - step,
+            (Some(i), true) => (i + step) % len,
+            (Some(i), false) => (i + len - step % len) % len,
+        })
+        .find(|&i| items[i].is_selectable())
+}
+
+
+impl<'a> Colorable for Menu<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for Menu<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}
@@ -4,12 +4,14 @@
 //! re-exports all widgets (and their modules) that are provided by conrod.
 
 use graph::{Container, UniqueWidgetState};
+use input;
 use position::{Align, Depth, Dimension, Dimensions, Padding, Position, Point,
-               Positionable, Rect, Relative, Sizeable};
+               Positionable, Rect, Relative, Scalar, Sizeable};
 use std;
 use text::font;
 use theme::{self, Theme};
 use ui::{self, Ui, UiCell};
+use utils;
 
 
 pub use self::id::Id;
@@ -28,13 +30,17 @@ pub use self::bordered_rectangle::BorderedRectangle;
 pub use self::button::Button;
 pub use self::canvas::Canvas;
 pub use self::collapsible_area::CollapsibleArea;
+pub use self::dock::Dock;
 pub use self::drop_down_list::DropDownList;
 pub use self::envelope_editor::EnvelopeEditor;
 pub use self::file_navigator::FileNavigator;
+pub use self::flex::Flex;
+pub use self::flow::Flow;
 pub use self::grid::Grid;
 pub use self::list::List;
 pub use self::list_select::ListSelect;
 pub use self::matrix::Matrix;
+pub use self::menu::Menu;
 pub use self::graph::Graph;
 pub use self::number_dialer::NumberDialer;
 pub use self::plot_path::PlotPath;
@@ -42,11 +48,13 @@ pub use self::range_slider::RangeSlider;
 pub use self::rounded_rectangle::RoundedRectangle;
 pub use self::scrollbar::Scrollbar;
 pub use self::slider::Slider;
+pub use self::splitter::Splitter;
 pub use self::tabs::Tabs;
 pub use self::text_box::TextBox;
 pub use self::text_edit::TextEdit;
 pub use self::title_bar::TitleBar;
 pub use self::toggle::Toggle;
+pub use self::tooltip::Tooltip;
 pub use self::xy_pad::XYPad;
 
 
@@ -66,13 +74,17 @@ pub mod bordered_rectangle;
 pub mod button;
 pub mod canvas;
 pub mod collapsible_area;
+pub mod dock;
 pub mod drop_down_list;
 pub mod envelope_editor;
 pub mod file_navigator;
+pub mod flex;
+pub mod flow;
 pub mod grid;
 pub mod list;
 pub mod list_select;
 pub mod matrix;
+pub mod menu;
 pub mod graph;
 pub mod number_dialer;
 pub mod plot_path;
@@ -80,11 +92,13 @@ pub mod range_slider;
 pub mod rounded_rectangle;
 pub mod scrollbar;
 pub mod slider;
+pub mod splitter;
 pub mod tabs;
 pub mod text_box;
 pub mod text_edit;
 pub mod title_bar;
 pub mod toggle;
+pub mod tooltip;
 pub mod xy_pad;
 
 
@@ -215,6 +229,8 @@ pub struct CommonBuilder {
     pub maybe_x_scroll: Option<scroll::Scroll>,
     /// Arguments to the scrolling of the widget's *y* axis.
     pub maybe_y_scroll: Option<scroll::Scroll>,
+    /// The parameters for kinetic scrolling along the widget's scrollable axes, if any.
+    pub maybe_kinetic_scroll: Option<scroll::Kinetic>,
     /// Whether or not the **Widget** should be placed on the kid_area.
     ///
     /// If `true`, the **Widget** will be placed on the `kid_area` of the parent **Widget** if the
@@ -236,6 +252,11 @@ pub struct CommonBuilder {
     /// default.
     /// - Any **Graphic** child of *b* will be considered as a **Graphic** child of *a*.
     pub maybe_graphics_for: Option<Id>,
+    /// The position of the **Widget** within the `Ui`'s keyboard focus chain.
+    ///
+    /// Focusable widgets with a tab index are visited in ascending order before all others, which
+    /// are visited in the order in which they were instantiated.
+    pub maybe_tab_index: Option<usize>,
 }
 
 /// Styling and positioning data that is common between all widget types.
@@ -245,6 +266,14 @@ pub struct CommonStyle {
     pub maybe_x_dimension: Option<Dimension>,
     /// The height of a Widget.
     pub maybe_y_dimension: Option<Dimension>,
+    /// The minimum width of a Widget.
+    pub maybe_min_x_dimension: Option<Dimension>,
+    /// The maximum width of a Widget.
+    pub maybe_max_x_dimension: Option<Dimension>,
+    /// The minimum height of a Widget.
+    pub maybe_min_y_dimension: Option<Dimension>,
+    /// The maximum height of a Widget.
+    pub maybe_max_y_dimension: Option<Dimension>,
     /// The position of a Widget along the *x* axis.
     pub maybe_x_position: Option<Position>,
     /// The position of a Widget along the *y* axis.
@@ -326,7 +355,15 @@ pub struct PreUpdateCache {
     /// widget.
     pub maybe_graphics_for: Option<Id>,
     /// A function describing whether or not a given point is over the widget.
-    pub is_over: IsOverFn
+    pub is_over: IsOverFn,
+    /// Whether or not the **Widget** may receive keyboard focus via `Tab` and `Shift+Tab`.
+    pub is_focusable: bool,
+    /// Whether or not the **Widget** accepts text input while capturing the keyboard.
+    pub is_text_input: bool,
+    /// Whether or not the **Widget** handles keys bound to spatial navigation itself.
+    pub handles_navigation_keys: bool,
+    /// The position of the **Widget** within the keyboard focus chain if one was given.
+    pub maybe_tab_index: Option<usize>,
 }
 
 // **Widget** data to be cached after the **Widget::update** call in the **widget::set_widget**
@@ -386,6 +423,51 @@ pub trait Style: std::any::Any + std::fmt::Debug + PartialEq + Sized {}
 impl<T> Style for T where T: std::any::Any + std::fmt::Debug + PartialEq + Sized {}
 
 
+/// Resolve the dimensions of a widget with a `Dimension::ToContent` along either axis.
+///
+/// Returns `None` if neither dimension is sized to the widget's content.
+///
+/// If the widget cannot measure its own content, its previous dimension is used until it is fit
+/// around its children once they have been set, within the given minimum and maximum `bounds`.
+fn content_sized_wh<W>(widget: &W,
+                       id: Id,
+                       bounds: [(Option<Scalar>, Option<Scalar>); 2],
+                       ui: &mut UiCell) -> Option<Dimensions>
+    where W: Widget,
+{
+    let content_pad = |dimension| match dimension {
+        Dimension::ToContent(maybe_pad) => Some(maybe_pad.unwrap_or(0.0)),
+        _ => None,
+    };
+    let pads = [
+        content_pad(widget.get_x_dimension(ui)),
+        content_pad(widget.get_y_dimension(ui)),
+    ];
+    if pads == [None, None] {
+        return None;
+    }
+
+    let maybe_content_wh = widget.content_wh(ui);
+    let prev_wh = ui.rect_of(id).map(|rect| rect.dim()).unwrap_or([0.0, 0.0]);
+    let mut wh = [widget.get_w(ui).unwrap_or(0.0), widget.get_h(ui).unwrap_or(0.0)];
+    let mut fit = [None, None];
+    for i in 0..2 {
+        if let Some(pad) = pads[i] {
+            match maybe_content_wh {
+                Some(content_wh) => wh[i] = content_wh[i] + pad * 2.0,
+                None => {
+                    wh[i] = prev_wh[i];
+                    fit[i] = Some(pad);
+                },
+            }
+        }
+    }
+    if fit != [None, None] {
+        ui::fit_to_content(ui::ref_mut_from_ui_cell(ui), id, fit, bounds);
+    }
+    Some([widget.clamp_w(wh[0], ui), widget.clamp_h(wh[1], ui)])
+}
+
 /// Determines the default **Dimension** for a **Widget**.
 ///
 /// This function checks for a default dimension in the following order.
@@ -660,6 +742,16 @@ pub trait Widget: Common + Sized {
         default_y_dimension(self, ui)
     }
 
+    /// The dimensions of the widget's content, used to resolve `Dimension::ToContent`.
+    ///
+    /// Widgets that can measure their content without being set (e.g. from the width of their
+    /// label) should implement this. By default `None` is returned, in which case the widget is fit
+    /// around the bounding box of its children once they have been set. Note that this leaves
+    /// widgets without children (e.g. a `Slider` sized to its label) with no content at all.
+    fn content_wh(&self, _ui: &Ui) -> Option<Dimensions> {
+        None
+    }
+
     /// If the widget is draggable, implement this method and return the position and dimensions of
     /// the draggable space. The position should be relative to the center of the widget.
     fn drag_area(&self,
@@ -698,6 +790,40 @@ pub trait Widget: Common + Sized {
         is_over_rect
     }
 
+    /// Whether or not the widget belongs to the `Ui`'s keyboard focus chain.
+    ///
+    /// Focusable widgets may be given keyboard capture by pressing `Tab` or `Shift+Tab`, or via
+    /// spatial navigation (see `input::navigation`). Widgets that handle keyboard input or that
+    /// may be activated by a `Click` should override this to return `true`.
+    ///
+    /// By default, this returns `false`.
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// Whether or not the widget accepts text input while capturing the keyboard.
+    ///
+    /// Keys that would enter text (or move a text cursor) are left to such widgets rather than
+    /// triggering shortcuts or spatial navigation.
+    ///
+    /// By default, this returns `false`.
+    fn is_text_input(&self) -> bool {
+        false
+    }
+
+    /// Whether or not the widget handles keys bound to spatial navigation (see
+    /// `input::navigation`) itself.
+    ///
+    /// While this widget or any of its descendants is capturing the keyboard, such keys are
+    /// delivered to the capturing widget rather than moving focus. Widgets that use the arrow keys
+    /// or `Return` to move through or choose from their own contents should override this to
+    /// return `true`.
+    ///
+    /// By default, this returns `false`.
+    fn handles_navigation_keys(&self) -> bool {
+        false
+    }
+
 
     // None of the following methods should require overriding. Perhaps they should be split off
     // into a separate trait which is impl'ed for W: Widget to make this clearer?
@@ -754,6 +880,16 @@ pub trait Widget: Common + Sized {
         self
     }
 
+    /// Specify the position of the widget within the `Ui`'s keyboard focus chain.
+    ///
+    /// Widgets with a tab index are visited in ascending order before those without, which are
+    /// visited in the order in which they were instantiated. This has no effect if the widget is
+    /// not focusable (see `Widget::is_focusable`).
+    fn tab_index(mut self, index: usize) -> Self {
+        self.common_mut().maybe_tab_index = Some(index);
+        self
+    }
+
     /// Set whether or not the widget is floating (the default is `false`).
     /// A typical example of a floating widget would be a pop-up or alert window.
     ///
@@ -805,6 +941,25 @@ pub trait Widget: Common + Sized {
         self.crop_kids()
     }
 
+    /// Makes the widget's scrollable axes scroll kinetically with the default `scroll::Kinetic`
+    /// parameters.
+    ///
+    /// Scrolling will continue with decaying momentum once scroll input stops and bounce off
+    /// either end of the scrollable range.
+    ///
+    /// This has no effect unless the widget is also made scrollable (e.g. via `scroll_kids`).
+    fn scroll_kinetically(self) -> Self {
+        self.kinetic_scroll(scroll::Kinetic::new())
+    }
+
+    /// Makes the widget's scrollable axes scroll kinetically with the given parameters.
+    ///
+    /// This has no effect unless the widget is also made scrollable (e.g. via `scroll_kids`).
+    fn kinetic_scroll(mut self, kinetic: scroll::Kinetic) -> Self {
+        self.common_mut().maybe_kinetic_scroll = Some(kinetic);
+        self
+    }
+
     /// A builder method that "lifts" the **Widget** through the given `build` function.
     ///
     /// This method is solely for providing slight ergonomic improvement by helping to maintain
@@ -944,7 +1099,6 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
 
     let new_style = widget.style();
     let depth = widget.get_depth();
-    let dim = widget.get_wh(&ui).unwrap_or([0.0, 0.0]);
     let x_pos = widget.get_x_position(ui);
     let y_pos = widget.get_y_position(ui);
     let place_on_kid_area = widget.common().place_on_kid_area;
@@ -953,6 +1107,22 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
     // check the positioning to retrieve the Id from there.
     let maybe_parent_id = widget.common().maybe_parent_id.get(id, ui, x_pos, y_pos);
 
+    // Any widgets sized to their content that this widget is not a descendant of have had all of
+    // their children set, so fit them before this widget is positioned or sized relative to them.
+    ui::fit_finished_widgets_to_content(ui::ref_mut_from_ui_cell(ui), maybe_parent_id);
+
+    // The minimum and maximum width and height of the widget.
+    let bounds = [
+        (widget.get_min_w(ui), widget.get_max_w(ui)),
+        (widget.get_min_h(ui), widget.get_max_h(ui)),
+    ];
+    let dim = content_sized_wh(&widget, id, bounds, ui)
+        .unwrap_or_else(|| widget.get_wh(&ui).unwrap_or([0.0, 0.0]));
+
+    // Resolve any layout constraints registered for the widget within its own bounds.
+    let dim = ui::solve_constraints(ui::ref_mut_from_ui_cell(ui), id, x_pos, y_pos, dim, bounds,
+                                    place_on_kid_area);
+
     // Calculate the `xy` location of the widget, considering drag.
     let xy = maybe_prev_common
         .as_ref()
@@ -962,7 +1132,7 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
                 let mut left_mouse_drags = ui.widget_input(id).drags().left();
                 let maybe_first_drag = left_mouse_drags.next();
                 let prev_xy = prev.rect.xy();
-                maybe_first_drag
+                let xy = maybe_first_drag
                     .and_then(|first_drag| {
                         if drag_area.is_over(first_drag.from) {
                             let total_drag_xy = left_mouse_drags
@@ -974,7 +1144,22 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
                             None
                         }
                     })
-                    .unwrap_or(prev_xy)
+                    .unwrap_or(prev_xy);
+
+                // If the drag was cancelled, return the widget to where the drag began.
+                //
+                // The widget has followed the drag, so the drag began over its drag area if the
+                // point at which the drag was cancelled is over it now.
+                ui.widget_input(id)
+                    .drag_cancels()
+                    .filter(|cancel| cancel.button == input::MouseButton::Left)
+                    .filter(|cancel| drag_area.is_over(cancel.xy))
+                    .last()
+                    .map(|cancel| {
+                        let total_drag_xy = utils::vec2_sub(cancel.xy, cancel.origin);
+                        utils::vec2_sub(xy, total_drag_xy)
+                    })
+                    .unwrap_or(xy)
             })
         })
         // If there is no previous state to compare for dragging, return an initial state.
@@ -988,9 +1173,7 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
     // Check whether or not the widget is a "floating" (hovering / pop-up style) widget.
     let maybe_floating = if widget.common().is_floating {
 
-        fn new_floating() -> Floating {
-            Floating { time_last_clicked: std::time::Instant::now() }
-        }
+        let new_floating = || Floating { time_last_clicked: ui.clock().now() };
 
         // If it is floating, check to see if we need to update the last time it was clicked.
         match maybe_prev_common.as_ref() {
@@ -1037,12 +1220,15 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
     // TODO: On the first time the widget is set (i.e. if `maybe_prev_*_scroll_state` is `None` and
     // `maybe_*_scroll` is `Some`) we should consider and handle the `scroll_args`'
     // `maybe_initial_alignment` field.
+    let maybe_kinetic = widget.common().maybe_kinetic_scroll;
     let mut maybe_x_scroll_state = widget.common().maybe_x_scroll.map(|_scroll_args| {
-        let maybe_prev = maybe_prev_common.as_ref().and_then(|p| p.maybe_x_scroll_state);
+        let maybe_prev = maybe_prev_common.as_ref().and_then(|p| p.maybe_x_scroll_state)
+            .map(|mut prev| { prev.maybe_kinetic = maybe_kinetic; prev });
         scroll::State::update(ui, id, &prev_kid_area, maybe_prev, 0.0)
     });
     let mut maybe_y_scroll_state = widget.common().maybe_y_scroll.map(|_scroll_args| {
-        let maybe_prev = maybe_prev_common.as_ref().and_then(|p| p.maybe_y_scroll_state);
+        let maybe_prev = maybe_prev_common.as_ref().and_then(|p| p.maybe_y_scroll_state)
+            .map(|mut prev| { prev.maybe_kinetic = maybe_kinetic; prev });
         scroll::State::update(ui, id, &prev_kid_area, maybe_prev, 0.0)
     });
 
@@ -1096,6 +1282,10 @@ fn set_widget<'a, 'b, W>(widget: W, id: Id, ui: &'a mut UiCell<'b>) -> W::Event
             maybe_x_scroll_state: maybe_x_scroll_state,
             maybe_graphics_for: widget.common().maybe_graphics_for,
             is_over: widget.is_over(),
+            is_focusable: widget.is_focusable(),
+            is_text_input: widget.is_text_input(),
+            handles_navigation_keys: widget.handles_navigation_keys(),
+            maybe_tab_index: widget.common().maybe_tab_index,
         });
     }
 
@@ -1214,7 +1404,9 @@ impl Default for CommonBuilder {
             is_floating: false,
             maybe_x_scroll: None,
             maybe_y_scroll: None,
+            maybe_kinetic_scroll: None,
             crop_kids: false,
+            maybe_tab_index: None,
         }
     }
 }
@@ -1296,4 +1488,48 @@ impl<W> Sizeable for W
     fn get_y_dimension(&self, ui: &Ui) -> Dimension {
         self.common().style.maybe_y_dimension.unwrap_or_else(|| self.default_y_dimension(ui))
     }
+    fn min_x_dimension(mut self, min: Dimension) -> Self {
+        self.common_mut().style.maybe_min_x_dimension = Some(min);
+        self
+    }
+    fn max_x_dimension(mut self, max: Dimension) -> Self {
+        self.common_mut().style.maybe_max_x_dimension = Some(max);
+        self
+    }
+    fn min_y_dimension(mut self, min: Dimension) -> Self {
+        self.common_mut().style.maybe_min_y_dimension = Some(min);
+        self
+    }
+    fn max_y_dimension(mut self, max: Dimension) -> Self {
+        self.common_mut().style.maybe_max_y_dimension = Some(max);
+        self
+    }
+    /// The given minimum width, otherwise the minimum width within the **Theme**'s default style
+    /// for this widget, if any.
+    fn get_min_x_dimension(&self, ui: &Ui) -> Option<Dimension> {
+        self.common().style.maybe_min_x_dimension.or_else(|| {
+            ui.theme.widget_style::<W::Style>().and_then(|d| d.common.maybe_min_x_dimension)
+        })
+    }
+    /// The given maximum width, otherwise the maximum width within the **Theme**'s default style
+    /// for this widget, if any.
+    fn get_max_x_dimension(&self, ui: &Ui) -> Option<Dimension> {
+        self.common().style.maybe_max_x_dimension.or_else(|| {
+            ui.theme.widget_style::<W::Style>().and_then(|d| d.common.maybe_max_x_dimension)
+        })
+    }
+    /// The given minimum height, otherwise the minimum height within the **Theme**'s default style
+    /// for this widget, if any.
+    fn get_min_y_dimension(&self, ui: &Ui) -> Option<Dimension> {
+        self.common().style.maybe_min_y_dimension.or_else(|| {
+            ui.theme.widget_style::<W::Style>().and_then(|d| d.common.maybe_min_y_dimension)
+        })
+    }
+    /// The given maximum height, otherwise the maximum height within the **Theme**'s default style
+    /// for this widget, if any.
+    fn get_max_y_dimension(&self, ui: &Ui) -> Option<Dimension> {
+        self.common().style.maybe_max_y_dimension.or_else(|| {
+            ui.theme.widget_style::<W::Style>().and_then(|d| d.common.maybe_max_y_dimension)
+        })
+    }
 }
@@ -1,19 +1,31 @@
 //! The primitive widget used for displaying text.
 
-use {Color, Colorable, FontSize, Ui, Widget};
-use position::{Dimension, Scalar};
+use {Color, Colorable, FontSize, Positionable, Ui, Widget};
+use cursor;
+use event;
+use graph;
+use input;
+use position::{Align, Depth, Dimension, Dimensions, Rect, Scalar};
 use std;
 use text;
 use utils;
 use widget;
 
 
+/// How far behind the **Text** the rectangles highlighting its selection are placed.
+const SELECTION_DEPTH_OFFSET: Depth = 0.001;
+
+
 /// Displays some given text centered within a rectangular area.
 ///
 /// By default, the rectangular dimensions are fit to the area occuppied by the text.
 ///
 /// If some horizontal dimension is given, the text will automatically wrap to the width and align
 /// in accordance with the produced **Alignment**.
+///
+/// If the **Text** is made `selectable`, the user may select a range of the text with the mouse
+/// (or select all via `Ctrl+A`). The selection is highlighted, but the text may never be edited.
+/// The selected text may be retrieved via the `selected_text` function.
 #[derive(Clone, Debug, WidgetCommon_)]
 pub struct Text<'a> {
     /// Data necessary and common for all widget builder types.
@@ -46,6 +58,9 @@ pub struct Style {
     /// The id of the font to use for rendering and layout.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// Whether or not the user may select a range of the text with the mouse.
+    #[conrod(default = "false")]
+    pub selectable: Option<bool>,
     // /// The line styling for the text.
     // #[conrod(default = "None")]
     // pub line: Option<Option<Line>>,
@@ -70,6 +85,17 @@ pub enum Wrap {
 //     Through,
 // }
 
+/// A range of text that has been selected by the user within a `selectable` **Text**.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Selection {
+    /// The index at which the selection began. This is always the "anchor" point.
+    pub start: text::cursor::Index,
+    /// The index at which the selection ends.
+    ///
+    /// The `end` may be either greater or less than the `start`.
+    pub end: text::cursor::Index,
+}
+
 /// The state to be stored between updates for the **Text**.
 #[derive(Clone, Debug, PartialEq)]
 pub struct State {
@@ -77,6 +103,14 @@ pub struct State {
     pub string: String,
     /// The indices and width for each line of text within the `string`.
     pub line_infos: Vec<text::line::Info>,
+    /// The range of text selected by the user, if any.
+    ///
+    /// This is only ever `Some` for `selectable` **Text**.
+    pub selection: Option<Selection>,
+    /// Whether or not the user is currently dragging out a selection with the mouse.
+    is_selecting: bool,
+    /// A `Rectangle` for highlighting the selected range across each line.
+    selected_rectangles: widget::id::List,
 }
 
 
@@ -115,6 +149,15 @@ impl<'a> Text<'a> {
         self
     }
 
+    /// Allow the user to select a range of the **Text** with the mouse.
+    ///
+    /// The selected range is highlighted and may be retrieved via the `selected_text` function,
+    /// however the text itself can never be edited.
+    pub fn selectable(mut self) -> Self {
+        self.style.selectable = Some(true);
+        self
+    }
+
     /// Build the **Text** with the given **Style**.
     pub fn with_style(mut self, style: Style) -> Self {
         self.style = style;
@@ -154,6 +197,9 @@ impl<'a> Widget for Text<'a> {
         State {
             string: String::new(),
             line_infos: Vec::new(),
+            selection: None,
+            is_selecting: false,
+            selected_rectangles: widget::id::List::new(),
         }
     }
 
@@ -221,9 +267,18 @@ impl<'a> Widget for Text<'a> {
         Dimension::Absolute(height)
     }
 
+    /// The text's content is the area occupied by its lines, as given by the default dimensions.
+    fn content_wh(&self, ui: &Ui) -> Option<Dimensions> {
+        match (self.default_x_dimension(ui), self.default_y_dimension(ui)) {
+            (Dimension::Absolute(w), Dimension::Absolute(h)) => Some([w, h]),
+            _ => None,
+        }
+    }
+
     /// Update the state of the Text.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
-        let widget::UpdateArgs { rect, state, style, ui, .. } = args;
+        let widget::UpdateArgs { id, maybe_parent_id, rect, state, style, ui, .. } = args;
+        let depth = self.get_depth();
         let Text { text, .. } = self;
 
         let maybe_wrap = style.maybe_wrap(ui.theme());
@@ -252,6 +307,7 @@ impl<'a> Widget for Text<'a> {
             state.update(|state| {
                 state.string = text.to_owned();
                 state.line_infos = new_line_infos().collect();
+                state.selection = None;
             });
 
         // Otherwise, we'll check to see if we have to update the line breaks.
@@ -269,13 +325,191 @@ impl<'a> Widget for Text<'a> {
             };
 
             if let Some(new_line_infos) = maybe_new_line_infos {
-                state.update(|state| state.line_infos = new_line_infos);
+                state.update(|state| {
+                    state.line_infos = new_line_infos;
+                    state.selection = None;
+                });
+            }
+        }
+
+        if !style.selectable(ui.theme()) {
+            return;
+        }
+
+        let font_id = match style.font_id(&ui.theme).or(ui.fonts.ids().next()) {
+            Some(font_id) => font_id,
+            None => return,
+        };
+        let justify = style.justify(ui.theme());
+        let line_spacing = style.line_spacing(ui.theme());
+        // The `Text` primitive is always rendered aligned to the top of its `Rect`.
+        let y_align = Align::End;
+
+        // Find the closest cursor index to the given `xy` position.
+        //
+        // Returns `None` if the text is empty.
+        let closest_cursor_index = |xy, string: &str, line_infos: &[text::line::Info]| {
+            let font = ui.fonts.get(font_id).unwrap();
+            let xys_per_line = text::cursor::xys_per_line_from_text(string, line_infos, font,
+                                                                    font_size, justify, y_align,
+                                                                    line_spacing, rect);
+            text::cursor::closest_cursor_index_and_xy(xy, xys_per_line).map(|(idx, _)| idx)
+        };
+
+        let mut selection = state.selection;
+        let mut is_selecting = state.is_selecting;
+
+        // Check for the following events:
+        // - Left mouse `Press` for beginning a new selection.
+        // - Left mouse `Drag` for extending the end of the selection.
+        // - `Ctrl+A` for selecting all text.
+        // - Losing keyboard capture for clearing the selection.
+        for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+
+                event::Widget::Press(press) => match press.button {
+                    event::Button::Mouse(input::MouseButton::Left, rel_xy) => {
+                        let abs_xy = utils::vec2_add(rel_xy, rect.xy());
+                        selection = closest_cursor_index(abs_xy, &state.string, &state.line_infos)
+                            .map(|idx| Selection { start: idx, end: idx });
+                        is_selecting = true;
+                    },
+                    event::Button::Keyboard(input::Key::A) => {
+                        if press.modifiers.contains(input::keyboard::ModifierKey::CTRL) {
+                            let start = text::cursor::Index { line: 0, char: 0 };
+                            let end = {
+                                let line_infos = state.line_infos.iter().cloned();
+                                let num_chars = state.string.chars().count();
+                                text::cursor::index_before_char(line_infos, num_chars)
+                                    .unwrap_or(start)
+                            };
+                            selection = Some(Selection { start: start, end: end });
+                        }
+                    },
+                    _ => (),
+                },
+
+                event::Widget::Release(release) => {
+                    if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        is_selecting = false;
+                    }
+                },
+
+                event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
+                    if is_selecting {
+                        let abs_xy = utils::vec2_add(drag.to, rect.xy());
+                        let closest = closest_cursor_index(abs_xy, &state.string, &state.line_infos);
+                        if let (Some(prev), Some(end)) = (selection, closest) {
+                            selection = Some(Selection { start: prev.start, end: end });
+                        }
+                    }
+                },
+
+                event::Widget::UncapturesInputSource(input::Source::Keyboard) => {
+                    selection = None;
+                    is_selecting = false;
+                },
+
+                _ => (),
             }
         }
+
+        if let Some(_) = ui.widget_input(id).mouse() {
+            ui.set_mouse_cursor(cursor::MouseCursor::Text);
+        }
+
+        if state.selection != selection {
+            state.update(|state| state.selection = selection);
+        }
+
+        if state.is_selecting != is_selecting {
+            state.update(|state| state.is_selecting = is_selecting);
+        }
+
+        let (start, end) = match selection {
+            Some(Selection { start, end }) if start != end =>
+                (std::cmp::min(start, end), std::cmp::max(start, end)),
+            _ => return,
+        };
+
+        let selected_rects: Vec<Rect> = {
+            let line_infos = state.line_infos.iter().cloned();
+            let lines = line_infos.clone().map(|info| &state.string[info.byte_range()]);
+            let line_rects = text::line::rects(line_infos, font_size, rect,
+                                               justify, y_align, line_spacing);
+            let lines_with_rects = lines.zip(line_rects);
+            let font = ui.fonts.get(font_id).unwrap();
+            text::line::selected_rects(lines_with_rects, font, font_size, start, end).collect()
+        };
+
+        // Ensure we have at least as many widgets as selected rectangles.
+        if state.selected_rectangles.len() < selected_rects.len() {
+            let num_rects = selected_rects.len();
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| state.selected_rectangles.resize(num_rects, id_gen));
+        }
+
+        // Draw a semi-transparent `Rectangle` for the selected range across each line.
+        //
+        // The children of the **Text** would be drawn over its glyphs, so the rectangles are
+        // instead set as its siblings just behind it.
+        let selected_rect_color = style.color(ui.theme()).highlighted().alpha(0.25);
+        let iter = state.selected_rectangles.iter().zip(&selected_rects);
+        for (&selected_rectangle_id, selected_rect) in iter {
+            widget::Rectangle::fill(selected_rect.dim())
+                .xy(selected_rect.xy())
+                .color(selected_rect_color)
+                .graphics_for(id)
+                .and(|rectangle| match maybe_parent_id {
+                    Some(parent_id) => rectangle.parent(parent_id),
+                    None => rectangle.no_parent(),
+                })
+                .depth(depth + SELECTION_DEPTH_OFFSET)
+                .set(selected_rectangle_id, ui);
+        }
     }
 
 }
 
+impl State {
+
+    /// The currently selected range of the text, if any.
+    ///
+    /// Returns `None` if there is no selection or if the selection is empty.
+    pub fn selected_text(&self) -> Option<&str> {
+        let (start, end) = match self.selection {
+            Some(Selection { start, end }) if start != end =>
+                (std::cmp::min(start, end), std::cmp::max(start, end)),
+            _ => return None,
+        };
+
+        // Convert the given cursor index to a byte index into the `string`.
+        let byte_index = |idx: text::cursor::Index| {
+            self.line_infos.get(idx.line).map(|info| {
+                let line = &self.string[info.byte_range()];
+                let byte = line.char_indices().nth(idx.char).map(|(i, _)| i).unwrap_or(line.len());
+                info.start_byte + byte
+            })
+        };
+
+        match (byte_index(start), byte_index(end)) {
+            (Some(start), Some(end)) => Some(&self.string[start..end]),
+            _ => None,
+        }
+    }
+
+}
+
+/// The text currently selected within the `selectable` **Text** widget with the given `id`.
+///
+/// Returns `None` if there is no **Text** for the given `id` or if no text is selected.
+pub fn selected_text(id: widget::Id, ui: &Ui) -> Option<&str> {
+    ui.widget_graph()
+        .widget(id)
+        .and_then(|container| container.state_and_style::<State, Style>())
+        .and_then(|&graph::UniqueWidgetState { ref state, .. }| state.selected_text())
+}
+
 impl<'a> Colorable for Text<'a> {
     fn color(mut self, color: Color) -> Self {
         self.style.color = Some(color);
@@ -3,6 +3,7 @@
 use Ui;
 use position::{Align, Point, Padding, Range, Rect, Scalar};
 use std::marker::PhantomData;
+use utils;
 
 
 /// Arguments given via a scrollable `Widget`'s builder methods for the scrolling along a single
@@ -12,6 +13,22 @@ pub struct Scroll {
     maybe_initial_alignment: Option<Align>,
 }
 
+/// Parameters for the momentum and overscroll bounce of a kinetically scrolling widget.
+///
+/// When a kinetically scrolling widget stops receiving scroll input (e.g. a trackpad flick ends
+/// or the touches of a two-finger pan are lifted), the `Ui` continues scrolling it with the
+/// velocity of the most recent input, decaying over time. If the motion carries the scrollable
+/// range beyond either end, it travels up to `max_overscroll` further before bouncing back.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Kinetic {
+    /// The rate at which the scrolling velocity decays, as a fraction of the velocity per second.
+    pub friction: Scalar,
+    /// The greatest distance by which the scrollable range may be carried beyond either end.
+    ///
+    /// A value of `0.0` disables the bounce, bringing the motion to a stop at either end.
+    pub max_overscroll: Scalar,
+}
+
 /// Scroll state calculated for a single axis.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct State<A> {
@@ -30,6 +47,12 @@ pub struct State<A> {
     pub scrollable_range_len: Scalar,
     /// Whether or not the this axis is currently scrolling.
     pub is_scrolling: bool,
+    /// The distance beyond the `offset_bounds` by which the scrollable range is displayed.
+    ///
+    /// This is only ever non-zero for kinetically scrolling axes, while bouncing off either end.
+    pub overscroll: Scalar,
+    /// The parameters for kinetic scrolling, if the axis scrolls kinetically.
+    pub maybe_kinetic: Option<Kinetic>,
     /// The axis type used to instantiate this state.
     axis: PhantomData<A>,
 }
@@ -57,6 +80,15 @@ pub enum X {}
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Y {}
 
+/// The greatest duration in seconds stepped at once by `State::step_kinetic`.
+const KINETIC_TIME_STEP: Scalar = 1.0 / 120.0;
+/// The stiffness of the spring that pulls an overscrolled range back within its bounds.
+const BOUNCE_STIFFNESS: Scalar = 150.0;
+/// The speed below which kinetic motion comes to rest.
+const REST_VELOCITY: Scalar = 10.0;
+/// The overscroll below which a bouncing range snaps back within its bounds.
+const REST_OVERSCROLL: Scalar = 0.5;
+
 /// State for scrolling along the **X** axis.
 pub type StateX = State<X>;
 
@@ -73,6 +105,16 @@ impl Scroll {
     }
 }
 
+impl Kinetic {
+    /// The default `Kinetic` scrolling parameters.
+    pub fn new() -> Self {
+        Kinetic {
+            friction: 4.0,
+            max_overscroll: 60.0,
+        }
+    }
+}
+
 
 impl<A> State<A>
     where A: Axis
@@ -161,10 +203,14 @@ impl<A> State<A>
                   additional_offset: Scalar) -> Self
     {
 
-        // Retrieve the *current* scroll offset.
+        // Retrieve the *current* scroll offset along with any overscroll.
         let current_offset = maybe_prev_scroll_state.as_ref()
             .map(|state| state.offset)
             .unwrap_or(0.0);
+        let current_overscroll = maybe_prev_scroll_state.as_ref()
+            .map(|state| state.overscroll)
+            .unwrap_or(0.0);
+        let maybe_kinetic = maybe_prev_scroll_state.as_ref().and_then(|state| state.maybe_kinetic);
 
         // Padding for the range.
         let padding = A::padding_range(kid_area.pad);
@@ -180,7 +226,7 @@ impl<A> State<A>
             ui.kids_bounding_box(idx)
                 .map(|kids| {
                     A::parallel_range(kids)
-                        .shift(-current_offset)
+                        .shift(-(current_offset + current_overscroll))
                         .shift(-kid_area_range.middle())
                 })
                 .unwrap_or_else(|| Range::new(0.0, 0.0))
@@ -199,7 +245,7 @@ impl<A> State<A>
         //
         // The `additional_offset` is given via a `Scroll` event.
         let new_offset_unbounded =
-            if is_scrollable { current_offset + additional_offset }
+            if is_scrollable { current_offset + current_overscroll + additional_offset }
             else             { current_offset };
 
         // Clamp the new offset to ensure it does not exceed the `offset_bounds`.
@@ -213,15 +259,84 @@ impl<A> State<A>
             offset_bounds.clamp_value(new_offset)
         };
 
+        // Kinetically scrolling axes may carry the remainder beyond the bounds as overscroll.
+        let overscroll = match maybe_kinetic {
+            Some(kinetic) if is_scrollable => {
+                let max = kinetic.max_overscroll;
+                utils::clamp(new_offset_unbounded - new_offset, -max, max)
+            },
+            _ => 0.0,
+        };
+
         State {
             offset: new_offset,
             offset_bounds: offset_bounds,
             scrollable_range_len: scrollable_range.len(),
             axis: PhantomData,
             is_scrolling: additional_offset != 0.0,
+            overscroll: overscroll,
+            maybe_kinetic: maybe_kinetic,
         }
     }
 
+    /// Step the kinetic motion of the axis forward by `dt` seconds, given its current `velocity`
+    /// in offset per second.
+    ///
+    /// Within the `offset_bounds` the velocity decays with the `Kinetic::friction`. While
+    /// overscrolled, the range is pulled back within the bounds by a critically damped spring.
+    ///
+    /// Returns the additional offset by which the axis should be scrolled along with the new
+    /// velocity. The motion has come to rest once both are `0.0`, which is always the case if the
+    /// axis does not scroll kinetically.
+    pub fn step_kinetic(&self, velocity: Scalar, dt: Scalar) -> (Scalar, Scalar) {
+        let kinetic = match self.maybe_kinetic {
+            Some(kinetic) => kinetic,
+            None => return (0.0, 0.0),
+        };
+
+        let min = utils::partial_min(self.offset_bounds.start, self.offset_bounds.end);
+        let max = utils::partial_max(self.offset_bounds.start, self.offset_bounds.end);
+        let overscroll_at = |position: Scalar| {
+            if position < min { position - min }
+            else if position > max { position - max }
+            else { 0.0 }
+        };
+
+        let start = self.offset + self.overscroll;
+        let mut position = start;
+        let mut velocity = velocity;
+        let mut remaining = dt;
+        while remaining > 0.0 {
+            let step = utils::partial_min(remaining, KINETIC_TIME_STEP);
+            remaining -= step;
+
+            let overscroll = overscroll_at(position);
+            if overscroll == 0.0 {
+                velocity *= (-kinetic.friction * step).exp();
+            } else {
+                let damping = 2.0 * BOUNCE_STIFFNESS.sqrt();
+                velocity += (-BOUNCE_STIFFNESS * overscroll - damping * velocity) * step;
+            }
+            position += velocity * step;
+
+            // Stop dead upon reaching the furthest overscroll.
+            let limit = overscroll_at(position);
+            if limit.abs() > kinetic.max_overscroll {
+                position -= limit - limit.signum() * kinetic.max_overscroll;
+                velocity = 0.0;
+            }
+        }
+
+        // Come to rest once slow enough, snapping back within the bounds if necessary.
+        let overscroll = overscroll_at(position);
+        if velocity.abs() < REST_VELOCITY && overscroll.abs() < REST_OVERSCROLL {
+            position -= overscroll;
+            velocity = 0.0;
+        }
+
+        (position - start, velocity)
+    }
+
 }
 
 
@@ -159,12 +159,13 @@ impl<A> Widget for Scrollbar<A>
         let Scrollbar { widget, .. } = self;
 
         // Only continue if the widget that we want to scroll has some scroll state.
-        let (offset_bounds, offset, scrollable_range_len, is_scrolling) =
+        let (offset_bounds, offset, overscroll, scrollable_range_len, is_scrolling) =
             match ui.widget_graph().widget(widget) {
                 Some(widget) => match A::scroll_state(widget) {
                     Some(scroll) =>
                         (scroll.offset_bounds,
                          scroll.offset,
+                         scroll.overscroll,
                          scroll.scrollable_range_len,
                          scroll.is_scrolling),
                     None => return,
@@ -174,6 +175,8 @@ impl<A> Widget for Scrollbar<A>
 
         // Calculates the `Rect` for a scroll "handle" sitting on the given `track` with an offset
         // and length that represents the given `Axis`' `state`.
+        //
+        // While the widget is overscrolled, the handle is squashed against the end of the track.
         let handle_rect = {
             let perpendicular_track_range = A::perpendicular_range(rect);
             let track_range = A::parallel_range(rect);
@@ -188,9 +191,10 @@ impl<A> Widget for Scrollbar<A>
                 let pos_min = handle_range.align_start_of(track_range).middle();
                 let pos_max = handle_range.align_end_of(track_range).middle();
                 let pos_bounds = Range::new(pos_min, pos_max);
-                offset_bounds.map_value_to(offset, &pos_bounds)
+                offset_bounds.map_value_to(offset + overscroll, &pos_bounds)
             };
             let range = Range::from_pos_and_len(pos, len);
+            let range = range.overlap(track_range).unwrap_or(range);
             A::handle_rect(perpendicular_track_range, range)
         };
 
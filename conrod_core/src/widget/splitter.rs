@@ -0,0 +1,512 @@
+//! A layout widget that divides its area between panes separated by draggable handles.
+
+use {Color, Colorable, Positionable, Scalar, UiCell, Widget};
+use cursor;
+use event;
+use input;
+use position::{Axis, Rect};
+use std;
+use utils;
+use widget;
+
+
+/// Divides its area into a sequence of panes along an axis, separated by handles that the user
+/// may drag to resize the panes on either side.
+///
+/// The share of the area given to each pane is initialised from its `weight` and then persisted
+/// within the widget's state as the ratio of the available length, so that the layout survives
+/// resizing of the `Splitter` itself. Dragging a handle never shrinks a pane below its `min`
+/// length, and double-clicking a handle collapses (or restores) a neighbouring pane that is
+/// `collapsible`.
+///
+/// The `Splitter` itself only draws its handles. It produces a `Panes` iterator yielding an
+/// `Element` for each pane that is not collapsed, via which any widget may be set within the
+/// pane's area.
+#[derive(Clone, WidgetCommon_)]
+pub struct Splitter<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    panes: &'a [Pane],
+    style: Style,
+}
+
+/// The properties of a single pane of a `Splitter`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Pane {
+    /// The proportion of the available length initially given to the pane.
+    pub weight: Scalar,
+    /// The length below which the pane may not be resized.
+    pub min: Scalar,
+    /// Whether or not the pane may be collapsed by double-clicking an adjacent handle.
+    pub is_collapsible: bool,
+}
+
+/// Unique styling for the `Splitter`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// The axis along which the panes are laid out.
+    #[conrod(default = "Axis::X")]
+    pub axis: Option<Axis>,
+    /// The thickness of each handle between adjacent panes.
+    #[conrod(default = "6.0")]
+    pub handle_thickness: Option<Scalar>,
+    /// The color of the handles.
+    #[conrod(default = "theme.border_color")]
+    pub color: Option<Color>,
+}
+
+/// The state of the `Splitter`.
+pub struct State {
+    /// A `widget::Id` for each pane.
+    ids: widget::id::List,
+    /// A `widget::Id` for each handle.
+    handle_ids: widget::id::List,
+    /// The share of the available length belonging to each pane.
+    ratios: Vec<Scalar>,
+    /// Whether or not each pane is currently collapsed.
+    collapsed: Vec<bool>,
+    /// The index of the handle currently being dragged, if any.
+    maybe_dragged_handle: Option<usize>,
+    /// The `ratios` at the moment the dragged handle was pressed, restored if the drag is
+    /// cancelled.
+    drag_start_ratios: Vec<Scalar>,
+}
+
+/// The event produced by the `Splitter`, yielding an `Element` for each pane that is not
+/// collapsed.
+#[derive(Clone, Debug)]
+pub struct Panes {
+    splitter_id: widget::Id,
+    ratios: Vec<Scalar>,
+    collapsed: Vec<bool>,
+    elements: std::vec::IntoIter<Element>,
+}
+
+/// Data necessary for instantiating a widget within a single pane of a `Splitter`.
+#[derive(Copy, Clone, Debug)]
+pub struct Element {
+    /// The id generated for the widget.
+    pub widget_id: widget::Id,
+    /// The index of the `Pane` within the slice given to the `Splitter`.
+    pub index: usize,
+    /// The width of the element.
+    pub w: Scalar,
+    /// The height of the element.
+    pub h: Scalar,
+    /// The *x* position of the element relative to the centre of the `Splitter`.
+    pub rel_x: Scalar,
+    /// The *y* position of the element relative to the centre of the `Splitter`.
+    pub rel_y: Scalar,
+    /// The id of the `Splitter`, used for positioning.
+    splitter_id: widget::Id,
+}
+
+
+impl Pane {
+
+    /// A pane with a weight of `1.0` that may be resized to nothing and never collapses.
+    pub fn new() -> Self {
+        Pane {
+            weight: 1.0,
+            min: 0.0,
+            is_collapsible: false,
+        }
+    }
+
+    /// Specify the proportion of the available length initially given to the pane.
+    pub fn weight(mut self, weight: Scalar) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Specify the length below which the pane may not be resized.
+    pub fn min(mut self, min: Scalar) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Specify whether or not the pane may be collapsed by double-clicking an adjacent handle.
+    pub fn collapsible(mut self, is_collapsible: bool) -> Self {
+        self.is_collapsible = is_collapsible;
+        self
+    }
+
+}
+
+
+impl<'a> Splitter<'a> {
+
+    /// Begin building a `Splitter` dividing its area between the given panes.
+    pub fn new(panes: &'a [Pane]) -> Self {
+        Splitter {
+            common: widget::CommonBuilder::default(),
+            panes: panes,
+            style: Style::default(),
+        }
+    }
+
+    /// Lay the panes out from left to right.
+    pub fn row(self) -> Self {
+        self.axis(Axis::X)
+    }
+
+    /// Lay the panes out from top to bottom.
+    pub fn column(self) -> Self {
+        self.axis(Axis::Y)
+    }
+
+    builder_methods!{
+        pub axis { style.axis = Some(Axis) }
+        pub handle_thickness { style.handle_thickness = Some(Scalar) }
+    }
+
+}
+
+
+impl<'a> Widget for Splitter<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = Panes;
+
+    fn init_state(&self, _: widget::id::Generator) -> Self::State {
+        State {
+            ids: widget::id::List::new(),
+            handle_ids: widget::id::List::new(),
+            ratios: Vec::new(),
+            collapsed: Vec::new(),
+            maybe_dragged_handle: None,
+            drag_start_ratios: Vec::new(),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    /// Update the state of the Splitter.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
+        let Splitter { panes, .. } = self;
+        let num_handles = panes.len().saturating_sub(1);
+
+        // Ensure there is an id for every pane and handle.
+        if state.ids.len() < panes.len() || state.handle_ids.len() < num_handles {
+            let id_gen = &mut ui.widget_id_generator();
+            state.update(|state| {
+                state.ids.resize(panes.len(), id_gen);
+                state.handle_ids.resize(num_handles, id_gen);
+            });
+        }
+
+        // Begin with the weights of the panes the first time they are given.
+        if state.ratios.len() != panes.len() {
+            let total_weight = panes.iter().fold(0.0, |total, pane| total + pane.weight);
+            state.update(|state| {
+                state.ratios = panes.iter().map(|pane| pane.weight / total_weight).collect();
+                state.collapsed = vec![false; panes.len()];
+                state.maybe_dragged_handle = None;
+            });
+        }
+
+        let axis = style.axis(&ui.theme);
+        let thickness = style.handle_thickness(&ui.theme);
+        let len = match axis {
+            Axis::X => rect.w(),
+            Axis::Y => rect.h(),
+        };
+        let available = utils::partial_max(0.0, len - thickness * num_handles as Scalar);
+
+        // The distance of the given point (relative to the centre) from the start of the axis.
+        let distance_along_axis = |xy: [Scalar; 2]| match axis {
+            Axis::X => xy[0] + rect.w() / 2.0,
+            Axis::Y => rect.h() / 2.0 - xy[1],
+        };
+
+        // The handle whose area contains the given distance along the axis.
+        let handle_at = |lengths: &[Scalar], distance: Scalar| -> Option<usize> {
+            (0..num_handles).find(|&i| {
+                let start = handle_start(lengths, i, thickness);
+                distance >= start && distance <= start + thickness
+            })
+        };
+
+        let mut ratios = state.ratios.clone();
+        let mut collapsed = state.collapsed.clone();
+        let mut maybe_dragged_handle = state.maybe_dragged_handle;
+        let mut drag_start_ratios = None;
+        let mut lengths = pane_lengths(panes, &ratios, &collapsed, available);
+        for widget_event in ui.widget_input(id).events() {
+            match widget_event {
+
+                // Begin dragging the handle under the mouse.
+                event::Widget::Press(press) => {
+                    if let event::Button::Mouse(input::MouseButton::Left, xy) = press.button {
+                        maybe_dragged_handle = handle_at(&lengths, distance_along_axis(xy));
+                        if maybe_dragged_handle.is_some() {
+                            drag_start_ratios = Some(ratios.clone());
+                        }
+                    }
+                },
+
+                event::Widget::Release(release) => {
+                    if let event::Button::Mouse(input::MouseButton::Left, _) = release.button {
+                        maybe_dragged_handle = None;
+                    }
+                },
+
+                // Move the dragged handle towards the mouse, without shrinking the panes on
+                // either side below their minimum lengths.
+                event::Widget::Drag(drag) if drag.button == input::MouseButton::Left => {
+                    let i = match maybe_dragged_handle {
+                        Some(i) => i,
+                        None => continue,
+                    };
+                    let j = i + 1;
+                    if collapsed[i] || collapsed[j] || available <= 0.0 {
+                        continue;
+                    }
+                    let handle_middle = handle_start(&lengths, i, thickness) + thickness / 2.0;
+                    let delta = distance_along_axis(drag.to) - handle_middle;
+                    let min_delta = panes[i].min - lengths[i];
+                    let max_delta = lengths[j] - panes[j].min;
+                    if min_delta > max_delta {
+                        continue;
+                    }
+                    let delta = utils::clamp(delta, min_delta, max_delta);
+                    lengths[i] += delta;
+                    lengths[j] -= delta;
+
+                    // Persist the new lengths as ratios of the space shared by the open panes.
+                    let open_ratio = open_ratio(&ratios, &collapsed);
+                    ratios[i] = lengths[i] / available * open_ratio;
+                    ratios[j] = lengths[j] / available * open_ratio;
+                },
+
+                // A left `DragCancel` returns the panes to their lengths before the drag.
+                event::Widget::DragCancel(cancel) if cancel.button == input::MouseButton::Left => {
+                    if maybe_dragged_handle.take().is_some() {
+                        let start_ratios = drag_start_ratios.take()
+                            .unwrap_or_else(|| state.drag_start_ratios.clone());
+                        if start_ratios.len() == ratios.len() {
+                            ratios = start_ratios;
+                            lengths = pane_lengths(panes, &ratios, &collapsed, available);
+                        }
+                    }
+                },
+
+                // Collapse or restore the collapsible pane adjacent to the handle.
+                event::Widget::DoubleClick(click) if click.button == input::MouseButton::Left => {
+                    let i = match handle_at(&lengths, distance_along_axis(click.xy)) {
+                        Some(i) => i,
+                        None => continue,
+                    };
+                    let target = if panes[i].is_collapsible {
+                        i
+                    } else if panes[i + 1].is_collapsible {
+                        i + 1
+                    } else {
+                        continue;
+                    };
+                    let num_open = collapsed.iter().filter(|&&is_collapsed| !is_collapsed).count();
+                    if !collapsed[target] && num_open <= 1 {
+                        continue;
+                    }
+                    collapsed[target] = !collapsed[target];
+                    maybe_dragged_handle = None;
+                    lengths = pane_lengths(panes, &ratios, &collapsed, available);
+                },
+
+                _ => (),
+            }
+        }
+
+        if state.ratios != ratios {
+            state.update(|state| state.ratios = ratios.clone());
+        }
+        if state.collapsed != collapsed {
+            state.update(|state| state.collapsed = collapsed.clone());
+        }
+        if state.maybe_dragged_handle != maybe_dragged_handle {
+            state.update(|state| state.maybe_dragged_handle = maybe_dragged_handle);
+        }
+        if let Some(drag_start_ratios) = drag_start_ratios {
+            state.update(|state| state.drag_start_ratios = drag_start_ratios);
+        }
+
+        // The `Rect` spanning the given range along the axis, relative to the centre.
+        let rect_along_axis = |start: Scalar, len: Scalar| {
+            let (w, h) = match axis {
+                Axis::X => (len, rect.h()),
+                Axis::Y => (rect.w(), len),
+            };
+            let (x, y) = match axis {
+                Axis::X => (start + len / 2.0 - rect.w() / 2.0, 0.0),
+                Axis::Y => (0.0, rect.h() / 2.0 - start - len / 2.0),
+            };
+            Rect::from_xy_dim([x, y], [w, h])
+        };
+
+        // Show the resize cursor while hovering over or dragging a handle.
+        let maybe_mouse = ui.widget_input(id).mouse();
+        let maybe_hovered_handle = maybe_mouse
+            .filter(|mouse| mouse.is_over())
+            .and_then(|mouse| handle_at(&lengths, distance_along_axis(mouse.rel_xy())));
+        if maybe_dragged_handle.is_some() || maybe_hovered_handle.is_some() {
+            let resize_cursor = match axis {
+                Axis::X => cursor::MouseCursor::ResizeHorizontal,
+                Axis::Y => cursor::MouseCursor::ResizeVertical,
+            };
+            ui.set_mouse_cursor(resize_cursor);
+        }
+
+        // Draw each handle, highlighting it while it is hovered or dragged.
+        let color = style.color(&ui.theme);
+        for (i, &handle_id) in state.handle_ids.iter().enumerate().take(num_handles) {
+            let color = if maybe_dragged_handle == Some(i) {
+                color.clicked()
+            } else if maybe_hovered_handle == Some(i) {
+                color.highlighted()
+            } else {
+                color
+            };
+            let handle_rect = rect_along_axis(handle_start(&lengths, i, thickness), thickness);
+            widget::Rectangle::fill(handle_rect.dim())
+                .xy_relative_to(id, handle_rect.xy())
+                .color(color)
+                .graphics_for(id)
+                .parent(id)
+                .set(handle_id, ui);
+        }
+
+        let mut start = 0.0;
+        let mut elements = Vec::with_capacity(panes.len());
+        for (i, (&widget_id, &len)) in state.ids.iter().zip(lengths.iter()).enumerate() {
+            if !collapsed[i] {
+                let pane_rect = rect_along_axis(start, len);
+                elements.push(Element {
+                    widget_id: widget_id,
+                    index: i,
+                    w: pane_rect.w(),
+                    h: pane_rect.h(),
+                    rel_x: pane_rect.x(),
+                    rel_y: pane_rect.y(),
+                    splitter_id: id,
+                });
+            }
+            start += len + thickness;
+        }
+
+        Panes {
+            splitter_id: id,
+            ratios: ratios,
+            collapsed: collapsed,
+            elements: elements.into_iter(),
+        }
+    }
+
+}
+
+
+/// The sum of the ratios of all panes that are not collapsed.
+fn open_ratio(ratios: &[Scalar], collapsed: &[bool]) -> Scalar {
+    ratios.iter().zip(collapsed)
+        .filter(|&(_, &is_collapsed)| !is_collapsed)
+        .fold(0.0, |total, (&ratio, _)| total + ratio)
+}
+
+/// The distance from the start of the axis to the start of the handle at the given index.
+fn handle_start(lengths: &[Scalar], index: usize, thickness: Scalar) -> Scalar {
+    lengths[..index + 1].iter().fold(0.0, |total, &len| total + len) + thickness * index as Scalar
+}
+
+/// Share the available length between the open panes in proportion to their ratios.
+///
+/// Panes that would be shorter than their `min` are fixed at their `min`, while the remaining
+/// length is shared between the rest. Collapsed panes have no length.
+fn pane_lengths(panes: &[Pane],
+                ratios: &[Scalar],
+                collapsed: &[bool],
+                available: Scalar) -> Vec<Scalar>
+{
+    let mut lengths = vec![0.0; panes.len()];
+    let mut is_fixed: Vec<bool> = collapsed.to_vec();
+    loop {
+        let fixed_len = (0..panes.len())
+            .filter(|&i| is_fixed[i] && !collapsed[i])
+            .fold(0.0, |total, i| total + lengths[i]);
+        let remaining = utils::partial_max(0.0, available - fixed_len);
+        let free_ratio = (0..panes.len())
+            .filter(|&i| !is_fixed[i])
+            .fold(0.0, |total, i| total + ratios[i]);
+
+        let free: Vec<usize> = (0..panes.len()).filter(|&i| !is_fixed[i]).collect();
+        let mut is_clamped = false;
+        for i in free {
+            lengths[i] = if free_ratio > 0.0 { remaining * ratios[i] / free_ratio } else { 0.0 };
+            if lengths[i] < panes[i].min {
+                lengths[i] = panes[i].min;
+                is_fixed[i] = true;
+                is_clamped = true;
+            }
+        }
+        if !is_clamped {
+            return lengths;
+        }
+    }
+}
+
+
+impl Iterator for Panes {
+    type Item = Element;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.elements.next()
+    }
+}
+
+impl Panes {
+
+    /// The id of the `Splitter` that produced the `Panes`.
+    pub fn splitter_id(&self) -> widget::Id {
+        self.splitter_id
+    }
+
+    /// The share of the available length belonging to each pane, including those collapsed.
+    pub fn ratios(&self) -> &[Scalar] {
+        &self.ratios
+    }
+
+    /// Whether or not the pane at the given index is currently collapsed.
+    pub fn is_collapsed(&self, index: usize) -> bool {
+        self.collapsed.get(index).cloned().unwrap_or(false)
+    }
+
+}
+
+
+impl Element {
+
+    /// Sets the given widget as the widget to use for the pane.
+    ///
+    /// Sets the:
+    /// - position of the widget.
+    /// - dimensions of the widget.
+    /// - parent of the widget.
+    /// - and finally sets the widget within the `Ui`.
+    pub fn set<W>(self, widget: W, ui: &mut UiCell) -> W::Event
+        where W: Widget,
+    {
+        use Sizeable;
+        let Element { widget_id, splitter_id, w, h, rel_x, rel_y, .. } = self;
+        widget
+            .w_h(w, h)
+            .x_y_relative_to(splitter_id, rel_x, rel_y)
+            .parent(splitter_id)
+            .set(widget_id, ui)
+    }
+
+}
+
+
+impl<'a> Colorable for Splitter<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
@@ -1,6 +1,6 @@
 //! A widget for displaying and mutating a one-line field of text.
 
-use {Color, Colorable, FontSize, Borderable, Positionable, Sizeable, Widget};
+use {Color, Colorable, Dimensions, FontSize, Borderable, Positionable, Sizeable, Ui, Widget};
 use event;
 use input;
 use position::{Range, Rect, Scalar};
@@ -128,10 +128,25 @@ impl<'a> Widget for TextBox<'a> {
         self.style.clone()
     }
 
+    /// The content of the box is its text along with the border and padding surrounding it.
+    fn content_wh(&self, ui: &Ui) -> Option<Dimensions> {
+        let font_size = self.style.font_size(&ui.theme);
+        let border = self.style.border(&ui.theme);
+        let text_padding = self.style.text_padding(&ui.theme);
+        let text_w = self.style.font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id))
+            .map(|font| text::line::width(self.text, font, font_size))
+            .unwrap_or(0.0);
+        let w = text_w + (border + text_padding) * 2.0;
+        let h = font_size as Scalar + 1.0 + border * 2.0;
+        Some([w, h])
+    }
+
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
-        let TextBox { text, .. } = self;
+        let TextBox { text, common, .. } = self;
 
         let font_size = style.font_size(ui.theme());
         let border = style.border(ui.theme());
@@ -169,6 +184,7 @@ impl<'a> Widget for TextBox<'a> {
             .color(text_color)
             .justify(justify)
             .parent(id)
+            .and_then(common.maybe_tab_index, |w, index| w.tab_index(index))
             .set(state.ids.text_edit, ui)
         {
             events.push(Event::Update(new_string));
@@ -1,7 +1,9 @@
 //! A widget for displaying and mutating multi-line text, given as a `String`.
 
-use {Color, Colorable, FontSize, Positionable, Sizeable, Widget, Ui};
+use {Color, Colorable, FontSize, Positionable, Sizeable, Widget, Ui, UiCell};
+use color;
 use event;
+use graph;
 use input;
 use position::{Align, Dimension, Point, Range, Rect, Scalar};
 use std;
@@ -16,12 +18,18 @@ use widget::primitive::text::Wrap;
 ///
 /// By default the text is wrapped via the first whitespace before the line exceeds the
 /// `TextEdit`'s width, however a user may change this using the `.wrap_by_character` method.
+///
+/// All occurrences of some query may be highlighted using the `.search` method. The
+/// `.search_next`, `.search_previous`, `.replace` and `.replace_all` methods may then be used to
+/// navigate between and replace the matches.
 #[derive(WidgetCommon_)]
 pub struct TextEdit<'a> {
     #[conrod(common_builder)]
     common: widget::CommonBuilder,
     text: &'a str,
     style: Style,
+    maybe_search: Option<&'a str>,
+    maybe_search_action: Option<SearchAction<'a>>,
 }
 
 /// Unique graphical styling for the TextEdit.
@@ -51,10 +59,14 @@ pub struct Style {
     /// The font used for the `Text`.
     #[conrod(default = "theme.font_id")]
     pub font_id: Option<Option<text::font::Id>>,
+    /// The color used to highlight matches of the `search` query.
+    #[conrod(default = "color::YELLOW")]
+    pub search_color: Option<Color>,
 }
 
 widget_ids! {
     struct Ids {
+        search_rectangles[],
         selected_rectangles[],
         text,
         cursor,
@@ -98,6 +110,19 @@ pub enum Cursor {
     },
 }
 
+/// An action to apply to the matches of the `search` query.
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum SearchAction<'a> {
+    /// Select the next match after the cursor.
+    Next,
+    /// Select the previous match before the cursor.
+    Previous,
+    /// Replace the selected match with the given string and select the next match.
+    Replace(&'a str),
+    /// Replace all matches with the given string.
+    ReplaceAll(&'a str),
+}
+
 
 impl<'a> TextEdit<'a> {
 
@@ -107,6 +132,8 @@ impl<'a> TextEdit<'a> {
             common: widget::CommonBuilder::default(),
             style: Style::default(),
             text: text,
+            maybe_search: None,
+            maybe_search_action: None,
         }
     }
 
@@ -167,6 +194,49 @@ impl<'a> TextEdit<'a> {
         self
     }
 
+    /// Highlight all occurrences of the given `query` within the text.
+    ///
+    /// Matches are case-sensitive and never overlap. An empty `query` matches nothing.
+    pub fn search(mut self, query: &'a str) -> Self {
+        self.maybe_search = Some(query);
+        self
+    }
+
+    /// Select the first match of the `search` query following the cursor, wrapping around to the
+    /// beginning of the text if necessary.
+    ///
+    /// If one of the `TextEdit`'s parents is scrollable, it will be scrolled so that the match is
+    /// visible.
+    pub fn search_next(mut self) -> Self {
+        self.maybe_search_action = Some(SearchAction::Next);
+        self
+    }
+
+    /// Select the last match of the `search` query preceding the cursor, wrapping around to the
+    /// end of the text if necessary.
+    ///
+    /// If one of the `TextEdit`'s parents is scrollable, it will be scrolled so that the match is
+    /// visible.
+    pub fn search_previous(mut self) -> Self {
+        self.maybe_search_action = Some(SearchAction::Previous);
+        self
+    }
+
+    /// Replace the selected match of the `search` query with the given string and select the next
+    /// match.
+    ///
+    /// If the current selection is not a match, this behaves the same as `search_next`.
+    pub fn replace(mut self, with: &'a str) -> Self {
+        self.maybe_search_action = Some(SearchAction::Replace(with));
+        self
+    }
+
+    /// Replace every match of the `search` query with the given string.
+    pub fn replace_all(mut self, with: &'a str) -> Self {
+        self.maybe_search_action = Some(SearchAction::ReplaceAll(with));
+        self
+    }
+
     builder_methods!{
         pub font_size { style.font_size = Some(FontSize) }
         pub justify { style.justify = Some(text::Justify) }
@@ -174,6 +244,7 @@ impl<'a> TextEdit<'a> {
         pub line_wrap { style.line_wrap = Some(Wrap) }
         pub line_spacing { style.line_spacing = Some(Scalar) }
         pub restrict_to_height { style.restrict_to_height = Some(bool) }
+        pub search_color { style.search_color = Some(Color) }
     }
 
 }
@@ -200,6 +271,14 @@ impl<'a> Widget for TextEdit<'a> {
         self.style.clone()
     }
 
+    fn is_focusable(&self) -> bool {
+        true
+    }
+
+    fn is_text_input(&self) -> bool {
+        true
+    }
+
     fn default_y_dimension(&self, ui: &Ui) -> Dimension {
         // If the user has specified `restrict_to_height = true`, then we should infer the height
         // using the previous widget as is the default case.
@@ -240,7 +319,7 @@ impl<'a> Widget for TextEdit<'a> {
     /// Update the state of the TextEdit.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, rect, style, ui, .. } = args;
-        let TextEdit { text, .. } = self;
+        let TextEdit { text, maybe_search, maybe_search_action, .. } = self;
         let mut text = std::borrow::Cow::Borrowed(text);
 
         // Retrieve the `font_id`, as long as a valid `Font` for it still exists.
@@ -741,6 +820,102 @@ impl<'a> Widget for TextEdit<'a> {
             }
         }
 
+        // Apply any requested search action, tracking the match that should be scrolled into view.
+        let mut maybe_match_to_reveal = None;
+        if let (Some(query), Some(action)) = (maybe_search, maybe_search_action) {
+
+            // The char index range covered by the given cursor.
+            let cursor_char_range = |cursor: Cursor, infos: &[text::line::Info]| {
+                let (start, end) = match cursor {
+                    Cursor::Idx(idx) => (idx, idx),
+                    Cursor::Selection { start, end } =>
+                        (std::cmp::min(start, end), std::cmp::max(start, end)),
+                };
+                let line_infos = infos.iter().cloned();
+                (text::glyph::index_after_cursor(line_infos.clone(), start).unwrap_or(0),
+                 text::glyph::index_after_cursor(line_infos, end).unwrap_or(0))
+            };
+
+            // Select the match after (or before) the cursor, wrapping around the text.
+            let select_match = |forward: bool,
+                                cursor: Cursor,
+                                text: &str,
+                                infos: &[text::line::Info]|
+                -> Option<(Cursor, std::ops::Range<usize>)>
+            {
+                let matches = search_matches(text, query);
+                let (start, end) = cursor_char_range(cursor, infos);
+                let on_match = matches.iter().any(|m| m.start == start && m.end == end);
+                let found = if forward {
+                    matches.iter()
+                        .find(|m| if on_match { m.start > start } else { m.start >= end })
+                        .or(matches.first())
+                } else {
+                    matches.iter().rev()
+                        .find(|m| m.start < start)
+                        .or(matches.last())
+                };
+                found.and_then(|m| {
+                    let line_infos = infos.iter().cloned();
+                    let start = text::cursor::index_before_char(line_infos.clone(), m.start);
+                    let end = text::cursor::index_before_char(line_infos, m.end);
+                    match (start, end) {
+                        (Some(start), Some(end)) =>
+                            Some((Cursor::Selection { start: start, end: end }, m.clone())),
+                        _ => None,
+                    }
+                })
+            };
+
+            match action {
+
+                SearchAction::Next | SearchAction::Previous => {
+                    let forward = action == SearchAction::Next;
+                    if let Some((new_cursor, m)) = select_match(forward, cursor, &text, &state.line_infos) {
+                        cursor = new_cursor;
+                        maybe_match_to_reveal = Some(m);
+                    }
+                },
+
+                SearchAction::Replace(with) => {
+                    let (start, end) = cursor_char_range(cursor, &state.line_infos);
+                    let on_match = search_matches(&text, query).iter()
+                        .any(|m| m.start == start && m.end == end);
+                    if on_match {
+                        let font = ui.fonts.get(font_id).unwrap();
+                        if let Some((new_text, new_cursor, new_line_infos)) =
+                            insert_text(with, cursor, &text, &state.line_infos, font)
+                        {
+                            *text.to_mut() = new_text;
+                            cursor = new_cursor;
+                            state.update(|state| state.line_infos = new_line_infos);
+                        }
+                    }
+                    if let Some((new_cursor, m)) = select_match(true, cursor, &text, &state.line_infos) {
+                        cursor = new_cursor;
+                        maybe_match_to_reveal = Some(m);
+                    }
+                },
+
+                SearchAction::ReplaceAll(with) => {
+                    if !query.is_empty() && text.contains(query) {
+                        let new_text = text.replace(query, with);
+                        let new_line_infos: Vec<_> = {
+                            let font = ui.fonts.get(font_id).unwrap();
+                            line_infos(&new_text, font, font_size, line_wrap, rect.w()).collect()
+                        };
+                        let height = text::height(new_line_infos.len(), font_size, line_spacing);
+                        if height < rect.h() || !restrict_to_height {
+                            let cursor_idx = text::cursor::Index { line: 0, char: 0 };
+                            *text.to_mut() = new_text;
+                            cursor = Cursor::Idx(cursor_idx);
+                            state.update(|state| state.line_infos = new_line_infos);
+                        }
+                    }
+                },
+            }
+        }
+
         if let Some(_) = ui.widget_input(id).mouse() {
             ui.set_mouse_cursor(cursor::MouseCursor::Text);
         }
@@ -784,6 +959,79 @@ impl<'a> Widget for TextEdit<'a> {
             .font_size(font_size)
             .set(state.ids.text, ui);
 
+        // Highlight all matches of the search query.
+        if let Some(query) = maybe_search {
+            let (start, end) = match cursor {
+                Cursor::Idx(idx) => (idx, idx),
+                Cursor::Selection { start, end } =>
+                    (std::cmp::min(start, end), std::cmp::max(start, end)),
+            };
+
+            // Produce the rects for each match along with whether or not it is selected.
+            let search_rects: Vec<(Rect, bool)> = {
+                let font = ui.fonts.get(font_id).unwrap();
+                let line_infos = state.line_infos.iter().cloned();
+                let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+                let line_rects = text::line::rects(line_infos.clone(), font_size, rect,
+                                                   justify, y_align, line_spacing);
+                let lines_with_rects = lines.zip(line_rects);
+                let mut search_rects = Vec::new();
+                for m in search_matches(&text, query) {
+                    let m_start = text::cursor::index_before_char(line_infos.clone(), m.start);
+                    let m_end = text::cursor::index_before_char(line_infos.clone(), m.end);
+                    if let (Some(m_start), Some(m_end)) = (m_start, m_end) {
+                        let is_selected = m_start == start && m_end == end;
+                        let rects = text::line::selected_rects(lines_with_rects.clone(), font,
+                                                               font_size, m_start, m_end);
+                        search_rects.extend(rects.map(|r| (r, is_selected)));
+                    }
+                }
+                search_rects
+            };
+
+            // Ensure we have at least as many widgets as search_rectangles.
+            if state.ids.search_rectangles.len() < search_rects.len() {
+                let num_rects = search_rects.len();
+                let id_gen = &mut ui.widget_id_generator();
+                state.update(|state| state.ids.search_rectangles.resize(num_rects, id_gen));
+            }
+
+            // Draw a semi-transparent `Rectangle` over each match, emphasising the selected match.
+            let search_color = style.search_color(ui.theme());
+            let iter = state.ids.search_rectangles.iter().zip(&search_rects);
+            for (&search_rectangle_id, &(search_rect, is_selected)) in iter {
+                let alpha = if is_selected { 0.6 } else { 0.3 };
+                widget::Rectangle::fill(search_rect.dim())
+                    .xy(search_rect.xy())
+                    .color(search_color.alpha(alpha))
+                    .graphics_for(id)
+                    .parent(id)
+                    .set(search_rectangle_id, ui);
+            }
+
+            // If navigation selected a new match, ensure that it is visible.
+            if let Some(m) = maybe_match_to_reveal {
+                let font = ui.fonts.get(font_id).unwrap();
+                let line_infos = state.line_infos.iter().cloned();
+                let m_start = text::cursor::index_before_char(line_infos.clone(), m.start);
+                let m_end = text::cursor::index_before_char(line_infos.clone(), m.end);
+                if let (Some(m_start), Some(m_end)) = (m_start, m_end) {
+                    let lines = line_infos.clone().map(|info| &text[info.byte_range()]);
+                    let line_rects = text::line::rects(line_infos, font_size, rect,
+                                                       justify, y_align, line_spacing);
+                    let maybe_rect = text::line::selected_rects(lines.zip(line_rects), font,
+                                                                font_size, m_start, m_end)
+                        .fold(None, |acc: Option<Rect>, r| match acc {
+                            Some(acc) => Some(acc.max(r)),
+                            None => Some(r),
+                        });
+                    if let Some(match_rect) = maybe_rect {
+                        scroll_into_view(id, match_rect, ui);
+                    }
+                }
+            }
+        }
+
         // Draw the line for the cursor.
         let cursor_idx = match cursor {
             Cursor::Idx(idx) => idx,
@@ -822,21 +1070,7 @@ impl<'a> Widget for TextEdit<'a> {
         if cursor_has_changed {
             let cursor_rect = ui.rect_of(state.ids.cursor).unwrap();
             if prev_cursor_rect != Some(cursor_rect) {
-                use graph::Walker;
-                let mut scrollable_parents = ui.widget_graph().scrollable_y_parent_recursion(id);
-                if let Some(parent_id) = scrollable_parents.next_node(ui.widget_graph()) {
-                    if let Some(parent_rect) = ui.rect_of(parent_id) {
-                        // If cursor is below, scroll down.
-                        if cursor_rect.bottom() < parent_rect.bottom() {
-                            let distance = parent_rect.bottom() - cursor_rect.bottom();
-                            ui.scroll_widget(parent_id, [0.0, distance]);
-                        // If cursor is above, scroll up.
-                        } else if cursor_rect.top() > parent_rect.top() {
-                            let distance = cursor_rect.top() - parent_rect.top();
-                            ui.scroll_widget(parent_id, [0.0, -distance]);
-                        }
-                    }
-                }
+                scroll_into_view(id, cursor_rect, ui);
             }
         }
 
@@ -879,6 +1113,54 @@ impl<'a> Widget for TextEdit<'a> {
 }
 
 
+/// The position of the cursor within the **TextEdit** widget with the given `id`.
+///
+/// Returns `None` if there is no **TextEdit** for the given `id`.
+pub fn cursor(id: widget::Id, ui: &Ui) -> Option<Cursor> {
+    ui.widget_graph()
+        .widget(id)
+        .and_then(|container| container.state_and_style::<State, Style>())
+        .map(|&graph::UniqueWidgetState { ref state, .. }| state.cursor)
+}
+
+
+/// The char index ranges of all non-overlapping occurrences of `query` within `text`.
+fn search_matches(text: &str, query: &str) -> Vec<std::ops::Range<usize>> {
+    let mut matches = Vec::new();
+    if query.is_empty() {
+        return matches;
+    }
+    let query_char_count = query.chars().count();
+    let (mut prev_byte, mut prev_char) = (0, 0);
+    for (byte, _) in text.match_indices(query) {
+        prev_char += text[prev_byte..byte].chars().count();
+        prev_byte = byte;
+        matches.push(prev_char..prev_char + query_char_count);
+    }
+    matches
+}
+
+/// If the given `rect` would fall outside the first scrollable parent of the widget with the given
+/// `id`, scroll the parent so that the `rect` is visible.
+fn scroll_into_view(id: widget::Id, rect: Rect, ui: &mut UiCell) {
+    use graph::Walker;
+    let mut scrollable_parents = ui.widget_graph().scrollable_y_parent_recursion(id);
+    if let Some(parent_id) = scrollable_parents.next_node(ui.widget_graph()) {
+        if let Some(parent_rect) = ui.rect_of(parent_id) {
+            // If the rect is below, scroll down.
+            if rect.bottom() < parent_rect.bottom() {
+                let distance = parent_rect.bottom() - rect.bottom();
+                ui.scroll_widget(parent_id, [0.0, distance]);
+            // If the rect is above, scroll up.
+            } else if rect.top() > parent_rect.top() {
+                let distance = rect.top() - parent_rect.top();
+                ui.scroll_widget(parent_id, [0.0, -distance]);
+            }
+        }
+    }
+}
+
+
 impl<'a> Colorable for TextEdit<'a> {
     builder_method!(color { style.color = Some(Color) });
 }
@@ -1,6 +1,7 @@
 //! A button that allows for toggling boolean state.
 
-use {Color, Colorable, FontSize, Borderable, Labelable, Positionable, Scalar, Widget};
+use {Color, Colorable, Dimensions, FontSize, Borderable, Labelable, Positionable, Scalar, Ui,
+     Widget};
 use position::{self, Align};
 use text;
 use widget;
@@ -143,6 +144,21 @@ impl<'a> Widget for Toggle<'a> {
         self.style.clone()
     }
 
+    fn content_wh(&self, ui: &Ui) -> Option<Dimensions> {
+        let font_size = self.style.label_font_size(&ui.theme);
+        let maybe_font = self.style.label_font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id));
+        match (self.maybe_label, maybe_font) {
+            (Some(label), Some(font)) => Some(text::dimensions(label, font, font_size, 0.0)),
+            _ => Some([0.0, 0.0]),
+        }
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
     /// Update the state of the Toggle.
     fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
         let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
@@ -0,0 +1,168 @@
+//! A small box of text describing the widget beneath the mouse cursor.
+
+use {Color, Colorable, Borderable, FontSize, Positionable, Scalar, Ui, Widget};
+use position::Dimension;
+use std;
+use text;
+use utils;
+use widget;
+
+
+/// A small box of text, normally displayed near the mouse cursor while it hovers over a widget.
+///
+/// The `Ui` displays a `Tooltip` for any widget whose text was registered via `UiCell::tooltip`
+/// once the mouse has rested over the widget for the `theme.tooltip_delay`. A `Tooltip` may also
+/// be instantiated directly like any other widget.
+///
+/// By default, the `Tooltip` is sized to fit its text along with its `padding`.
+#[derive(Clone, WidgetCommon_)]
+pub struct Tooltip<'a> {
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+    text: &'a str,
+    style: Style,
+}
+
+/// Unique styling for the `Tooltip`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, WidgetStyle_)]
+pub struct Style {
+    /// Color of the `Tooltip`'s background.
+    #[conrod(default = "theme.shape_color")]
+    pub color: Option<Color>,
+    /// The width of the border surrounding the `Tooltip`.
+    #[conrod(default = "theme.border_width")]
+    pub border: Option<Scalar>,
+    /// The color of the `Tooltip`'s border.
+    #[conrod(default = "theme.border_color")]
+    pub border_color: Option<Color>,
+    /// The color of the `Tooltip`'s text.
+    #[conrod(default = "theme.label_color")]
+    pub text_color: Option<Color>,
+    /// The font size of the `Tooltip`'s text.
+    #[conrod(default = "theme.font_size_small")]
+    pub font_size: Option<FontSize>,
+    /// The ID of the font used to display the text.
+    #[conrod(default = "theme.font_id")]
+    pub font_id: Option<Option<text::font::Id>>,
+    /// The distance between the text and the edges of the `Tooltip`.
+    #[conrod(default = "6.0")]
+    pub padding: Option<Scalar>,
+}
+
+widget_ids! {
+    struct Ids {
+        rectangle,
+        text,
+    }
+}
+
+/// The state of the `Tooltip`.
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Tooltip<'a> {
+
+    /// Construct a new `Tooltip` displaying the given text.
+    pub fn new(text: &'a str) -> Self {
+        Tooltip {
+            common: widget::CommonBuilder::default(),
+            text: text,
+            style: Style::default(),
+        }
+    }
+
+    /// Specify the font used for displaying the text.
+    pub fn font_id(mut self, font_id: text::font::Id) -> Self {
+        self.style.font_id = Some(Some(font_id));
+        self
+    }
+
+    builder_methods!{
+        pub text_color { style.text_color = Some(Color) }
+        pub font_size { style.font_size = Some(FontSize) }
+        pub padding { style.padding = Some(Scalar) }
+    }
+
+    /// The dimensions required to fit the `Tooltip`'s text along with its padding.
+    ///
+    /// Returns `None` if the font used by the `Tooltip` cannot be found.
+    pub fn text_dimensions(&self, ui: &Ui) -> Option<[Scalar; 2]> {
+        let font = match self.style.font_id(&ui.theme)
+            .or(ui.fonts.ids().next())
+            .and_then(|id| ui.fonts.get(id))
+        {
+            Some(font) => font,
+            None => return None,
+        };
+        let font_size = self.style.font_size(&ui.theme);
+        let padding = self.style.padding(&ui.theme);
+        let mut max_w = 0.0;
+        let mut num_lines = 0;
+        for line in self.text.lines() {
+            max_w = utils::partial_max(max_w, text::line::width(line, font, font_size));
+            num_lines += 1;
+        }
+        let h = text::height(std::cmp::max(num_lines, 1), font_size, 1.0);
+        Some([max_w + padding * 2.0, h + padding * 2.0])
+    }
+
+}
+
+impl<'a> Widget for Tooltip<'a> {
+    type State = State;
+    type Style = Style;
+    type Event = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {
+        self.style.clone()
+    }
+
+    fn default_x_dimension(&self, ui: &Ui) -> Dimension {
+        Dimension::Absolute(self.text_dimensions(ui).map(|dim| dim[0]).unwrap_or(0.0))
+    }
+
+    fn default_y_dimension(&self, ui: &Ui) -> Dimension {
+        Dimension::Absolute(self.text_dimensions(ui).map(|dim| dim[1]).unwrap_or(0.0))
+    }
+
+    /// Update the state of the `Tooltip`.
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        let widget::UpdateArgs { id, state, style, rect, ui, .. } = args;
+
+        widget::BorderedRectangle::new(rect.dim())
+            .middle_of(id)
+            .graphics_for(id)
+            .color(style.color(ui.theme()))
+            .border(style.border(ui.theme()))
+            .border_color(style.border_color(ui.theme()))
+            .set(state.ids.rectangle, ui);
+
+        let font_id = style.font_id(&ui.theme).or(ui.fonts.ids().next());
+        widget::Text::new(self.text)
+            .and_then(font_id, widget::Text::font_id)
+            .middle_of(id)
+            .graphics_for(id)
+            .no_line_wrap()
+            .color(style.text_color(ui.theme()))
+            .font_size(style.font_size(ui.theme()))
+            .set(state.ids.text, ui);
+    }
+}
+
+impl<'a> Colorable for Tooltip<'a> {
+    builder_method!(color { style.color = Some(Color) });
+}
+
+impl<'a> Borderable for Tooltip<'a> {
+    builder_methods!{
+        border { style.border = Some(Scalar) }
+        border_color { style.border_color = Some(Color) }
+    }
+}